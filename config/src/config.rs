@@ -11,8 +11,8 @@ use crate::font::{
 };
 use crate::frontend::FrontEndSelection;
 use crate::keyassignment::{
-    KeyAssignment, KeyTable, KeyTableEntry, KeyTables, MouseEventTrigger, PaneEncoding,
-    SpawnCommand,
+    ClipboardCopyDestination, KeyAssignment, KeyTable, KeyTableEntry, KeyTables, MouseEventTrigger,
+    PaneEncoding, SpawnCommand,
 };
 use crate::keys::{DeferredKeyCode, Key, KeyNoAction, LeaderKey, Mouse};
 use crate::lua::make_lua_context;
@@ -464,6 +464,10 @@ pub struct Config {
     /// selection behavior is less surprising.
     #[dynamic(default = "default_true")]
     pub copy_on_select: bool,
+    /// Where copy on select puts the text. When unset, the destination
+    /// named by the mouse binding that completed the selection is used.
+    #[dynamic(default)]
+    pub copy_on_select_destination: Option<ClipboardCopyDestination>,
 
     #[dynamic(default)]
     pub daemon_options: DaemonOptions,
@@ -577,6 +581,13 @@ pub struct Config {
     #[dynamic(default = "default_split_thickness")]
     pub split_thickness: f32,
 
+    /// Extra pixels on each side of a split divider that still grab it
+    /// for mouse resizing. Only the hit-test area grows; the drawn line
+    /// keeps `split_thickness`.
+    /// Defaults to 4.0 pixels.
+    #[dynamic(default = "default_split_hit_test_padding")]
+    pub split_hit_test_padding: f32,
+
     #[dynamic(default)]
     pub window_content_alignment: WindowContentAlignment,
 
@@ -627,6 +638,11 @@ pub struct Config {
     #[dynamic(default = "default_one_point_oh")]
     pub window_background_opacity: f32,
 
+    /// How much `IncreaseBackgroundOpacity` and `DecreaseBackgroundOpacity`
+    /// change `window_background_opacity` per invocation.
+    #[dynamic(default = "default_background_opacity_step")]
+    pub background_opacity_step: f32,
+
     /// inactive_pane_hue, inactive_pane_saturation and
     /// inactive_pane_brightness allow for transforming the color
     /// of inactive panes.
@@ -891,6 +907,11 @@ pub struct Config {
     #[dynamic(default)]
     pub bell_dock_badge: bool,
 
+    /// Make `kaku config` write every setting it manages, defaults included,
+    /// instead of removing lines that match the default.
+    #[dynamic(default)]
+    pub kaku_config_explicit_defaults: bool,
+
     #[dynamic(default)]
     pub canonicalize_pasted_newlines: Option<NewlineCanon>,
 
@@ -930,6 +951,34 @@ pub struct Config {
     #[dynamic(default)]
     pub quote_dropped_files: DroppedFileQuoting,
 
+    /// How dropped or pasted files are written to the terminal. `Path`
+    /// pastes the path quoted per `quote_dropped_files`; `FileUri` and
+    /// `MarkdownLink` paste a `file://` URI or a `[name](file://...)` link
+    /// instead, for note-taking workflows.
+    #[dynamic(default)]
+    pub dropped_file_format: DroppedFileFormat,
+
+    /// How an image on the clipboard is pasted. `Path` writes it to a temp
+    /// file and pastes that path; `Inline` sends the raw image bytes to the
+    /// program, for tools that read an image from stdin. Inline applies only
+    /// when the clipboard holds a single image: a list of copied files is
+    /// always pasted as paths, even if some of them are images.
+    ///
+    /// Inline bytes bypass bracketed paste, so only use it with programs that
+    /// read binary input. At a shell prompt the image's control bytes act as
+    /// keystrokes, including Enter, Ctrl+C and Ctrl+D.
+    #[dynamic(default)]
+    pub clipboard_image_paste_mode: ClipboardImagePasteMode,
+
+    /// Clipboard text longer than this many bytes is cut short on paste and
+    /// ends with a marker, so a huge clipboard cannot stall the paste.
+    #[dynamic(default = "default_max_clipboard_text_bytes")]
+    pub max_clipboard_text_bytes: usize,
+
+    /// Where in the window in-app toasts such as "Copied" are shown.
+    #[dynamic(default)]
+    pub toast_position: ToastPosition,
+
     #[dynamic(default)]
     pub ui_key_cap_rendering: UIKeyCapRendering,
 
@@ -1903,6 +1952,14 @@ fn default_split_thickness() -> f32 {
     2.0
 }
 
+fn default_split_hit_test_padding() -> f32 {
+    4.0
+}
+
+fn default_background_opacity_step() -> f32 {
+    0.05
+}
+
 fn default_integrated_title_buttons() -> Vec<IntegratedTitleButton> {
     use IntegratedTitleButton::*;
     vec![Hide, Maximize, Close]
@@ -2454,6 +2511,40 @@ impl DroppedFileQuoting {
     }
 }
 
+#[derive(Debug, Default, FromDynamic, ToDynamic, Clone, Copy, PartialEq, Eq)]
+pub enum DroppedFileFormat {
+    /// The path itself, quoted per `quote_dropped_files`
+    #[default]
+    Path,
+    /// A percent-encoded `file://` URI
+    FileUri,
+    /// A markdown link named after the file, pointing at its `file://` URI
+    MarkdownLink,
+}
+
+#[derive(Debug, Default, FromDynamic, ToDynamic, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardImagePasteMode {
+    /// Paste the path of a temp file holding the image
+    #[default]
+    Path,
+    /// Send the image bytes as-is; only for programs that read binary stdin
+    Inline,
+}
+
+#[derive(Debug, Default, FromDynamic, ToDynamic, Clone, Copy, PartialEq, Eq)]
+pub enum ToastPosition {
+    TopRight,
+    TopCenter,
+    #[default]
+    BottomRight,
+    BottomCenter,
+    Center,
+}
+
+fn default_max_clipboard_text_bytes() -> usize {
+    16 * 1024 * 1024
+}
+
 fn default_glyph_cache_image_cache_size() -> usize {
     256
 }
@@ -186,6 +186,9 @@ pub enum PaneEncoding {
     #[default]
     Utf8,
     Gbk,
+    /// Decodes exactly like `Gbk`, which is its superset; kept as its own
+    /// entry for tools and users that expect the GB2312 label.
+    Gb2312,
     Gb18030,
     Big5,
     EucKr,
@@ -206,6 +209,7 @@ impl FromStr for PaneEncoding {
         match normalized.as_str() {
             "utf-8" | "utf8" => Ok(Self::Utf8),
             "gbk" => Ok(Self::Gbk),
+            "gb2312" | "gb-2312" => Ok(Self::Gb2312),
             "gb18030" => Ok(Self::Gb18030),
             "big5" => Ok(Self::Big5),
             "euc-kr" | "euckr" => Ok(Self::EucKr),
@@ -216,9 +220,12 @@ impl FromStr for PaneEncoding {
 }
 
 impl PaneEncoding {
-    const DEFAULT_ORDER: [Self; 6] = [
+    /// Every encoding in menu order. Sizes and loops derive from this, so a
+    /// new variant only needs adding here and to the `match`es below.
+    const DEFAULT_ORDER: &'static [Self] = &[
         Self::Utf8,
         Self::Gbk,
+        Self::Gb2312,
         Self::Gb18030,
         Self::Big5,
         Self::EucKr,
@@ -233,6 +240,7 @@ impl PaneEncoding {
             Self::Big5 => 3,
             Self::EucKr => 4,
             Self::ShiftJis => 5,
+            Self::Gb2312 => 6,
         }
     }
 
@@ -243,6 +251,7 @@ impl PaneEncoding {
             3 => Self::Big5,
             4 => Self::EucKr,
             5 => Self::ShiftJis,
+            6 => Self::Gb2312,
             _ => Self::Utf8,
         }
     }
@@ -258,7 +267,7 @@ impl PaneEncoding {
         result.push(Self::Utf8);
         result.push(last_selected);
 
-        for encoding in Self::DEFAULT_ORDER {
+        for &encoding in Self::DEFAULT_ORDER {
             if encoding != Self::Utf8 && encoding != last_selected {
                 result.push(encoding);
             }
@@ -275,6 +284,7 @@ impl PaneEncoding {
         match self {
             Self::Utf8 => "UTF-8",
             Self::Gbk => "GBK",
+            Self::Gb2312 => "GB2312",
             Self::Gb18030 => "GB18030",
             Self::Big5 => "Big5",
             Self::EucKr => "EUC-KR",
@@ -342,21 +352,108 @@ impl std::fmt::Display for SpawnCommand {
         if let Some(encoding) = &self.encoding {
             write!(fmt, " encoding={encoding}")?;
         }
+        if let Some(position) = &self.position {
+            write!(fmt, " position={position}")?;
+        }
         Ok(())
     }
 }
 
+/// Largest distance from the origin, in pixels, points or cells, that a
+/// spawned window may be placed at. Generous enough for any monitor layout.
+const MAX_SPAWN_COORDINATE: f32 = 100_000.;
+
+/// Clamps a spawn coordinate into a usable range, or returns None when it is
+/// not a number at all.
+fn validate_spawn_dimension(dim: crate::Dimension) -> Option<crate::Dimension> {
+    use crate::Dimension;
+    let (value, clamped) = match dim {
+        Dimension::Percent(n) => (n, n.clamp(0., 1.)),
+        Dimension::Pixels(n) | Dimension::Points(n) | Dimension::Cells(n) => {
+            (n, n.clamp(-MAX_SPAWN_COORDINATE, MAX_SPAWN_COORDINATE))
+        }
+    };
+    if !value.is_finite() {
+        return None;
+    }
+    Some(match dim {
+        _ if clamped == value => dim,
+        Dimension::Percent(_) => Dimension::Percent(clamped),
+        Dimension::Pixels(_) => Dimension::Pixels(clamped),
+        Dimension::Points(_) => Dimension::Points(clamped),
+        Dimension::Cells(_) => Dimension::Cells(clamped),
+    })
+}
+
+fn validate_spawn_position(position: crate::GuiPosition) -> Option<crate::GuiPosition> {
+    let (Some(x), Some(y)) = (
+        validate_spawn_dimension(position.x),
+        validate_spawn_dimension(position.y),
+    ) else {
+        log::warn!("ignoring spawn position {position}: coordinates are not numbers");
+        return None;
+    };
+    let validated = crate::GuiPosition {
+        x,
+        y,
+        origin: position.origin.clone(),
+    };
+    if validated != position {
+        log::warn!("clamped spawn position {position} to {validated}");
+    }
+    Some(validated)
+}
+
 impl SpawnCommand {
+    /// Returns a label for the launcher/palette: the explicit label, else the
+    /// command line, else the final component of `cwd`, else the domain name.
+    /// Returns None for the default shell in the default or current domain,
+    /// so callers can show their own generic description.
     pub fn label_for_palette(&self) -> Option<String> {
         if let Some(label) = &self.label {
             Some(label.to_string())
         } else if let Some(args) = &self.args {
             Some(shlex::try_join(args.iter().map(|s| s.as_str())).ok()?)
+        } else if let Some(name) = self
+            .cwd
+            .as_ref()
+            .and_then(|cwd| cwd.file_name())
+            .and_then(|name| name.to_str())
+        {
+            Some(name.to_string())
+        } else if let SpawnTabDomain::DomainName(name) = &self.domain {
+            Some(name.to_string())
         } else {
             None
         }
     }
 
+    /// Spawn the domain's default program (typically the user's shell)
+    /// with `cwd` as its working directory, in the current pane's domain.
+    pub fn in_directory(cwd: PathBuf) -> Self {
+        Self {
+            cwd: Some(cwd),
+            domain: SpawnTabDomain::CurrentPaneDomain,
+            ..Self::default()
+        }
+    }
+
+    /// Spawn the default program in a new window placed at `position`.
+    /// Out of range coordinates are clamped and unusable ones dropped,
+    /// see `validated_position`.
+    pub fn at_position(position: crate::GuiPosition) -> Self {
+        Self {
+            position: validate_spawn_position(position),
+            ..Self::default()
+        }
+    }
+
+    /// The requested window position after the same checks as `at_position`,
+    /// for positions that arrived through Lua rather than the constructor.
+    pub fn validated_position(&self) -> Option<crate::GuiPosition> {
+        self.position.clone().and_then(validate_spawn_position)
+    }
+
     pub fn from_command_builder(cmd: &CommandBuilder) -> anyhow::Result<Self> {
         let mut args = vec![];
         let mut set_environment_variables = HashMap::new();
@@ -661,6 +758,11 @@ pub enum KeyAssignment {
         text: String,
         destination: ClipboardCopyDestination,
     },
+    /// Copy the active pane's current working directory.
+    CopyPaneCwd(ClipboardCopyDestination),
+    /// Copy the text of the most recent command output zone reported by
+    /// shell integration.
+    CopyLastCommandOutput(ClipboardCopyDestination),
     PasteFrom(ClipboardPasteSource),
     ActivateTabRelative(isize),
     ActivateTabRelativeNoWrap(isize),
@@ -668,6 +770,9 @@ pub enum KeyAssignment {
     DecreaseFontSize,
     ResetFontSize,
     ResetFontAndWindowSize,
+    IncreaseBackgroundOpacity,
+    DecreaseBackgroundOpacity,
+    ResetBackgroundOpacity,
     ActivateTab(isize),
     ActivateLastTab,
     ToggleCurrentTabPanesInputBroadcast,
@@ -683,6 +788,9 @@ pub enum KeyAssignment {
     },
     ReopenLastClosedTab,
     ReloadConfiguration,
+    /// Open the directory holding the Kaku config file in the system
+    /// file manager.
+    OpenConfigDirectory,
     MoveTabRelative(isize),
     MoveTab(usize),
     ScrollByPage(NotNan<f64>),
@@ -694,7 +802,13 @@ pub enum KeyAssignment {
     ShowTabNavigator,
     ShowDebugOverlay,
     HideApplication,
-    QuitApplication,
+    /// Quit the application. `confirm` forces the quit confirmation
+    /// prompt even when `window_close_confirmation` is `NeverPrompt`.
+    /// The bare `QuitApplication` form is still accepted.
+    QuitApplication {
+        #[dynamic(default)]
+        confirm: bool,
+    },
     SpawnCommandInNewTab(SpawnCommand),
     SpawnCommandInNewWindow(SpawnCommand),
     SplitHorizontal(SpawnCommand),
@@ -719,6 +833,7 @@ pub enum KeyAssignment {
     TogglePaneZoomState,
     SetPaneZoomState(bool),
     SetPaneEncoding(PaneEncoding),
+    ShowEncodingPicker,
     CloseCurrentPane {
         confirm: bool,
     },
@@ -859,8 +974,12 @@ pub struct KeyTableEntry {
 
 #[cfg(test)]
 mod tests {
-    use super::PaneEncoding;
+    use super::{KeyAssignment, PaneEncoding, SpawnCommand, SpawnTabDomain};
+    use crate::{Dimension, GeometryOrigin, GuiPosition};
+    use std::path::PathBuf;
+    use std::str::FromStr;
     use std::sync::Mutex;
+    use wezterm_dynamic::{FromDynamic, ToDynamic, Value};
 
     lazy_static::lazy_static! {
         static ref TEST_LOCK: Mutex<()> = Mutex::new(());
@@ -876,6 +995,7 @@ mod tests {
             vec![
                 PaneEncoding::Utf8,
                 PaneEncoding::Gbk,
+                PaneEncoding::Gb2312,
                 PaneEncoding::Gb18030,
                 PaneEncoding::Big5,
                 PaneEncoding::EucKr,
@@ -890,6 +1010,7 @@ mod tests {
                 PaneEncoding::Utf8,
                 PaneEncoding::Big5,
                 PaneEncoding::Gbk,
+                PaneEncoding::Gb2312,
                 PaneEncoding::Gb18030,
                 PaneEncoding::EucKr,
                 PaneEncoding::ShiftJis,
@@ -897,6 +1018,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pane_encoding_order_lists_every_encoding_once() {
+        let _guard = TEST_LOCK.lock().expect("TEST_LOCK mutex poisoned");
+
+        for &selected in PaneEncoding::DEFAULT_ORDER {
+            assert_eq!(PaneEncoding::from_u8(selected.to_u8()), selected);
+
+            PaneEncoding::set_last_selected(selected);
+            let list = PaneEncoding::ordered_list();
+            assert_eq!(list.len(), PaneEncoding::DEFAULT_ORDER.len());
+            assert_eq!(list[0], PaneEncoding::Utf8);
+            for encoding in PaneEncoding::DEFAULT_ORDER {
+                assert_eq!(list.iter().filter(|e| *e == encoding).count(), 1);
+            }
+        }
+
+        PaneEncoding::set_last_selected(PaneEncoding::Utf8);
+    }
+
+    #[test]
+    fn test_pane_encoding_gb2312_is_its_own_entry() {
+        let _guard = TEST_LOCK.lock().expect("TEST_LOCK mutex poisoned");
+
+        assert_eq!(PaneEncoding::Gb2312.to_string(), "GB2312");
+        assert_eq!(PaneEncoding::from_str("gb2312"), Ok(PaneEncoding::Gb2312));
+        assert_eq!(
+            PaneEncoding::from_str(PaneEncoding::Gb2312.as_str()),
+            Ok(PaneEncoding::Gb2312)
+        );
+        assert_eq!(
+            PaneEncoding::from_u8(PaneEncoding::Gb2312.to_u8()),
+            PaneEncoding::Gb2312
+        );
+
+        PaneEncoding::set_last_selected(PaneEncoding::Gb2312);
+        let list = PaneEncoding::ordered_list();
+        assert_eq!(list[1], PaneEncoding::Gb2312);
+        assert_eq!(list.iter().filter(|e| **e == PaneEncoding::Gbk).count(), 1);
+
+        PaneEncoding::set_last_selected(PaneEncoding::Utf8);
+    }
+
     #[test]
     fn test_pane_encoding_selection_persistence() {
         let _guard = TEST_LOCK.lock().expect("TEST_LOCK mutex poisoned");
@@ -909,4 +1072,97 @@ mod tests {
 
         PaneEncoding::set_last_selected(PaneEncoding::Utf8);
     }
+
+    #[test]
+    fn test_spawn_command_in_directory() {
+        let cmd = SpawnCommand::in_directory(PathBuf::from("/tmp/project"));
+        assert_eq!(cmd.args, None);
+        assert_eq!(cmd.domain, SpawnTabDomain::CurrentPaneDomain);
+        assert_eq!(
+            cmd.to_string(),
+            "SpawnCommand domain=CurrentPaneDomain cwd=/tmp/project"
+        );
+    }
+
+    #[test]
+    fn test_spawn_command_display_includes_position() {
+        let cmd = SpawnCommand::default();
+        assert_eq!(cmd.to_string(), "SpawnCommand domain=CurrentPaneDomain");
+
+        let cmd = SpawnCommand::at_position(GuiPosition::from_str("main:10,20").unwrap());
+        assert_eq!(
+            cmd.to_string(),
+            "SpawnCommand domain=CurrentPaneDomain position=main:10px,20px"
+        );
+    }
+
+    #[test]
+    fn test_spawn_command_at_position_clamps_or_drops() {
+        let cmd = SpawnCommand::at_position(GuiPosition {
+            x: Dimension::Percent(1.5),
+            y: Dimension::Pixels(-1e9),
+            origin: GeometryOrigin::ActiveScreen,
+        });
+        assert_eq!(
+            cmd.position,
+            Some(GuiPosition {
+                x: Dimension::Percent(1.),
+                y: Dimension::Pixels(-100_000.),
+                origin: GeometryOrigin::ActiveScreen,
+            })
+        );
+
+        let cmd = SpawnCommand::at_position(GuiPosition {
+            x: Dimension::Pixels(f32::NAN),
+            y: Dimension::Pixels(0.),
+            origin: GeometryOrigin::MainScreen,
+        });
+        assert_eq!(cmd.position, None);
+    }
+
+    #[test]
+    fn test_spawn_command_label_prefers_label_then_args() {
+        let mut cmd = SpawnCommand {
+            args: Some(vec!["htop".to_string(), "-d".to_string(), "10".to_string()]),
+            cwd: Some(PathBuf::from("/tmp/project")),
+            ..SpawnCommand::default()
+        };
+        assert_eq!(cmd.label_for_palette().as_deref(), Some("htop -d 10"));
+
+        cmd.label = Some("Monitor".to_string());
+        assert_eq!(cmd.label_for_palette().as_deref(), Some("Monitor"));
+    }
+
+    #[test]
+    fn test_spawn_command_label_falls_back_to_cwd() {
+        let cmd = SpawnCommand::in_directory(PathBuf::from("/Users/me/myproject"));
+        assert_eq!(cmd.label_for_palette().as_deref(), Some("myproject"));
+    }
+
+    #[test]
+    fn test_spawn_command_label_falls_back_to_domain_name() {
+        let cmd = SpawnCommand {
+            domain: SpawnTabDomain::DomainName("devbox".to_string()),
+            ..SpawnCommand::default()
+        };
+        assert_eq!(cmd.label_for_palette().as_deref(), Some("devbox"));
+
+        assert_eq!(SpawnCommand::default().label_for_palette(), None);
+    }
+
+    #[test]
+    fn test_quit_application_accepts_bare_and_confirm_forms() {
+        let bare = KeyAssignment::from_dynamic(
+            &Value::String("QuitApplication".to_string()),
+            Default::default(),
+        )
+        .unwrap();
+        assert_eq!(bare, KeyAssignment::QuitApplication { confirm: false });
+
+        let confirm = KeyAssignment::QuitApplication { confirm: true };
+        assert_eq!(
+            KeyAssignment::from_dynamic(&confirm.to_dynamic(), Default::default()).unwrap(),
+            confirm
+        );
+    }
 }
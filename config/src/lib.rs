@@ -693,7 +693,9 @@ fn write_new_file_atomic(path: &Path, data: &[u8]) -> anyhow::Result<()> {
     .with_context(|| format!("allocate temporary config file for {}", path.display()))
 }
 
-fn minimal_user_config_template() -> &'static str {
+/// The starter `kaku.lua`: loads the bundled defaults into `config` and
+/// returns it, leaving room for user overrides.
+pub fn minimal_user_config_template() -> &'static str {
     r#"local wezterm = require 'wezterm'
 
 local function resolve_bundled_config()
@@ -801,6 +803,32 @@ return config
 "#
 }
 
+/// Labels of the fields shown by the `kaku config` settings TUI, in display
+/// order. The GUI lists these in the command palette and passes one back via
+/// `kaku config --field` to open the TUI on that field.
+pub const SETTINGS_FIELD_LABELS: &[&str] = &[
+    "Theme",
+    "Font",
+    "Ligatures",
+    "Font Size",
+    "Line Height",
+    "Global Hotkey",
+    "Kaku Assistant",
+    "Tab Bar Position",
+    "Tab Bar Visibility",
+    "Scrollbar",
+    "Shadow",
+    "Background Opacity",
+    "Background Blur",
+    "Padding",
+    "Copy on Select",
+    "Copy Destination",
+    "Confirm Tab Close",
+    "Confirm Pane Close",
+    "Bell Tab Indicator",
+    "Bell Dock Badge",
+];
+
 fn xdg_config_home_from(home_dir: &Path, xdg_config_home: Option<OsString>) -> PathBuf {
     // Normalize empty env values to "unset" to preserve HOME/.config fallback behavior.
     xdg_config_home
@@ -139,15 +139,20 @@ impl Default for Dimension {
     }
 }
 
+impl std::fmt::Display for Dimension {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Points(n) => write!(fmt, "{}pt", n),
+            Self::Pixels(n) => write!(fmt, "{}px", n),
+            Self::Percent(n) => write!(fmt, "{}%", n * 100.),
+            Self::Cells(n) => write!(fmt, "{}cell", n),
+        }
+    }
+}
+
 impl ToDynamic for Dimension {
     fn to_dynamic(&self) -> Value {
-        let s = match self {
-            Self::Points(n) => format!("{}pt", n),
-            Self::Pixels(n) => format!("{}px", n),
-            Self::Percent(n) => format!("{}%", n * 100.),
-            Self::Cells(n) => format!("{}cell", n),
-        };
-        Value::String(s)
+        Value::String(self.to_string())
     }
 }
 
@@ -229,6 +234,19 @@ impl GuiPosition {
     }
 }
 
+/// Formats as the `origin:x,y` spec that `FromStr` accepts.
+impl std::fmt::Display for GuiPosition {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.origin {
+            GeometryOrigin::ScreenCoordinateSystem => write!(fmt, "screen")?,
+            GeometryOrigin::MainScreen => write!(fmt, "main")?,
+            GeometryOrigin::ActiveScreen => write!(fmt, "active")?,
+            GeometryOrigin::Named(name) => write!(fmt, "{}", name)?,
+        }
+        write!(fmt, ":{},{}", self.x, self.y)
+    }
+}
+
 impl FromStr for GuiPosition {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> anyhow::Result<GuiPosition> {
@@ -287,6 +305,13 @@ mod test {
         );
     }
 
+    #[test]
+    fn display_round_trips() {
+        for spec in ["screen:10px,20px", "main:50%,0px", "hdmi-1:-1920px,0px"] {
+            assert_eq!(GuiPosition::from_str(spec).unwrap().to_string(), spec);
+        }
+    }
+
     #[test]
     fn active() {
         assert_eq!(
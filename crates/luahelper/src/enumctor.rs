@@ -132,15 +132,20 @@ where
 
         methods.add_meta_method(MetaMethod::Index, |lua, _myself, field: String| {
             // Step 1: see if this is a unit variant.
-            // A unit variant will be convertible from string
-            if let Ok(_) = T::from_dynamic(
+            // A unit variant will be convertible from string, and will
+            // convert back to that same string. Struct variants whose
+            // fields are all defaulted also accept the bare string, but
+            // they need the table form below so that they remain callable.
+            if let Ok(unit) = T::from_dynamic(
                 &DynValue::String(field.to_string()),
                 FromDynamicOptions {
                     unknown_fields: UnknownFieldAction::Deny,
                     deprecated_fields: UnknownFieldAction::Ignore,
                 },
             ) {
-                return Ok(field.into_lua(lua)?);
+                if let DynValue::String(_) = unit.to_dynamic() {
+                    return Ok(field.into_lua(lua)?);
+                }
             }
 
             // Step 2: see if this is a valid variant, and whether we can
@@ -176,6 +176,38 @@ fn derive_enum(input: &DeriveInput, enumeration: &DataEnum) -> Result<TokenStrea
                         }
                         ))
                     }
+                    Fields::Named(fields) => {
+                        // A struct variant whose fields can all be defaulted
+                        // may also be spelled as its bare name, which allows
+                        // adding optional fields to what used to be a unit
+                        // variant without breaking existing configs.
+                        let infos = fields
+                            .named
+                            .iter()
+                            .map(|f| attr::field_info(f).unwrap())
+                            .collect::<Vec<_>>();
+                        if infos.iter().any(|info| {
+                            info.skip
+                                || info.flatten
+                                || matches!(info.allow_default, attr::DefValue::None)
+                        }) {
+                            return None;
+                        }
+                        let ident = &variant.ident;
+                        let literal = ident.to_string();
+                        let var_fields = infos
+                            .iter()
+                            .map(|info| info.field_from_dynamic(&literal))
+                            .collect::<Vec<_>>();
+                        Some(quote!(
+                        #literal => {
+                            let obj = &wezterm_dynamic::Object::default();
+                            return Ok(Self::#ident {
+                                #( #var_fields )*
+                            });
+                        }
+                        ))
+                    }
                     _ => None,
                 })
                 .collect::<Vec<_>>();
@@ -135,6 +135,44 @@ fn named_variants() {
     );
 }
 
+#[derive(FromDynamic, Debug, PartialEq)]
+enum NamedDefaults {
+    A {
+        #[dynamic(default)]
+        confirm: bool,
+    },
+    B {
+        required: bool,
+    },
+}
+
+#[test]
+fn named_variant_from_bare_name() {
+    assert_eq!(
+        NamedDefaults::A { confirm: false },
+        NamedDefaults::from_dynamic(&Value::String("A".to_string()), Default::default()).unwrap()
+    );
+    assert_eq!(
+        NamedDefaults::A { confirm: true },
+        NamedDefaults::from_dynamic(
+            &Value::Object(
+                btreemap!(
+                    "A".to_dynamic() => Value::Object(
+                        btreemap!(
+                            "confirm".to_dynamic() => Value::Bool(true),
+                        ).into())
+                )
+                .into()
+            ),
+            Default::default()
+        )
+        .unwrap()
+    );
+    assert!(
+        NamedDefaults::from_dynamic(&Value::String("B".to_string()), Default::default()).is_err()
+    );
+}
+
 #[derive(FromDynamic, Debug, PartialEq)]
 enum UnNamed {
     A(f32, f32, f32, f32),
@@ -236,6 +236,9 @@ pub fn show_notif(toast: ToastNotification) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+/// Opened instead when no Kaku binaries are found to run the update with.
+const KAKU_RELEASES_URL: &str = "https://github.com/tw93/Kaku/releases/latest";
+
 fn spawn_kaku_update() {
     std::thread::spawn(|| {
         // Find kaku-gui in the current app bundle or /Applications
@@ -258,13 +261,24 @@ fn spawn_kaku_update() {
                 std::path::PathBuf::from("/Applications/Kaku.app/Contents/MacOS/kaku")
             });
 
+        if !kaku_cli.exists() {
+            log::error!("spawn_kaku_update: kaku CLI not found at {:?}", kaku_cli);
+            wezterm_open_url::open_url(KAKU_RELEASES_URL);
+            return;
+        }
+
         let result = std::process::Command::new(&kaku_gui)
             .args(["start", "--", kaku_cli.to_str().unwrap_or("kaku"), "update"])
             .spawn();
 
         match result {
             Ok(_) => log::info!("spawn_kaku_update: process spawned successfully"),
-            Err(e) => log::error!("spawn_kaku_update: failed to spawn: {}", e),
+            Err(e) => {
+                log::error!("spawn_kaku_update: failed to spawn: {}", e);
+                // The user asked for the update; point them at the download
+                // rather than doing nothing.
+                wezterm_open_url::open_url(KAKU_RELEASES_URL);
+            }
         }
     });
 }
@@ -231,6 +231,9 @@ impl CommandDef {
             IncreaseFontSize,
             DecreaseFontSize,
             ResetFontAndWindowSize,
+            IncreaseBackgroundOpacity,
+            DecreaseBackgroundOpacity,
+            ResetBackgroundOpacity,
             ScrollToTop,
             ScrollToBottom,
             // Window menu
@@ -467,9 +470,42 @@ impl CommandDef {
                 .then_with(|| a.brief.cmp(&b.brief))
         });
 
+        // Encodings follow, most recently selected first, so "encoding"
+        // searches surface the usual choices.
+        Self::append_encoding_commands(&mut deduped, config);
+
+        // One entry per settings TUI field, so "Settings: Font Size" jumps
+        // straight to that field.
+        for label in config::SETTINGS_FIELD_LABELS {
+            let event = format!(
+                "{}{}",
+                crate::frontend::OPEN_KAKU_CONFIG_FIELD_EVENT_PREFIX,
+                label
+            );
+            if let Some(command) = Self::expand_action(EmitEvent(event), config, true) {
+                deduped.push(command);
+            }
+        }
+
         deduped
     }
 
+    /// Appends one entry per encoding in `PaneEncoding::ordered_list` order.
+    /// An encoding already listed because the user bound a key to it moves
+    /// into that order, keeping its keys, rather than showing up twice.
+    fn append_encoding_commands(commands: &mut Vec<ExpandedCommand>, config: &ConfigHandle) {
+        for encoding in PaneEncoding::ordered_list() {
+            let action = KeyAssignment::SetPaneEncoding(encoding);
+            let command = match commands.iter().position(|cmd| cmd.action == action) {
+                Some(idx) => Some(commands.remove(idx)),
+                None => Self::expand_action(action, config, true),
+            };
+            if let Some(command) = command {
+                commands.push(command);
+            }
+        }
+    }
+
     pub fn actions_for_palette_and_menubar(config: &ConfigHandle) -> Vec<ExpandedCommand> {
         let mut result = Self::expanded_commands(config);
 
@@ -702,7 +738,7 @@ impl CommandDef {
             match title {
                 "Kaku" => match action {
                     HideApplication => 80,
-                    QuitApplication => 90,
+                    QuitApplication { .. } => 90,
                     _ => 500,
                 },
                 "Shell" => match action {
@@ -733,6 +769,9 @@ impl CommandDef {
                     IncreaseFontSize => 20,
                     DecreaseFontSize => 30,
                     ResetFontAndWindowSize => 40,
+                    IncreaseBackgroundOpacity => 41,
+                    DecreaseBackgroundOpacity => 42,
+                    ResetBackgroundOpacity => 43,
                     ScrollToTop => 50,
                     ScrollToBottom => 51,
                     _ => 500,
@@ -1107,6 +1146,23 @@ pub fn derive_command_from_key_assignment(action: &KeyAssignment) -> Option<Comm
             menubar: &[],
             icon: None,
         },
+        CopyPaneCwd(destination) => CommandDef {
+            brief: "Copy working directory".into(),
+            doc: format!("Copies the current pane's working directory to {destination:?}").into(),
+            keys: vec![],
+            args: &[ArgType::ActivePane],
+            menubar: &[],
+            icon: None,
+        },
+        CopyLastCommandOutput(destination) => CommandDef {
+            brief: "Copy last command output".into(),
+            doc: format!("Copies the output of the most recent command to {destination:?}")
+                .into(),
+            keys: vec![],
+            args: &[ArgType::ActivePane],
+            menubar: &[],
+            icon: None,
+        },
         PasteFrom(ClipboardPasteSource::Clipboard) => CommandDef {
             brief: "Paste from clipboard".into(),
             doc: "Pastes text from the clipboard".into(),
@@ -1392,6 +1448,30 @@ pub fn derive_command_from_key_assignment(action: &KeyAssignment) -> Option<Comm
             menubar: &["View"],
             icon: None,
         },
+        IncreaseBackgroundOpacity => CommandDef {
+            brief: "Increase Background Opacity".into(),
+            doc: "Make the window background more opaque".into(),
+            keys: vec![],
+            args: &[ArgType::ActiveWindow],
+            menubar: &["View"],
+            icon: None,
+        },
+        DecreaseBackgroundOpacity => CommandDef {
+            brief: "Decrease Background Opacity".into(),
+            doc: "Make the window background more transparent".into(),
+            keys: vec![],
+            args: &[ArgType::ActiveWindow],
+            menubar: &["View"],
+            icon: None,
+        },
+        ResetBackgroundOpacity => CommandDef {
+            brief: "Reset Background Opacity".into(),
+            doc: "Reset to configured background opacity".into(),
+            keys: vec![],
+            args: &[ArgType::ActiveWindow],
+            menubar: &["View"],
+            icon: None,
+        },
         SpawnTab(SpawnTabDomain::CurrentPaneDomain) => CommandDef {
             brief: "New Tab".into(),
             doc: "Open a new tab".into(),
@@ -1502,13 +1582,21 @@ pub fn derive_command_from_key_assignment(action: &KeyAssignment) -> Option<Comm
             icon: None,
         },
         SetPaneEncoding(encoding) => CommandDef {
-            brief: format!("Set Pane Encoding to {encoding}").into(),
+            brief: format!("Set Encoding: {encoding}").into(),
             doc: format!("Sets the current pane encoding to {encoding}").into(),
             keys: vec![],
             args: &[ArgType::ActivePane],
             menubar: &[],
             icon: None,
         },
+        ShowEncodingPicker => CommandDef {
+            brief: "Select Encoding".into(),
+            doc: "Choose the current pane encoding from a list, most recent first".into(),
+            keys: vec![],
+            args: &[ArgType::ActivePane],
+            menubar: &[],
+            icon: None,
+        },
         EmitEvent(name) => {
             if name == "run-kaku-ai-config" {
                 CommandDef {
@@ -1546,6 +1634,17 @@ pub fn derive_command_from_key_assignment(action: &KeyAssignment) -> Option<Comm
                     menubar: &["Shell"],
                     icon: None,
                 }
+            } else if let Some(field) =
+                name.strip_prefix(crate::frontend::OPEN_KAKU_CONFIG_FIELD_EVENT_PREFIX)
+            {
+                CommandDef {
+                    brief: format!("Settings: {field}").into(),
+                    doc: format!("Open Kaku settings at {field}").into(),
+                    keys: vec![],
+                    args: &[ArgType::ActiveWindow],
+                    menubar: &[],
+                    icon: None,
+                }
             } else if name == "kaku-open-remote-files" {
                 CommandDef {
                     brief: "Remote Files".into(),
@@ -1758,6 +1857,14 @@ pub fn derive_command_from_key_assignment(action: &KeyAssignment) -> Option<Comm
                 icon: None,
             }
         }
+        OpenConfigDirectory => CommandDef {
+            brief: "Open Config Directory".into(),
+            doc: "Show the folder containing the Kaku config file".into(),
+            keys: vec![],
+            args: &[ArgType::ActiveWindow],
+            menubar: &[],
+            icon: None,
+        },
         ReloadConfiguration => CommandDef {
             brief: "Reload configuration (disabled)".into(),
             doc: "Manual reload is disabled; configuration changes are reloaded automatically."
@@ -1767,7 +1874,7 @@ pub fn derive_command_from_key_assignment(action: &KeyAssignment) -> Option<Comm
             menubar: &[],
             icon: None,
         },
-        QuitApplication => CommandDef {
+        QuitApplication { .. } => CommandDef {
             brief: "Quit Kaku".into(),
             doc: "Quits Kaku".into(),
             keys: vec![(Modifiers::SUPER, "q".into())],
@@ -2503,7 +2610,7 @@ fn compute_default_actions() -> Vec<KeyAssignment> {
         #[cfg(target_os = "macos")]
         HideApplication,
         #[cfg(target_os = "macos")]
-        QuitApplication,
+        QuitApplication { confirm: false },
         // ----------------- Shell
         SpawnTab(SpawnTabDomain::CurrentPaneDomain),
         SpawnWindow,
@@ -2531,6 +2638,8 @@ fn compute_default_actions() -> Vec<KeyAssignment> {
         CopyTo(ClipboardCopyDestination::PrimarySelection),
         CopyTo(ClipboardCopyDestination::Clipboard),
         PasteFrom(ClipboardPasteSource::Clipboard),
+        CopyPaneCwd(ClipboardCopyDestination::Clipboard),
+        CopyLastCommandOutput(ClipboardCopyDestination::Clipboard),
         ClearScrollback(ScrollbackEraseMode::ScrollbackOnly),
         ClearScrollback(ScrollbackEraseMode::ScrollbackAndViewport),
         QuickSelect,
@@ -2543,6 +2652,9 @@ fn compute_default_actions() -> Vec<KeyAssignment> {
         IncreaseFontSize,
         ResetFontSize,
         ResetFontAndWindowSize,
+        IncreaseBackgroundOpacity,
+        DecreaseBackgroundOpacity,
+        ResetBackgroundOpacity,
         ScrollByPage(NotNan::new(-1.0).unwrap()),
         ScrollByPage(NotNan::new(1.0).unwrap()),
         ScrollToTop,
@@ -2613,6 +2725,8 @@ fn compute_default_actions() -> Vec<KeyAssignment> {
         ShowDebugOverlay,
         // ----------------- Misc
         OpenLinkAtMouseCursor,
+        OpenConfigDirectory,
+        ShowEncodingPicker,
     ];
 
     actions.extend(
@@ -2626,8 +2740,8 @@ fn compute_default_actions() -> Vec<KeyAssignment> {
 
 #[cfg(test)]
 mod tests {
-    use super::{derive_command_from_key_assignment, CommandDef};
-    use config::keyassignment::KeyAssignment;
+    use super::{derive_command_from_key_assignment, CommandDef, ExpandedCommand};
+    use config::keyassignment::{ClipboardCopyDestination, KeyAssignment, PaneEncoding};
     use config::ConfigHandle;
     use window::Modifiers;
 
@@ -2671,4 +2785,81 @@ mod tests {
             .iter()
             .any(|(_, _, action)| *action == KeyAssignment::ToggleAllPanesInputBroadcast));
     }
+
+    #[test]
+    fn set_pane_encoding_is_labeled_for_palette_search() {
+        let cmd =
+            derive_command_from_key_assignment(&KeyAssignment::SetPaneEncoding(PaneEncoding::Gbk))
+                .expect("command");
+
+        assert_eq!(cmd.brief, "Set Encoding: GBK");
+    }
+
+    #[test]
+    fn bound_encoding_is_listed_once_with_its_keys() {
+        let config = ConfigHandle::default_config();
+        let action = KeyAssignment::SetPaneEncoding(PaneEncoding::Gbk);
+        let keys = vec![(Modifiers::SUPER.union(Modifiers::ALT), "g".into())];
+        let mut commands = vec![ExpandedCommand {
+            brief: "Set Encoding: GBK".into(),
+            doc: "".into(),
+            action: action.clone(),
+            keys: keys.clone(),
+            menubar: &[],
+            icon: None,
+        }];
+
+        CommandDef::append_encoding_commands(&mut commands, &config);
+
+        let bound: Vec<_> = commands.iter().filter(|cmd| cmd.action == action).collect();
+        assert_eq!(bound.len(), 1);
+        assert_eq!(bound[0].keys, keys);
+        assert_eq!(commands.len(), PaneEncoding::ordered_list().len());
+    }
+
+    #[test]
+    fn show_encoding_picker_is_in_default_assignments() {
+        let config = ConfigHandle::default_config();
+
+        assert!(CommandDef::default_key_assignments(&config)
+            .iter()
+            .any(|(_, _, action)| *action == KeyAssignment::ShowEncodingPicker));
+    }
+
+    #[test]
+    fn settings_field_events_are_labeled_for_palette_search() {
+        let event = format!(
+            "{}Font Size",
+            crate::frontend::OPEN_KAKU_CONFIG_FIELD_EVENT_PREFIX
+        );
+        let cmd =
+            derive_command_from_key_assignment(&KeyAssignment::EmitEvent(event)).expect("command");
+
+        assert_eq!(cmd.brief, "Settings: Font Size");
+        assert!(cmd.keys.is_empty());
+    }
+
+    #[test]
+    fn copy_pane_cwd_has_palette_command() {
+        let cmd = derive_command_from_key_assignment(&KeyAssignment::CopyPaneCwd(
+            ClipboardCopyDestination::Clipboard,
+        ))
+        .expect("command");
+
+        assert_eq!(cmd.brief, "Copy working directory");
+        assert!(cmd.keys.is_empty());
+    }
+
+    #[test]
+    fn copy_last_command_output_has_palette_command() {
+        let cmd = derive_command_from_key_assignment(&KeyAssignment::CopyLastCommandOutput(
+            ClipboardCopyDestination::Clipboard,
+        ))
+        .expect("command");
+
+        assert_eq!(cmd.brief, "Copy last command output");
+        assert!(cmd
+            .doc
+            .contains("output of the most recent command to Clipboard"));
+    }
 }
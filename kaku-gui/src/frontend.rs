@@ -19,6 +19,9 @@ use wezterm_term::{Alert, ClipboardSelection};
 use wezterm_toast_notification::*;
 
 pub const SET_DEFAULT_TERMINAL_EVENT: &str = "set-default-terminal";
+/// Prefix of the palette events that open the settings TUI on one field;
+/// the field label follows the prefix.
+pub const OPEN_KAKU_CONFIG_FIELD_EVENT_PREFIX: &str = "open-kaku-config-field:";
 
 pub struct GuiFrontEnd {
     connection: Rc<Connection>,
@@ -133,7 +136,17 @@ pub(crate) fn kaku_cli_program_for_spawn() -> String {
 }
 
 pub fn open_kaku_config() {
+    open_kaku_config_at_field(None);
+}
+
+/// Opens the settings TUI in a new tab, with the cursor on `field` if given.
+pub fn open_kaku_config_at_field(field: Option<&str>) {
     let kaku_bin = kaku_cli_program_for_spawn();
+    let mut args = vec![kaku_bin, "config".to_string()];
+    if let Some(field) = field {
+        args.push("--field".to_string());
+        args.push(field.to_string());
+    }
 
     promise::spawn::spawn_into_main_thread(async move {
         let config = fast_config_snapshot();
@@ -143,7 +156,7 @@ pub fn open_kaku_config() {
         crate::spawn::spawn_command_impl(
             &SpawnCommand {
                 domain: SpawnTabDomain::DomainName("local".to_string()),
-                args: Some(vec![kaku_bin, "config".to_string()]),
+                args: Some(args),
                 ..Default::default()
             },
             SpawnWhere::NewTab,
@@ -642,13 +655,20 @@ impl GuiFrontEnd {
                     KeyAssignment::EmitEvent(event) if event == "open-kaku-config" => {
                         open_kaku_config();
                     }
+                    KeyAssignment::EmitEvent(event)
+                        if event.starts_with(OPEN_KAKU_CONFIG_FIELD_EVENT_PREFIX) =>
+                    {
+                        open_kaku_config_at_field(
+                            event.strip_prefix(OPEN_KAKU_CONFIG_FIELD_EVENT_PREFIX),
+                        );
+                    }
                     KeyAssignment::EmitEvent(event) if event == SET_DEFAULT_TERMINAL_EVENT => {
                         set_default_terminal_with_feedback();
                     }
                     KeyAssignment::ReloadConfiguration => {
                         // Manual reload is intentionally disabled.
                     }
-                    KeyAssignment::QuitApplication => {
+                    KeyAssignment::QuitApplication { .. } => {
                         // If we get here, there are no windows that could have received
                         // the QuitApplication command, therefore it must be ok to quit
                         // immediately
@@ -762,7 +762,7 @@ fn run_terminal_gui(opts: StartCommand, default_domain_name: Option<String>) ->
     startup_trace::mark("GuiFrontEnd::try_new() start");
     let gui = crate::frontend::try_new()?;
     startup_trace::mark("GuiFrontEnd::try_new() done");
-    let activity = Activity::new();
+    let activity = Activity::with_reason("gui startup");
 
     promise::spawn::spawn(async move {
         if let Err(err) = async_run_terminal_gui(cmd, opts, publish.should_publish()).await {
@@ -215,12 +215,23 @@ pub fn show_debug_overlay(
     let version = config::wezterm_version();
     let triple = config::wezterm_target_triple();
     let mut doctor_snapshot = PendingDoctorSnapshot::spawn();
+    let activity_reasons = mux::activity::Activity::current_reasons();
+    let activity_info = if activity_reasons.is_empty() {
+        mux::activity::Activity::count().to_string()
+    } else {
+        format!(
+            "{} ({})",
+            mux::activity::Activity::count(),
+            activity_reasons.join(", ")
+        )
+    };
 
     term.render(&[Change::Text(format!(
         "Kaku Doctor\r\n\
          Kaku version: {version} {triple}\r\n\
          Window Environment: {connection_info}\r\n\
          Lua Version: {lua_version}\r\n\
+         Pending Activities: {activity_info}\r\n\
          {opengl_info}\r\n\
          {}\
          Enter lua statements or expressions and hit Enter.\r\n\
@@ -44,7 +44,7 @@ pub async fn spawn_command_internal(
     term_config: Arc<TermConfig>,
 ) -> anyhow::Result<()> {
     let mux = Mux::get();
-    let activity = Activity::new();
+    let activity = Activity::with_reason("spawn command");
 
     let current_pane_id = match src_window_id {
         Some(window_id) => {
@@ -135,6 +135,7 @@ pub async fn spawn_command_internal(
             }
         }
         _ => {
+            let position = spawn.validated_position();
             let (_tab, pane, window_id) = mux
                 .spawn_tab_or_window(
                     match spawn_where {
@@ -148,7 +149,7 @@ pub async fn spawn_command_internal(
                     size,
                     current_pane_id,
                     workspace,
-                    spawn.position,
+                    position,
                 )
                 .await
                 .context("spawn_tab_or_window")?;
@@ -1,12 +1,15 @@
+use crate::selection::{SelectionCoordinate, SelectionRange};
 use crate::termwindow::TermWindowNotif;
 use crate::TermWindow;
 use config::keyassignment::{ClipboardCopyDestination, ClipboardPasteSource};
-use mux::pane::Pane;
+use mux::pane::{CachePolicy, Pane};
 use smol::Timer;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use wezterm_term::SemanticType;
 use wezterm_toast_notification::persistent_toast_notification;
 use window::{Clipboard, ClipboardData, WindowOps};
 
@@ -56,24 +59,78 @@ impl TermWindow {
         }
     }
 
+    /// Copy the pane's working directory as reported by OSC 7 or process
+    /// tracking; toasts instead of copying an empty string when unknown.
+    pub fn copy_pane_cwd(&mut self, pane: &Arc<dyn Pane>, clipboard: ClipboardCopyDestination) {
+        let cwd = pane
+            .get_current_working_dir(CachePolicy::AllowStale)
+            .and_then(|url| url.to_file_path().ok());
+        match cwd {
+            Some(cwd) => {
+                self.copy_to_clipboard(clipboard, cwd.to_string_lossy().into_owned());
+                self.show_copy_toast();
+            }
+            None => self.show_toast("Working directory unknown".to_string()),
+        }
+    }
+
+    /// Copy the most recent `Output` semantic zone; toasts instead when shell
+    /// integration has not marked any command output yet.
+    pub fn copy_last_command_output(
+        &mut self,
+        pane: &Arc<dyn Pane>,
+        clipboard: ClipboardCopyDestination,
+    ) {
+        let text = pane
+            .get_semantic_zones()
+            .ok()
+            .and_then(|zones| {
+                zones
+                    .into_iter()
+                    .rev()
+                    .find(|zone| zone.semantic_type == SemanticType::Output)
+            })
+            .map(|zone| {
+                let range = SelectionRange {
+                    start: SelectionCoordinate::x_y(zone.start_x, zone.start_y),
+                    end: SelectionCoordinate::x_y(zone.end_x, zone.end_y),
+                };
+                Self::range_text(pane, &range.normalize(), false)
+            })
+            .filter(|text| !text.trim().is_empty());
+        match text {
+            Some(text) => {
+                self.copy_to_clipboard(clipboard, text);
+                self.show_copy_toast();
+            }
+            None => self.show_toast("No command output found".to_string()),
+        }
+    }
+
     fn show_toast_internal(&mut self, message: String, lifetime: Duration) {
         let now = Instant::now();
         let fade_after = lifetime.saturating_sub(Duration::from_millis(500));
-        self.toast = Some((now, message, lifetime));
+        self.toast = Some((now, now + fade_after, message, lifetime));
         if let Some(window) = self.window.clone() {
             let win = window.clone();
-            // Trigger fade-out during the last 500ms.
+            // Kick off the fade-out during the last 500ms; paint_toast keeps
+            // scheduling frames until the alpha reaches zero.
             let fade_win = win.clone();
             promise::spawn::spawn(async move {
                 Timer::after(fade_after).await;
-                fade_win.invalidate();
+                let notify_win = fade_win.clone();
+                notify_win.notify(TermWindowNotif::Apply(Box::new(move |tw| {
+                    if matches!(&tw.toast, Some((toast_time, ..)) if *toast_time == now) {
+                        fade_win.invalidate();
+                    }
+                })));
             })
             .detach();
             // Clear when lifetime expires.
             promise::spawn::spawn(async move {
                 Timer::after(lifetime).await;
                 window.notify(TermWindowNotif::Apply(Box::new(move |tw| {
-                    if let Some((toast_time, _, _)) = &tw.toast {
+                    if let Some((toast_time, ..)) = &tw.toast {
                         if *toast_time == now {
                             tw.toast = None;
                         }
@@ -164,12 +221,35 @@ impl TermWindow {
             ClipboardPasteSource::PrimarySelection => Clipboard::PrimarySelection,
         };
         let quote_dropped_files = self.config.quote_dropped_files;
+        let dropped_file_format = self.config.dropped_file_format;
+        let image_paste_mode = self.config.clipboard_image_paste_mode;
         let future = window.get_clipboard_data(clipboard);
         promise::spawn::spawn(async move {
             match future.await {
                 Ok(data) => {
                     window.notify(TermWindowNotif::Apply(Box::new(move |_myself| {
-                        let clip = match data_to_paste_string(data, quote_dropped_files) {
+                        if image_paste_mode == config::ClipboardImagePasteMode::Inline {
+                            if let Some(path) = inline_clipboard_image_path(&data) {
+                                // The image can be tens of MiB and the write blocks until
+                                // the program drains the pty, so keep it off the GUI thread.
+                                std::thread::spawn(move || {
+                                    paste_inline_image(
+                                        &path,
+                                        data,
+                                        &targets,
+                                        quote_dropped_files,
+                                        dropped_file_format,
+                                    )
+                                });
+                                return;
+                            }
+                        }
+
+                        let clip = match data_to_paste_string(
+                            data,
+                            quote_dropped_files,
+                            dropped_file_format,
+                        ) {
                             Some(clip) => clip,
                             None => return,
                         };
@@ -194,9 +274,65 @@ impl TermWindow {
     }
 }
 
+/// The image to send for `ClipboardImagePasteMode::Inline`. Only a single
+/// image read from the clipboard qualifies; copied files, including several
+/// images, fall through to the path paste.
+fn inline_clipboard_image_path(data: &ClipboardData) -> Option<PathBuf> {
+    let ClipboardData::Files(paths) = data else {
+        return None;
+    };
+    let [path] = paths.as_slice() else {
+        return None;
+    };
+    if !window::is_clipboard_image_path(path) {
+        return None;
+    }
+    Some(path.clone())
+}
+
+/// Writes the image bytes to each pane, unbracketed and without encoding, as
+/// the receiving program expects binary on stdin. A failed read falls back to
+/// pasting the path. Runs on a background thread.
+fn paste_inline_image(
+    path: &Path,
+    data: ClipboardData,
+    targets: &[Arc<dyn Pane>],
+    quote_dropped_files: config::DroppedFileQuoting,
+    dropped_file_format: config::DroppedFileFormat,
+) {
+    let image = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            log::warn!("failed to read clipboard image {}: {err:#}", path.display());
+            let Some(clip) = data_to_paste_string(data, quote_dropped_files, dropped_file_format)
+            else {
+                return;
+            };
+            for pane in targets {
+                if let Err(err) = pane.send_paste(&clip) {
+                    log::warn!(
+                        "failed to paste clipboard content into pane {}: {err:#}",
+                        pane.pane_id()
+                    );
+                }
+            }
+            return;
+        }
+    };
+    for pane in targets {
+        if let Err(err) = pane.writer().write_all(&image) {
+            log::warn!(
+                "failed to paste clipboard image into pane {}: {err:#}",
+                pane.pane_id()
+            );
+        }
+    }
+}
+
 fn data_to_paste_string(
     data: ClipboardData,
     quote_dropped_files: config::DroppedFileQuoting,
+    dropped_file_format: config::DroppedFileFormat,
 ) -> Option<String> {
     match data {
         ClipboardData::Text(text) => Some(text),
@@ -204,7 +340,11 @@ fn data_to_paste_string(
             if paths.is_empty() {
                 return None;
             }
-            Some(format_dropped_paths(paths, quote_dropped_files))
+            Some(format_dropped_paths(
+                paths,
+                quote_dropped_files,
+                dropped_file_format,
+            ))
         }
     }
 }
@@ -212,10 +352,14 @@ fn data_to_paste_string(
 fn format_dropped_paths(
     paths: Vec<PathBuf>,
     quote_dropped_files: config::DroppedFileQuoting,
+    dropped_file_format: config::DroppedFileFormat,
 ) -> String {
     paths
         .iter()
-        .map(|path| quote_path_for_clipboard_paste(path, quote_dropped_files))
+        .map(|path| {
+            format_file_link(path, dropped_file_format)
+                .unwrap_or_else(|| quote_path_for_clipboard_paste(path, quote_dropped_files))
+        })
         .collect::<Vec<_>>()
         .join(" ")
         + " " // Trailing space so the shell treats this as ready-to-append arguments.
@@ -249,3 +393,124 @@ fn quote_path_for_clipboard_paste(
         }
     }
 }
+
+/// `path` as a `file://` URI, percent-encoded so spaces survive. Relative
+/// paths cannot form a URI and are used as-is after the scheme.
+fn file_uri(path: &Path) -> String {
+    url::Url::from_file_path(path)
+        .map(String::from)
+        .unwrap_or_else(|()| format!("file://{}", path.to_string_lossy()))
+}
+
+/// Renders a dropped or pasted file as a URI or markdown link, or returns
+/// None for `DroppedFileFormat::Path`, whose quoting is up to the caller.
+pub(crate) fn format_file_link(path: &Path, format: config::DroppedFileFormat) -> Option<String> {
+    match format {
+        config::DroppedFileFormat::Path => None,
+        config::DroppedFileFormat::FileUri => Some(file_uri(path)),
+        config::DroppedFileFormat::MarkdownLink => {
+            let name = path
+                .file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+                .replace('\\', "\\\\")
+                .replace('[', "\\[")
+                .replace(']', "\\]");
+            // Unbalanced parentheses would end the link target early.
+            let target = file_uri(path).replace('(', "%28").replace(')', "%29");
+            Some(format!("[{name}]({target})"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_dropped_paths, format_file_link, inline_clipboard_image_path};
+    use config::{DroppedFileFormat, DroppedFileQuoting};
+    use std::path::{Path, PathBuf};
+    use window::ClipboardData;
+
+    fn clipboard_image(name: &str) -> PathBuf {
+        config::RUNTIME_DIR
+            .join(window::CLIPBOARD_IMAGE_DIR)
+            .join(name)
+    }
+
+    #[test]
+    fn file_uri_format_percent_encodes_spaces() {
+        assert_eq!(
+            format_file_link(
+                Path::new("/tmp/My Notes/a b.md"),
+                DroppedFileFormat::FileUri
+            )
+            .as_deref(),
+            Some("file:///tmp/My%20Notes/a%20b.md")
+        );
+    }
+
+    #[test]
+    fn markdown_format_links_file_name_to_uri() {
+        assert_eq!(
+            format_file_link(
+                Path::new("/tmp/My Notes/a b.md"),
+                DroppedFileFormat::MarkdownLink
+            )
+            .as_deref(),
+            Some("[a b.md](file:///tmp/My%20Notes/a%20b.md)")
+        );
+        assert_eq!(
+            format_file_link(
+                Path::new("/tmp/[draft] (v2).md"),
+                DroppedFileFormat::MarkdownLink
+            )
+            .as_deref(),
+            Some("[\\[draft\\] (v2).md](file:///tmp/[draft]%20%28v2%29.md)")
+        );
+    }
+
+    #[test]
+    fn path_format_keeps_existing_quoting() {
+        let paths = vec![PathBuf::from("/tmp/a b.png"), PathBuf::from("/tmp/c.md")];
+        assert_eq!(
+            format_dropped_paths(
+                paths.clone(),
+                DroppedFileQuoting::SpacesOnly,
+                DroppedFileFormat::Path
+            ),
+            "'/tmp/a b.png' /tmp/c.md "
+        );
+        assert_eq!(
+            format_dropped_paths(
+                paths,
+                DroppedFileQuoting::SpacesOnly,
+                DroppedFileFormat::MarkdownLink
+            ),
+            "[a b.png](file:///tmp/a%20b.png) [c.md](file:///tmp/c.md) "
+        );
+    }
+
+    #[test]
+    fn single_clipboard_image_is_sent_inline() {
+        let image = clipboard_image("kaku-1.png");
+        let data = ClipboardData::Files(vec![image.clone()]);
+        assert_eq!(inline_clipboard_image_path(&data), Some(image));
+    }
+
+    #[test]
+    fn several_clipboard_images_are_pasted_as_paths() {
+        let data = ClipboardData::Files(vec![
+            clipboard_image("kaku-1.png"),
+            clipboard_image("kaku-2.png"),
+        ]);
+        assert_eq!(inline_clipboard_image_path(&data), None);
+    }
+
+    #[test]
+    fn copied_files_and_text_are_pasted_as_paths() {
+        let data = ClipboardData::Files(vec![PathBuf::from("/tmp/photo.png")]);
+        assert_eq!(inline_clipboard_image_path(&data), None);
+
+        let data = ClipboardData::Text("hello".to_string());
+        assert_eq!(inline_clipboard_image_path(&data), None);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::termwindow::box_model::*;
+use crate::termwindow::modal::Modal;
+use crate::termwindow::render::corners::{
+    BOTTOM_LEFT_ROUNDED_CORNER, BOTTOM_RIGHT_ROUNDED_CORNER, TOP_LEFT_ROUNDED_CORNER,
+    TOP_RIGHT_ROUNDED_CORNER,
+};
+use crate::termwindow::DimensionContext;
+use crate::utilsprites::RenderMetrics;
+use crate::TermWindow;
+use config::keyassignment::{KeyAssignment, PaneEncoding};
+use config::Dimension;
+use std::cell::{Ref, RefCell};
+use wezterm_term::{KeyCode, KeyModifiers, MouseEvent};
+use window::color::LinearRgba;
+
+const SHORTCUTS: &str = "123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Returns the shortcut key for the row at `index`, if it has one.
+fn shortcut_for_index(index: usize) -> Option<char> {
+    SHORTCUTS.chars().nth(index)
+}
+
+/// Returns the row index addressed by the shortcut key `c`.
+fn index_for_shortcut(c: char) -> Option<usize> {
+    SHORTCUTS.chars().position(|s| s == c.to_ascii_lowercase())
+}
+
+pub struct EncodingSelector {
+    element: RefCell<Option<Vec<ComputedElement>>>,
+    encodings: Vec<PaneEncoding>,
+    active: PaneEncoding,
+    selected_row: RefCell<usize>,
+}
+
+impl EncodingSelector {
+    pub fn new(term_window: &mut TermWindow) -> Self {
+        // Re-read the recency order every time the picker opens so the
+        // most recently chosen encodings stay at the top.
+        let encodings = PaneEncoding::ordered_list();
+        let active = term_window
+            .get_active_pane_no_overlay()
+            .map(|pane| pane.get_encoding())
+            .unwrap_or_default();
+        let selected_row = encodings.iter().position(|&e| e == active).unwrap_or(0);
+
+        Self {
+            element: RefCell::new(None),
+            encodings,
+            active,
+            selected_row: RefCell::new(selected_row),
+        }
+    }
+
+    fn compute(
+        term_window: &mut TermWindow,
+        encodings: &[PaneEncoding],
+        active: PaneEncoding,
+        selected_row: usize,
+    ) -> anyhow::Result<Vec<ComputedElement>> {
+        let font = term_window
+            .fonts
+            .char_select_font()
+            .expect("to resolve char selection font");
+        let metrics = RenderMetrics::with_font_metrics(&font.metrics());
+
+        let top_bar_height = if term_window.show_tab_bar && !term_window.config.tab_bar_at_bottom {
+            term_window.tab_bar_pixel_height().unwrap()
+        } else {
+            0.
+        };
+        let (padding_left, padding_top) = term_window.padding_left_top();
+        let border = term_window.get_os_border();
+        let top_pixel_y = top_bar_height + padding_top + border.top.get() as f32;
+
+        let fg: InheritableColor = term_window.config.char_select_fg_color.to_linear().into();
+        let bg: InheritableColor = term_window.config.char_select_bg_color.to_linear().into();
+
+        let mut elements =
+            vec![
+                Element::new(&font, ElementContent::Text("Select Encoding".to_string()))
+                    .colors(ElementColors {
+                        border: BorderColor::default(),
+                        bg: LinearRgba::TRANSPARENT.into(),
+                        text: fg.clone(),
+                    })
+                    .display(DisplayType::Block),
+            ];
+
+        for (idx, &encoding) in encodings.iter().enumerate() {
+            let (row_bg, row_text) = if idx == selected_row {
+                (fg.clone(), bg.clone())
+            } else {
+                (LinearRgba::TRANSPARENT.into(), fg.clone())
+            };
+            let shortcut = shortcut_for_index(idx).unwrap_or(' ');
+            let marker = if encoding == active {
+                "  (current)"
+            } else {
+                ""
+            };
+            elements.push(
+                Element::new(
+                    &font,
+                    ElementContent::Text(format!("{shortcut}  {encoding}{marker}")),
+                )
+                .colors(ElementColors {
+                    border: BorderColor::default(),
+                    bg: row_bg,
+                    text: row_text,
+                })
+                .padding(BoxDimension {
+                    left: Dimension::Cells(0.25),
+                    right: Dimension::Cells(0.25),
+                    top: Dimension::Cells(0.),
+                    bottom: Dimension::Cells(0.),
+                })
+                .display(DisplayType::Block),
+            );
+        }
+
+        let element = Element::new(&font, ElementContent::Children(elements))
+            .colors(ElementColors {
+                border: BorderColor::new(
+                    term_window.config.char_select_bg_color.to_linear().into(),
+                ),
+                bg,
+                text: fg,
+            })
+            .margin(BoxDimension {
+                left: Dimension::Cells(1.25),
+                right: Dimension::Cells(1.25),
+                top: Dimension::Cells(1.25),
+                bottom: Dimension::Cells(1.25),
+            })
+            .padding(BoxDimension {
+                left: Dimension::Cells(0.25),
+                right: Dimension::Cells(0.25),
+                top: Dimension::Cells(0.25),
+                bottom: Dimension::Cells(0.25),
+            })
+            .border(BoxDimension::new(Dimension::Pixels(1.)))
+            .border_corners(Some(Corners {
+                top_left: SizedPoly {
+                    width: Dimension::Cells(0.25),
+                    height: Dimension::Cells(0.25),
+                    poly: TOP_LEFT_ROUNDED_CORNER,
+                },
+                top_right: SizedPoly {
+                    width: Dimension::Cells(0.25),
+                    height: Dimension::Cells(0.25),
+                    poly: TOP_RIGHT_ROUNDED_CORNER,
+                },
+                bottom_left: SizedPoly {
+                    width: Dimension::Cells(0.25),
+                    height: Dimension::Cells(0.25),
+                    poly: BOTTOM_LEFT_ROUNDED_CORNER,
+                },
+                bottom_right: SizedPoly {
+                    width: Dimension::Cells(0.25),
+                    height: Dimension::Cells(0.25),
+                    poly: BOTTOM_RIGHT_ROUNDED_CORNER,
+                },
+            }));
+
+        let dimensions = term_window.dimensions;
+        let size = term_window.terminal_size;
+
+        let computed = term_window.compute_element(
+            &LayoutContext {
+                height: DimensionContext {
+                    dpi: dimensions.dpi as f32,
+                    pixel_max: dimensions.pixel_height as f32,
+                    pixel_cell: metrics.cell_size.height as f32,
+                },
+                width: DimensionContext {
+                    dpi: dimensions.dpi as f32,
+                    pixel_max: dimensions.pixel_width as f32,
+                    pixel_cell: metrics.cell_size.width as f32,
+                },
+                bounds: euclid::rect(
+                    padding_left,
+                    top_pixel_y,
+                    size.cols as f32 * term_window.render_metrics.cell_size.width as f32,
+                    size.rows as f32 * term_window.render_metrics.cell_size.height as f32,
+                ),
+                metrics: &metrics,
+                gl_state: term_window.render_state.as_ref().unwrap(),
+                zindex: 100,
+            },
+            &element,
+        )?;
+
+        Ok(vec![computed])
+    }
+
+    fn perform_selection(&self, row: usize, term_window: &mut TermWindow) {
+        let encoding = match self.encodings.get(row) {
+            Some(&encoding) => encoding,
+            None => return,
+        };
+        term_window.cancel_modal();
+
+        // Route through the key assignment so the recency order is
+        // updated the same way as when picking from the palette.
+        if let Some(pane) = term_window.get_active_pane_no_overlay() {
+            if let Err(err) =
+                term_window.perform_key_assignment(&pane, &KeyAssignment::SetPaneEncoding(encoding))
+            {
+                log::error!("Error while setting encoding {encoding}: {err:#}");
+            }
+        }
+    }
+}
+
+impl Modal for EncodingSelector {
+    fn perform_assignment(
+        &self,
+        _assignment: &KeyAssignment,
+        _term_window: &mut TermWindow,
+    ) -> bool {
+        false
+    }
+
+    fn mouse_event(&self, _event: MouseEvent, _term_window: &mut TermWindow) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn key_down(
+        &self,
+        key: KeyCode,
+        mods: KeyModifiers,
+        term_window: &mut TermWindow,
+    ) -> anyhow::Result<bool> {
+        match (key, mods) {
+            (KeyCode::Escape, KeyModifiers::NONE) | (KeyCode::Char('g'), KeyModifiers::CTRL) => {
+                term_window.cancel_modal();
+                return Ok(true);
+            }
+            (KeyCode::UpArrow, KeyModifiers::NONE) | (KeyCode::Char('p'), KeyModifiers::CTRL) => {
+                let mut row = self.selected_row.borrow_mut();
+                *row = row.saturating_sub(1);
+            }
+            (KeyCode::DownArrow, KeyModifiers::NONE) | (KeyCode::Char('n'), KeyModifiers::CTRL) => {
+                let mut row = self.selected_row.borrow_mut();
+                *row = (*row + 1).min(self.encodings.len().saturating_sub(1));
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                let row = *self.selected_row.borrow();
+                self.perform_selection(row, term_window);
+                return Ok(true);
+            }
+            (KeyCode::Char(c), KeyModifiers::NONE) | (KeyCode::Char(c), KeyModifiers::SHIFT) => {
+                if let Some(row) = index_for_shortcut(c).filter(|&i| i < self.encodings.len()) {
+                    self.perform_selection(row, term_window);
+                }
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        }
+        term_window.invalidate_modal();
+        Ok(true)
+    }
+
+    fn computed_element(
+        &self,
+        term_window: &mut TermWindow,
+    ) -> anyhow::Result<Ref<'_, [ComputedElement]>> {
+        if self.element.borrow().is_none() {
+            let element = Self::compute(
+                term_window,
+                &self.encodings,
+                self.active,
+                *self.selected_row.borrow(),
+            )?;
+            self.element.borrow_mut().replace(element);
+        }
+        Ok(Ref::map(self.element.borrow(), |v| {
+            v.as_ref().unwrap().as_slice()
+        }))
+    }
+
+    fn reconfigure(&self, _term_window: &mut TermWindow) {
+        self.element.borrow_mut().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{index_for_shortcut, shortcut_for_index};
+
+    #[test]
+    fn shortcuts_use_digits_then_letters() {
+        assert_eq!(shortcut_for_index(0), Some('1'));
+        assert_eq!(shortcut_for_index(8), Some('9'));
+        assert_eq!(shortcut_for_index(9), Some('a'));
+        assert_eq!(shortcut_for_index(35), None);
+    }
+
+    #[test]
+    fn shortcut_lookup_round_trips_and_ignores_case() {
+        for idx in 0..20 {
+            let c = shortcut_for_index(idx).unwrap();
+            assert_eq!(index_for_shortcut(c), Some(idx));
+        }
+        assert_eq!(index_for_shortcut('A'), Some(9));
+        assert_eq!(index_for_shortcut('0'), None);
+    }
+}
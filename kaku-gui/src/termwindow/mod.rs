@@ -57,12 +57,12 @@ use mux_lua::MuxPane;
 use smol::channel::Sender;
 use smol::Timer;
 use std::cell::{RefCell, RefMut};
-use std::collections::{HashMap, LinkedList};
+use std::collections::{BTreeSet, HashMap, LinkedList};
 use std::ops::Add;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::rc::Rc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use termwiz::hyperlink::Hyperlink;
@@ -78,6 +78,7 @@ pub mod background;
 pub mod box_model;
 pub mod charselect;
 pub mod clipboard;
+pub mod encodingselect;
 pub mod keyevent;
 pub mod modal;
 mod mouseevent;
@@ -158,6 +159,85 @@ const VSCODE_OPEN_CANDIDATES: &[&str] = &[
 ];
 
 const TOP_TAB_LAYOUT_FULLSCREEN_STICKY_MS: u64 = 160;
+/// `KAKU_CONFIG_CHANGED` signals arriving within this window of the first
+/// one are coalesced into a single config reload.
+const CONFIG_CHANGED_DEBOUNCE_MS: u64 = 300;
+
+/// Set while a debounced `KAKU_CONFIG_CHANGED` reload is scheduled. Global
+/// because the reload itself is global, whichever window saw the signal.
+static CONFIG_CHANGED_RELOAD_QUEUED: AtomicBool = AtomicBool::new(false);
+
+/// Keys named by the `KAKU_CONFIG_CHANGED` signals folded into the queued
+/// reload.
+static CONFIG_CHANGED_PENDING_SCOPE: Mutex<Option<ConfigChangeScope>> = Mutex::new(None);
+
+/// Config generation produced by a reload whose changed keys are all in
+/// `LIGHT_RELOAD_KEYS`; windows reloading to it skip font and glyph work.
+static LIGHT_RELOAD_GENERATION: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Settings that never affect fonts or glyph rendering, so a reload that only
+/// changes these can keep the loaded fonts and texture atlas. `color_scheme`
+/// is not here: the atlas must be rebuilt to flush glyphs rendered for the
+/// old background.
+const LIGHT_RELOAD_KEYS: &[&str] = &[
+    "window_background_opacity",
+    "macos_window_background_blur",
+    "copy_on_select",
+    "copy_on_select_destination",
+    "tab_close_confirmation",
+    "pane_close_confirmation",
+    "bell_tab_indicator",
+    "bell_dock_badge",
+];
+
+/// Which settings a `KAKU_CONFIG_CHANGED` signal reports as changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConfigChangeScope {
+    /// Anything may have changed; older `kaku` builds always send `1`.
+    All,
+    Keys(BTreeSet<String>),
+}
+
+impl ConfigChangeScope {
+    /// Parses the decoded user var value: comma-separated `lua_key`s, with an
+    /// empty value or `1` meaning everything.
+    fn from_payload(value: &str) -> Self {
+        let value = value.trim();
+        if value.is_empty() || value == "1" {
+            return Self::All;
+        }
+        let keys: BTreeSet<String> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect();
+        if keys.is_empty() {
+            Self::All
+        } else {
+            Self::Keys(keys)
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Keys(mut a), Self::Keys(b)) => {
+                a.extend(b);
+                Self::Keys(a)
+            }
+            _ => Self::All,
+        }
+    }
+
+    fn is_light(&self) -> bool {
+        match self {
+            Self::All => false,
+            Self::Keys(keys) => keys
+                .iter()
+                .all(|key| LIGHT_RELOAD_KEYS.contains(&key.as_str())),
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 struct FileLinkTarget {
@@ -189,6 +269,12 @@ pub fn is_light_color(color: &wezterm_term::color::SrgbaTuple) -> bool {
     luminance > 0.5
 }
 
+/// Apply a background opacity step, clamped to 0.0-1.0 and rounded so
+/// repeated steps don't accumulate float drift.
+fn step_opacity(current: f32, delta: f32) -> f32 {
+    ((current + delta).clamp(0.0, 1.0) * 1000.0).round() / 1000.0
+}
+
 fn ai_toast_lifetime_ms(message: &str) -> u64 {
     let lower = message.to_ascii_lowercase();
     if lower.contains("checking")
@@ -843,8 +929,8 @@ pub struct TermWindow {
     layout_sticky_fullscreen_until: Option<Instant>,
     closed_tab_history: std::collections::VecDeque<PathBuf>,
 
-    /// Toast notification: (start_time, message, lifetime)
-    toast: Option<(Instant, String, Duration)>,
+    /// Toast notification: (start_time, fade_start, message, lifetime)
+    toast: Option<(Instant, Instant, String, Duration)>,
     selection_copy_disabled_hint_shown: bool,
 }
 
@@ -1149,6 +1235,73 @@ impl TermWindow {
         .detach();
     }
 
+    fn schedule_config_changed_reload(payload: &str) {
+        {
+            let scope = ConfigChangeScope::from_payload(payload);
+            let mut pending = CONFIG_CHANGED_PENDING_SCOPE.lock().unwrap();
+            *pending = Some(match pending.take() {
+                Some(queued) => queued.merge(scope),
+                None => scope,
+            });
+        }
+        if !Self::queue_config_changed_reload(&CONFIG_CHANGED_RELOAD_QUEUED) {
+            return;
+        }
+        promise::spawn::spawn_into_main_thread(async move {
+            Timer::after(Duration::from_millis(CONFIG_CHANGED_DEBOUNCE_MS)).await;
+            // Clear before reloading so a save landing during the reload
+            // schedules another one instead of being lost.
+            CONFIG_CHANGED_RELOAD_QUEUED.store(false, Ordering::SeqCst);
+            let scope = CONFIG_CHANGED_PENDING_SCOPE
+                .lock()
+                .unwrap()
+                .take()
+                .unwrap_or(ConfigChangeScope::All);
+            config::reload();
+            // Windows apply the reload from queued notifications, so they see
+            // this before acting on the new generation.
+            let light_generation = if scope.is_light() {
+                configuration().generation()
+            } else {
+                usize::MAX
+            };
+            LIGHT_RELOAD_GENERATION.store(light_generation, Ordering::SeqCst);
+        })
+        .detach();
+    }
+
+    /// Returns true when the caller should schedule the reload, false when
+    /// one is already pending.
+    fn queue_config_changed_reload(queued: &AtomicBool) -> bool {
+        !queued.swap(true, Ordering::SeqCst)
+    }
+
+    /// Step `window_background_opacity` by `delta` through this window's
+    /// config overrides, or drop the override when `delta` is `None`.
+    fn adjust_background_opacity(&mut self, window: &Window, delta: Option<f32>) {
+        let mut overrides = match &self.config_overrides {
+            Value::Object(obj) => obj.clone(),
+            _ => wezterm_dynamic::Object::default(),
+        };
+        let key = Value::String("window_background_opacity".to_string());
+        match delta {
+            Some(delta) => {
+                let opacity = step_opacity(self.config.window_background_opacity, delta);
+                overrides.insert(key, Value::F64((opacity as f64).into()));
+            }
+            None => {
+                if overrides.remove(&key).is_none() {
+                    return;
+                }
+            }
+        }
+        let value = Value::Object(overrides);
+        if value != self.config_overrides {
+            self.config_overrides = value;
+            self.schedule_silent_config_reload(window);
+        }
+    }
+
     fn schedule_silent_config_reload(&mut self, window: &Window) {
         if self.silent_reload_queued {
             return;
@@ -1712,9 +1865,12 @@ impl TermWindow {
                 let paths = paths
                     .iter()
                     .map(|path| {
-                        self.config
-                            .quote_dropped_files
-                            .escape(&path.to_string_lossy())
+                        clipboard::format_file_link(path, self.config.dropped_file_format)
+                            .unwrap_or_else(|| {
+                                self.config
+                                    .quote_dropped_files
+                                    .escape(&path.to_string_lossy())
+                            })
                     })
                     .collect::<Vec<_>>()
                     .join(" ")
@@ -2492,7 +2648,8 @@ impl TermWindow {
             return;
         }
 
-        self.config_was_reloaded_impl();
+        let light = configuration().generation() == LIGHT_RELOAD_GENERATION.load(Ordering::SeqCst);
+        self.config_was_reloaded_impl(light);
     }
 
     fn config_was_reloaded_silently(&mut self) {
@@ -2500,10 +2657,13 @@ impl TermWindow {
             self.pending_config_reload_after_resize = true;
             return;
         }
-        self.config_was_reloaded_impl();
+        self.config_was_reloaded_impl(false);
     }
 
-    fn config_was_reloaded_impl(&mut self) {
+    /// Applies the current config to this window. `light` is set when the
+    /// reload only changed `LIGHT_RELOAD_KEYS`, so fonts and the glyph atlas
+    /// are kept.
+    fn config_was_reloaded_impl(&mut self, light: bool) {
         log::debug!(
             "config was reloaded, overrides: {:?}",
             self.config_overrides
@@ -2589,17 +2749,19 @@ impl TermWindow {
         self.render_state.as_mut().map(|rs| rs.config_changed());
         let dimensions = self.dimensions;
 
-        if let Err(err) = self.fonts.config_changed(&config) {
-            log::error!("Failed to load font configuration: {:#}", err);
-        }
+        if !light {
+            if let Err(err) = self.fonts.config_changed(&config) {
+                log::error!("Failed to load font configuration: {:#}", err);
+            }
 
-        // Recreate texture atlas to ensure subpixel AA and font rendering changes
-        // correctly flush out the old cached glyphs when theme changes.
-        if let Err(err) = self.recreate_texture_atlas(None) {
-            log::error!(
-                "recreate_texture_atlas after config reload failed: {:#}",
-                err
-            );
+            // Recreate texture atlas to ensure subpixel AA and font rendering changes
+            // correctly flush out the old cached glyphs when theme changes.
+            if let Err(err) = self.recreate_texture_atlas(None) {
+                log::error!(
+                    "recreate_texture_atlas after config reload failed: {:#}",
+                    err
+                );
+            }
         }
 
         if let Some(window) = mux.get_window(self.mux_window_id) {
@@ -2878,16 +3040,16 @@ impl TermWindow {
     fn emit_user_var_event(&mut self, pane_id: PaneId, name: String, value: String) {
         let window_contains_pane = self.window_contains_pane(pane_id);
 
-        // Config TUI signals that config file was just saved; reload immediately.
-        // Only the window containing the signaling pane triggers reload to avoid
-        // duplicate reloads when multiple windows are open.
+        // Config TUI signals that config file was just saved; schedule a reload.
+        // Signals that arrive while one is pending fold into it, since the
+        // reload reads the file after all of them were written.
         // Note: config::reload() notifies subscribers, and each window reloads from
         // that subscription path. We intentionally avoid calling
         // config_was_reloaded_impl() directly here so this event only triggers one
         // per-window reload, and we also avoid predicting the next generation value:
         // failed reloads do not advance config generation.
         if Self::should_reload_config_for_user_var(&name, window_contains_pane) {
-            config::reload();
+            Self::schedule_config_changed_reload(&value);
             return;
         }
 
@@ -3734,6 +3896,8 @@ impl TermWindow {
             CopyTextTo { text, destination } => {
                 self.copy_to_clipboard(*destination, text.clone());
             }
+            CopyPaneCwd(dest) => self.copy_pane_cwd(pane, *dest),
+            CopyLastCommandOutput(dest) => self.copy_last_command_output(pane, *dest),
             PasteFrom(source) => {
                 self.paste_from_clipboard(pane, *source);
             }
@@ -3758,6 +3922,23 @@ impl TermWindow {
                     self.reset_font_and_window_size(&w)?
                 }
             }
+            IncreaseBackgroundOpacity => {
+                if let Some(w) = window.as_ref() {
+                    let step = self.config.background_opacity_step;
+                    self.adjust_background_opacity(w, Some(step));
+                }
+            }
+            DecreaseBackgroundOpacity => {
+                if let Some(w) = window.as_ref() {
+                    let step = self.config.background_opacity_step;
+                    self.adjust_background_opacity(w, Some(-step));
+                }
+            }
+            ResetBackgroundOpacity => {
+                if let Some(w) = window.as_ref() {
+                    self.adjust_background_opacity(w, None);
+                }
+            }
             ActivateTab(n) => {
                 self.activate_tab(*n)?;
             }
@@ -3804,6 +3985,12 @@ impl TermWindow {
             }
             Nop | DisableDefaultAssignment => {}
             ReloadConfiguration => {}
+            OpenConfigDirectory => {
+                if let Err(err) = Self::open_config_directory() {
+                    log::error!("Failed to open config directory: {err:#}");
+                    self.show_toast("Failed to open config directory".to_string());
+                }
+            }
             MoveTab(n) => self.move_tab(*n)?,
             MoveTabRelative(n) => self.move_tab_relative(*n)?,
             ScrollByPage(n) => self.scroll_by_page(**n, pane)?,
@@ -3830,16 +4017,17 @@ impl TermWindow {
                 let con = Connection::get().expect("call on gui thread");
                 con.hide_application();
             }
-            QuitApplication => {
+            QuitApplication { confirm } => {
                 let mux = Mux::get();
                 let config = &self.config;
 
                 match config.window_close_confirmation {
-                    WindowCloseConfirmation::NeverPrompt => {
+                    WindowCloseConfirmation::NeverPrompt if !*confirm => {
                         let con = Connection::get().expect("call on gui thread");
                         con.terminate_message_loop();
                     }
-                    WindowCloseConfirmation::AlwaysPrompt => {
+                    WindowCloseConfirmation::NeverPrompt
+                    | WindowCloseConfirmation::AlwaysPrompt => {
                         let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
                             Some(tab) => tab,
                             None => anyhow::bail!("no active tab!?"),
@@ -3891,6 +4079,10 @@ impl TermWindow {
                     }
                 } else if name == "open-kaku-config" {
                     crate::frontend::open_kaku_config();
+                } else if let Some(field) =
+                    name.strip_prefix(crate::frontend::OPEN_KAKU_CONFIG_FIELD_EVENT_PREFIX)
+                {
+                    crate::frontend::open_kaku_config_at_field(Some(field));
                 } else if name == crate::frontend::SET_DEFAULT_TERMINAL_EVENT {
                     match Connection::get() {
                         Some(conn) => match conn.set_default_terminal() {
@@ -3917,7 +4109,8 @@ impl TermWindow {
                 let text = self.selection_text(pane);
                 if !text.is_empty() {
                     if self.config.copy_on_select {
-                        self.copy_to_clipboard(*dest, text);
+                        let dest = self.config.copy_on_select_destination.unwrap_or(*dest);
+                        self.copy_to_clipboard(dest, text);
                         self.show_copy_toast();
                     } else {
                         self.show_copy_on_select_disabled_hint();
@@ -3929,7 +4122,8 @@ impl TermWindow {
             CompleteSelection(dest) => {
                 let text = self.selection_text(pane);
                 if !text.is_empty() && self.config.copy_on_select {
-                    self.copy_to_clipboard(*dest, text);
+                    let dest = self.config.copy_on_select_destination.unwrap_or(*dest);
+                    self.copy_to_clipboard(dest, text);
                     self.show_copy_toast();
                 } else if !text.is_empty() {
                     self.show_copy_on_select_disabled_hint();
@@ -4102,7 +4296,7 @@ impl TermWindow {
                 }
             }
             SwitchToWorkspace { name, spawn } => {
-                let activity = crate::Activity::new();
+                let activity = crate::Activity::with_reason("switch workspace");
                 let mux = Mux::get();
                 let name = name
                     .as_ref()
@@ -4262,6 +4456,10 @@ impl TermWindow {
                     pane.set_encoding(encoding);
                 }
             }
+            ShowEncodingPicker => {
+                let modal = crate::termwindow::encodingselect::EncodingSelector::new(self);
+                self.set_modal(Rc::new(modal));
+            }
         };
         Ok(PerformAssignmentResult::Handled)
     }
@@ -4395,6 +4593,31 @@ impl TermWindow {
         anyhow::bail!("failed to open {}", target.path.display())
     }
 
+    fn open_config_directory() -> anyhow::Result<()> {
+        let config_path = config::config_file_override().unwrap_or_else(config::user_config_path);
+
+        #[cfg(target_os = "macos")]
+        {
+            // Reveal the file itself when it exists so Finder selects it.
+            if config_path.is_file() {
+                return Self::run_path_command("/usr/bin/open", &["-R".to_string()], &config_path)
+                    .context("reveal config in Finder");
+            }
+        }
+
+        let dir = config_path
+            .parent()
+            .with_context(|| format!("{} has no parent directory", config_path.display()))?;
+
+        #[cfg(target_os = "macos")]
+        let program = "/usr/bin/open";
+        #[cfg(not(target_os = "macos"))]
+        let program = "xdg-open";
+
+        Self::run_path_command(program, &[], dir)
+            .with_context(|| format!("open {} with `{program}`", dir.display()))
+    }
+
     fn try_open_file_in_vscode(target: &FileLinkTarget) -> anyhow::Result<bool> {
         let Some(line) = target.line else {
             return Ok(false);
@@ -5306,9 +5529,57 @@ impl Drop for TermWindow {
 
 #[cfg(test)]
 mod tests {
-    use super::{InputBroadcastMode, TermWindow};
+    use super::{step_opacity, InputBroadcastMode, TermWindow};
     use mux::tab::TabId;
 
+    #[test]
+    fn background_opacity_steps_clamp_and_round() {
+        assert_eq!(step_opacity(0.9, 0.05), 0.95);
+        assert_eq!(step_opacity(0.98, 0.05), 1.0);
+        assert_eq!(step_opacity(0.02, -0.05), 0.0);
+        let mut opacity = 1.0;
+        for _ in 0..4 {
+            opacity = step_opacity(opacity, -0.05);
+        }
+        assert_eq!(opacity, 0.8);
+    }
+
+    #[test]
+    fn config_changed_signals_coalesce_while_reload_is_queued() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let queued = AtomicBool::new(false);
+        assert!(TermWindow::queue_config_changed_reload(&queued));
+        assert!(!TermWindow::queue_config_changed_reload(&queued));
+        assert!(!TermWindow::queue_config_changed_reload(&queued));
+
+        queued.store(false, Ordering::SeqCst);
+        assert!(TermWindow::queue_config_changed_reload(&queued));
+    }
+
+    #[test]
+    fn config_changed_payload_lists_keys_or_means_everything() {
+        use super::ConfigChangeScope;
+
+        assert_eq!(ConfigChangeScope::from_payload("1"), ConfigChangeScope::All);
+        assert_eq!(ConfigChangeScope::from_payload(""), ConfigChangeScope::All);
+        assert_eq!(
+            ConfigChangeScope::from_payload(" , "),
+            ConfigChangeScope::All
+        );
+
+        let opacity = ConfigChangeScope::from_payload("window_background_opacity");
+        assert!(opacity.is_light());
+        let font = ConfigChangeScope::from_payload("font_size, window_background_opacity");
+        assert!(!font.is_light());
+
+        let merged = opacity
+            .clone()
+            .merge(ConfigChangeScope::from_payload("bell_dock_badge"));
+        assert!(merged.is_light());
+        assert!(!opacity.merge(ConfigChangeScope::All).is_light());
+    }
+
     #[test]
     fn other_user_vars_never_trigger_reload() {
         assert!(!TermWindow::should_reload_config_for_user_var(
@@ -8,7 +8,7 @@ use crate::termwindow::render::corners::{
 };
 use crate::termwindow::{DimensionContext, GuiWin, TermWindow};
 use crate::utilsprites::RenderMetrics;
-use config::keyassignment::KeyAssignment;
+use config::keyassignment::{KeyAssignment, PaneEncoding};
 use config::{Dimension, RgbaColor, SrgbaTuple};
 use frecency::Frecency;
 use luahelper::{from_lua_value_dynamic, impl_lua_conversion_dynamic};
@@ -208,6 +208,14 @@ fn build_commands(term_window: &mut TermWindow) -> Vec<ExpandedCommand> {
         }
     });
 
+    let encoding_order = PaneEncoding::ordered_list();
+    let encoding_rank = |encoding: &PaneEncoding| {
+        encoding_order
+            .iter()
+            .position(|candidate| candidate == encoding)
+            .unwrap_or(usize::MAX)
+    };
+
     let mut scores: HashMap<&str, f64> = HashMap::new();
     let recents = load_recents();
     if let Ok(recents) = &recents {
@@ -229,7 +237,13 @@ fn build_commands(term_window: &mut TermWindow) -> Vec<ExpandedCommand> {
         }
 
         match a.menubar.cmp(&b.menubar) {
-            Ordering::Equal => a.brief.cmp(&b.brief),
+            Ordering::Equal => match (&a.action, &b.action) {
+                // Keep encodings in `ordered_list` order, which tracks recency.
+                (KeyAssignment::SetPaneEncoding(a), KeyAssignment::SetPaneEncoding(b)) => {
+                    encoding_rank(a).cmp(&encoding_rank(b))
+                }
+                _ => a.brief.cmp(&b.brief),
+            },
             ordering => ordering,
         }
     });
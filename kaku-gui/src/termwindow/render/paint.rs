@@ -10,7 +10,7 @@ use crate::utilsprites::RenderMetrics;
 use ::window::bitmaps::atlas::OutOfTextureSpace;
 use ::window::WindowOps;
 use anyhow::Context;
-use config::Dimension;
+use config::{Dimension, ToastPosition};
 use smol::Timer;
 use std::collections::HashSet;
 use std::time::{Duration, Instant};
@@ -69,6 +69,46 @@ static BROADCAST_INDICATOR_POLY: &[Poly] = &[
     },
 ];
 
+/// Alpha for a toast that ramps linearly from opaque at `fade_at` to fully
+/// transparent at `expires_at`.
+fn toast_fade_alpha(now: Instant, fade_at: Instant, expires_at: Instant) -> f32 {
+    if now <= fade_at {
+        return 1.0;
+    }
+    let span = expires_at.saturating_duration_since(fade_at).as_secs_f32();
+    if span <= 0.0 {
+        return 0.0;
+    }
+    (1.0 - now.duration_since(fade_at).as_secs_f32() / span).clamp(0.0, 1.0)
+}
+
+/// Top-left corner for a toast of `size` anchored at `position` inside
+/// `area` (left, top, right, bottom), keeping `margin` from the edges it hugs.
+fn toast_origin(
+    position: ToastPosition,
+    area: (f32, f32, f32, f32),
+    size: (f32, f32),
+    margin: (f32, f32),
+) -> (f32, f32) {
+    let (left, top, right, bottom) = area;
+    let (width, height) = size;
+    let (h_margin, v_margin) = margin;
+
+    let right_x = right - width - h_margin;
+    let center_x = left + (right - left - width) / 2.0;
+    let top_y = top + v_margin;
+    let bottom_y = bottom - height - v_margin;
+    let center_y = top + (bottom - top - height) / 2.0;
+
+    match position {
+        ToastPosition::TopRight => (right_x, top_y),
+        ToastPosition::TopCenter => (center_x, top_y),
+        ToastPosition::BottomRight => (right_x, bottom_y),
+        ToastPosition::BottomCenter => (center_x, bottom_y),
+        ToastPosition::Center => (center_x, center_y),
+    }
+}
+
 fn toast_colors_for_palette(
     palette: &wezterm_term::color::ColorPalette,
     alpha: f32,
@@ -721,23 +761,19 @@ impl crate::TermWindow {
 
     /// Render the toast notification
     pub fn paint_toast(&mut self) -> anyhow::Result<()> {
-        let (toast_at, message, lifetime) = match &self.toast {
-            Some((t, msg, lifetime)) if t.elapsed() < *lifetime => (*t, msg.clone(), *lifetime),
+        let (toast_at, fade_at, message, lifetime) = match &self.toast {
+            Some((t, fade_at, msg, lifetime)) if t.elapsed() < *lifetime => {
+                (*t, *fade_at, msg.clone(), *lifetime)
+            }
             _ => return Ok(()),
         };
 
         let font = self.fonts.title_font()?;
         let metrics = RenderMetrics::with_font_metrics(&font.metrics());
 
-        // Fade out during the last 500ms of the configured lifetime.
-        let elapsed_ms = toast_at.elapsed().as_millis() as f32;
-        let lifetime_ms = lifetime.as_millis() as f32;
-        let fade_start_ms = (lifetime_ms - 500.0).max(0.0);
-        let alpha = if elapsed_ms > fade_start_ms {
-            (1.0 - (elapsed_ms - fade_start_ms) / 500.0).max(0.0)
-        } else {
-            1.0
-        };
+        // Fade out between fade_at and the end of the configured lifetime.
+        let now = Instant::now();
+        let alpha = toast_fade_alpha(now, fade_at, toast_at + lifetime);
 
         // Match the toast to the currently visible terminal palette so it
         // stays in sync with theme changes and client palette overrides.
@@ -804,11 +840,25 @@ impl crate::TermWindow {
         let h_margin = metrics.cell_size.width as f32 * 2.0;
         let v_margin = metrics.cell_size.height as f32 * 2.0;
 
-        // Position at bottom-right with fixed margin from window edge
-        let right_x =
-            dimensions.pixel_width as f32 - approx_width - h_margin - border.right.get() as f32;
-        let bottom_y =
-            dimensions.pixel_height as f32 - toast_height - v_margin - border.bottom.get() as f32;
+        // Anchor within the window, keeping a fixed margin from the edges and
+        // staying clear of a top tab bar.
+        let top_tab_bar_height = if self.show_tab_bar && !self.config.tab_bar_at_bottom {
+            self.tab_bar_pixel_height()?
+        } else {
+            0.
+        };
+        let area = (
+            border.left.get() as f32,
+            border.top.get() as f32 + top_tab_bar_height,
+            dimensions.pixel_width as f32 - border.right.get() as f32,
+            dimensions.pixel_height as f32 - border.bottom.get() as f32,
+        );
+        let (toast_x, toast_y) = toast_origin(
+            self.config.toast_position,
+            area,
+            (approx_width, toast_height),
+            (h_margin, v_margin),
+        );
 
         let computed = self.compute_element(
             &LayoutContext {
@@ -822,7 +872,7 @@ impl crate::TermWindow {
                     pixel_max: dimensions.pixel_width as f32,
                     pixel_cell: metrics.cell_size.width as f32,
                 },
-                bounds: euclid::rect(right_x, bottom_y, approx_width, toast_height),
+                bounds: euclid::rect(toast_x, toast_y, approx_width, toast_height),
                 metrics: &metrics,
                 gl_state: self.render_state.as_ref().unwrap(),
                 zindex: 120,
@@ -834,7 +884,7 @@ impl crate::TermWindow {
         self.render_element(&computed, gl_state, None)?;
 
         // Keep redrawing during fade-out
-        if elapsed_ms > fade_start_ms {
+        if now >= fade_at {
             let next = Instant::now() + Duration::from_millis(16);
             let mut anim = self.has_animation.borrow_mut();
             match *anim {
@@ -851,10 +901,55 @@ impl crate::TermWindow {
 
 #[cfg(test)]
 mod tests {
-    use super::toast_colors_for_palette;
+    use super::{toast_colors_for_palette, toast_fade_alpha, toast_origin};
+    use config::ToastPosition;
+    use std::time::{Duration, Instant};
     use wezterm_term::color::{ColorPalette, SrgbaTuple};
     use window::color::LinearRgba;
 
+    #[test]
+    fn toast_origin_follows_anchor() {
+        let area = (0.0, 40.0, 800.0, 600.0);
+        let size = (100.0, 20.0);
+        let margin = (10.0, 10.0);
+
+        assert_eq!(
+            toast_origin(ToastPosition::BottomRight, area, size, margin),
+            (690.0, 570.0)
+        );
+        assert_eq!(
+            toast_origin(ToastPosition::TopRight, area, size, margin),
+            (690.0, 50.0)
+        );
+        assert_eq!(
+            toast_origin(ToastPosition::TopCenter, area, size, margin),
+            (350.0, 50.0)
+        );
+        assert_eq!(
+            toast_origin(ToastPosition::BottomCenter, area, size, margin),
+            (350.0, 570.0)
+        );
+        assert_eq!(
+            toast_origin(ToastPosition::Center, area, size, margin),
+            (350.0, 310.0)
+        );
+    }
+
+    #[test]
+    fn toast_alpha_ramps_to_zero_over_fade_window() {
+        let fade_at = Instant::now();
+        let expires_at = fade_at + Duration::from_millis(500);
+
+        assert_eq!(toast_fade_alpha(fade_at, fade_at, expires_at), 1.0);
+        let midway = toast_fade_alpha(fade_at + Duration::from_millis(250), fade_at, expires_at);
+        assert!((midway - 0.5).abs() < 1e-3);
+        assert_eq!(toast_fade_alpha(expires_at, fade_at, expires_at), 0.0);
+        assert_eq!(
+            toast_fade_alpha(expires_at + Duration::from_millis(10), fade_at, expires_at),
+            0.0
+        );
+    }
+
     #[test]
     fn light_palette_uses_gold_background_and_dark_text() {
         let mut palette = ColorPalette::default();
@@ -127,20 +127,27 @@ impl crate::TermWindow {
             )?;
         }
 
-        // UI item for hit testing
+        // UI item for hit testing, padded beyond the drawn line so the
+        // divider is easier to grab.
+        let hit_padding = self.config.split_hit_test_padding.max(0.0) as usize;
+        let base_x = content_left as usize + (split.left * cell_width as usize);
+        let base_y =
+            padding_top as usize + first_row_offset as usize + split.top * cell_height as usize;
         let (x, y, width, height) = if is_horizontal {
+            let x = base_x.saturating_sub(hit_padding);
             (
-                content_left as usize + (split.left * cell_width as usize),
-                padding_top as usize + first_row_offset as usize + split.top * cell_height as usize,
-                cell_width as usize,
+                x,
+                base_y,
+                cell_width as usize + (base_x - x) + hit_padding,
                 split.size * cell_height as usize,
             )
         } else {
+            let y = base_y.saturating_sub(hit_padding);
             (
-                content_left as usize + (split.left * cell_width as usize),
-                padding_top as usize + first_row_offset as usize + split.top * cell_height as usize,
+                base_x,
+                y,
                 split.size * cell_width as usize,
-                cell_height as usize,
+                cell_height as usize + (base_y - y) + hit_padding,
             )
         };
 
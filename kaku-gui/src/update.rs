@@ -5,8 +5,9 @@ use http_req::uri::Uri;
 use serde::*;
 use std::cmp::Ordering as CmpOrdering;
 use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use wezterm_toast_notification::*;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -147,6 +148,167 @@ fn parse_version_numbers(version: &str) -> Option<Vec<u64>> {
     Some(out)
 }
 
+/// Longest release notes summary shown in the update notification.
+const MAX_NOTES_SUMMARY_CHARS: usize = 100;
+
+/// Replaces `[text](url)` with `text` and drops emphasis/code markers.
+fn strip_inline_markdown(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(open) = rest.find('[') {
+        let after_open = &rest[open + 1..];
+        let link = after_open.find("](").and_then(|close| {
+            let target = &after_open[close + 2..];
+            target.find(')').map(|end| (close, close + 2 + end + 1))
+        });
+        match link {
+            Some((close, consumed)) => {
+                out.push_str(&rest[..open]);
+                out.push_str(&after_open[..close]);
+                rest = &after_open[consumed..];
+            }
+            None => {
+                out.push_str(&rest[..open + 1]);
+                rest = after_open;
+            }
+        }
+    }
+    out.push_str(rest);
+    out.replace("**", "").replace("__", "").replace('`', "")
+}
+
+/// Picks the first line of the release notes that reads as prose, skipping
+/// headings, rules, images and comments, and truncates it for a toast.
+fn release_notes_summary(body: &str) -> Option<String> {
+    let line = body.lines().map(str::trim).find(|line| {
+        !line.is_empty()
+            && !line.starts_with('#')
+            && !line.starts_with("<!--")
+            && !line.starts_with("![")
+            && !line
+                .chars()
+                .all(|c| matches!(c, '-' | '*' | '_' | '=' | ' '))
+    })?;
+
+    let line = line
+        .trim_start_matches(|c: char| matches!(c, '-' | '*' | '+' | '>'))
+        .trim_start();
+    let line = match line.split_once(". ") {
+        Some((number, rest)) if number.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => line,
+    };
+    let text = strip_inline_markdown(line);
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if text.chars().count() > MAX_NOTES_SUMMARY_CHARS {
+        let truncated: String = text.chars().take(MAX_NOTES_SUMMARY_CHARS - 1).collect();
+        Some(format!("{}…", truncated.trim_end()))
+    } else {
+        Some(text.to_string())
+    }
+}
+
+fn update_toast_message(release: &Release) -> String {
+    match release_notes_summary(&release.body) {
+        Some(summary) => format!("{}: {}", release.tag_name, summary),
+        None => format!("{} is available. Click to update.", release.tag_name),
+    }
+}
+
+/// Written by `kaku update --skip <tag>`.
+const SKIPPED_UPDATE_FILE: &str = "skipped_update";
+
+/// A skipped tag only matters while it is still ahead of the running build.
+fn skipped_tag_still_applies(skipped: &str, current: &str) -> bool {
+    !skipped.is_empty() && is_newer(skipped, current)
+}
+
+fn is_skipped_release(latest: &str, skipped: Option<&str>) -> bool {
+    skipped.is_some_and(|skipped| skipped.trim_start_matches('v') == latest.trim_start_matches('v'))
+}
+
+/// Reads the skipped tag, clearing it once the installed version has
+/// reached or passed it so that later releases notify again.
+fn load_skipped_tag(current: &str) -> Option<String> {
+    let path = config::DATA_DIR.join(SKIPPED_UPDATE_FILE);
+    let skipped = std::fs::read_to_string(&path).ok()?.trim().to_string();
+    if skipped_tag_still_applies(&skipped, current) {
+        Some(skipped)
+    } else {
+        log::info!("update_checker: clearing skipped tag {:?}", skipped);
+        let _ = std::fs::remove_file(&path);
+        None
+    }
+}
+
+/// A discovered gui socket and when it was created, if the filesystem
+/// reports it.
+type GuiSock = (PathBuf, Option<SystemTime>);
+
+/// Picks the gui instance that owns update notifications: the eldest socket,
+/// then the lowest pid in its `gui-sock-<pid>` name, then the path. The
+/// result does not depend on the order `socks` were discovered in, so the
+/// same instance keeps the role across checks.
+fn elect_notification_leader(socks: &[GuiSock]) -> Option<&Path> {
+    fn leader_key((sock, created): &GuiSock) -> (bool, Option<SystemTime>, u32, &Path) {
+        let pid = sock
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("gui-sock-"))
+            .and_then(|pid| pid.parse().ok());
+        (
+            created.is_none(),
+            *created,
+            pid.unwrap_or(u32::MAX),
+            sock.as_path(),
+        )
+    }
+
+    socks
+        .iter()
+        .min_by(|a, b| leader_key(a).cmp(&leader_key(b)))
+        .map(|(sock, _)| sock.as_path())
+}
+
+/// Decides whether this process should show the update notification.
+/// `socks` may include stale sockets left behind by a crashed gui, so those
+/// are skipped rather than elected. If no instance responds, we show it
+/// ourselves.
+fn is_notification_leader(
+    socks: &[GuiSock],
+    my_sock: &Path,
+    is_live: impl Fn(&Path) -> bool,
+) -> bool {
+    let candidates: Vec<GuiSock> = socks
+        .iter()
+        .filter(|(sock, _)| sock.as_path() == my_sock || is_live(sock))
+        .cloned()
+        .collect();
+    match elect_notification_leader(&candidates) {
+        Some(leader) => leader == my_sock,
+        None => true,
+    }
+}
+
+fn should_show_notification(my_sock: &Path) -> bool {
+    let socks: Vec<GuiSock> = wezterm_client::discovery::discover_gui_socks()
+        .into_iter()
+        .map(|sock| {
+            let created = std::fs::metadata(&sock)
+                .and_then(|meta| meta.created().or_else(|_| meta.modified()))
+                .ok();
+            (sock, created)
+        })
+        .collect();
+    log::info!("update_checker: socks={:?}, my_sock={:?}", socks, my_sock);
+    is_notification_leader(&socks, my_sock, |sock| {
+        !wezterm_client::discovery::is_sock_dead(sock)
+    })
+}
+
 fn update_checker() {
     log::info!("update_checker thread started");
 
@@ -162,7 +324,10 @@ fn update_checker() {
         if let Ok(content) = std::fs::read_to_string(&update_file_name) {
             if let Ok(cached_release) = serde_json::from_str::<Release>(&content) {
                 let current = wezterm_version();
-                if is_newer(&cached_release.tag_name, current) {
+                let skipped = load_skipped_tag(current);
+                if is_newer(&cached_release.tag_name, current)
+                    && !is_skipped_release(&cached_release.tag_name, skipped.as_deref())
+                {
                     log::info!(
                         "update_checker: cached release {} is newer than current {}, showing notification",
                         cached_release.tag_name,
@@ -171,11 +336,10 @@ fn update_checker() {
                     std::thread::sleep(initial_interval);
                     let my_sock =
                         config::RUNTIME_DIR.join(format!("gui-sock-{}", unsafe { libc::getpid() }));
-                    let socks = wezterm_client::discovery::discover_gui_socks();
-                    if force_ui || socks.is_empty() || socks.first() == Some(&my_sock) {
+                    if force_ui || should_show_notification(&my_sock) {
                         persistent_toast_notification_with_click_to_open_url(
                             "Kaku Update Available",
-                            &format!("{} is available. Click to update.", cached_release.tag_name),
+                            &update_toast_message(&cached_release),
                             "kaku://update",
                         );
                     }
@@ -210,14 +374,6 @@ fn update_checker() {
     let my_sock = config::RUNTIME_DIR.join(format!("gui-sock-{}", unsafe { libc::getpid() }));
 
     loop {
-        // Figure out which other wezterm-guis are running.
-        // We have a little "consensus protocol" to decide which
-        // of us will show the toast notification or show the update
-        // window: the one of us that sorts first in the list will
-        // own doing that, so that if there are a dozen gui processes
-        // running, we don't spam the user with a lot of notifications.
-        let socks = wezterm_client::discovery::discover_gui_socks();
-
         log::info!(
             "update_checker: check_for_updates={}",
             configuration().check_for_updates
@@ -228,19 +384,27 @@ fn update_checker() {
                 Ok(latest) => {
                     log::info!("update_checker: got release {}", latest.tag_name);
                     let current = wezterm_version();
-                    if is_newer(&latest.tag_name, current) || force_ui {
+                    let skipped = load_skipped_tag(current);
+                    if is_skipped_release(&latest.tag_name, skipped.as_deref()) && !force_ui {
+                        log::info!("update_checker: release {} was skipped", latest.tag_name);
+                    } else if is_newer(&latest.tag_name, current) || force_ui {
                         log::info!(
                             "latest release {} is newer than current build {}",
                             latest.tag_name,
                             current
                         );
 
-                        log::info!("update_checker: socks={:?}, my_sock={:?}", socks, my_sock);
-                        if force_ui || socks.is_empty() || socks[0] == my_sock {
+                        // Figure out which other wezterm-guis are running.
+                        // We have a little "consensus protocol" to decide which
+                        // of us will show the toast notification or show the update
+                        // window: the eldest of us that still responds will
+                        // own doing that, so that if there are a dozen gui processes
+                        // running, we don't spam the user with a lot of notifications.
+                        if force_ui || should_show_notification(&my_sock) {
                             log::info!("update_checker: showing notification");
                             persistent_toast_notification_with_click_to_open_url(
                                 "Kaku Update Available",
-                                &format!("{} is available. Click to update.", latest.tag_name),
+                                &update_toast_message(&latest),
                                 "kaku://update",
                             );
                         } else {
@@ -328,7 +492,16 @@ fn check_update_completed() {
 
 #[cfg(test)]
 mod tests {
-    use super::is_newer;
+    use super::{
+        elect_notification_leader, is_newer, is_notification_leader, is_skipped_release,
+        release_notes_summary, skipped_tag_still_applies, GuiSock, MAX_NOTES_SUMMARY_CHARS,
+    };
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+
+    fn unknown_age(socks: &[PathBuf]) -> Vec<GuiSock> {
+        socks.iter().map(|sock| (sock.clone(), None)).collect()
+    }
 
     #[test]
     fn semver_numeric_comparison() {
@@ -337,4 +510,107 @@ mod tests {
         assert!(!is_newer("0.1.1", "0.1.1"));
         assert!(is_newer("v0.1.2", "0.1.1"));
     }
+
+    #[test]
+    fn notification_leader_skips_unreachable_socks() {
+        let stale = PathBuf::from("/run/kaku/gui-sock-100");
+        let other = PathBuf::from("/run/kaku/gui-sock-200");
+        let me = PathBuf::from("/run/kaku/gui-sock-300");
+        let live = |sock: &Path| sock != stale.as_path();
+
+        let socks = unknown_age(&[stale.clone(), me.clone()]);
+        assert!(is_notification_leader(&socks, &me, live));
+
+        let socks = unknown_age(&[stale.clone(), other.clone(), me.clone()]);
+        assert!(!is_notification_leader(&socks, &me, live));
+        assert!(is_notification_leader(&socks, &other, live));
+
+        assert!(is_notification_leader(
+            &unknown_age(&[stale.clone()]),
+            &me,
+            live
+        ));
+        assert!(is_notification_leader(&[], &me, live));
+    }
+
+    #[test]
+    fn notification_leader_is_eldest_regardless_of_order() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let young = (
+            PathBuf::from("/run/kaku/gui-sock-100"),
+            Some(t0 + Duration::from_secs(5)),
+        );
+        let old = (PathBuf::from("/run/kaku/gui-sock-900"), Some(t0));
+        let unknown = (PathBuf::from("/run/kaku/gui-sock-50"), None);
+
+        let socks = vec![young.clone(), unknown.clone(), old.clone()];
+        assert_eq!(elect_notification_leader(&socks), Some(old.0.as_path()));
+        let socks = vec![old.clone(), unknown, young];
+        assert_eq!(elect_notification_leader(&socks), Some(old.0.as_path()));
+    }
+
+    #[test]
+    fn notification_leader_ties_break_on_pid() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let a = (PathBuf::from("/run/kaku/gui-sock-1200"), Some(t0));
+        let b = (PathBuf::from("/run/kaku/gui-sock-300"), Some(t0));
+
+        assert_eq!(
+            elect_notification_leader(&[a.clone(), b.clone()]),
+            Some(b.0.as_path())
+        );
+        assert_eq!(
+            elect_notification_leader(&[b.clone(), a]),
+            Some(b.0.as_path())
+        );
+    }
+
+    #[test]
+    fn notification_leader_of_single_member_set() {
+        let only = (PathBuf::from("/run/kaku/gui-sock-42"), None);
+        assert_eq!(
+            elect_notification_leader(std::slice::from_ref(&only)),
+            Some(only.0.as_path())
+        );
+        assert_eq!(elect_notification_leader(&[]), None);
+    }
+
+    #[test]
+    fn skipped_release_suppresses_only_that_tag() {
+        assert!(is_skipped_release("v0.9.0", Some("0.9.0")));
+        assert!(is_skipped_release("0.9.0", Some("v0.9.0")));
+        assert!(!is_skipped_release("v0.9.1", Some("v0.9.0")));
+        assert!(!is_skipped_release("v0.9.0", None));
+    }
+
+    #[test]
+    fn skipped_tag_clears_once_installed_version_catches_up() {
+        assert!(skipped_tag_still_applies("v0.9.0", "0.8.2"));
+        assert!(!skipped_tag_still_applies("v0.9.0", "0.9.0"));
+        assert!(!skipped_tag_still_applies("v0.9.0", "0.10.0"));
+        assert!(!skipped_tag_still_applies("", "0.8.2"));
+    }
+
+    #[test]
+    fn release_notes_summary_skips_headings_and_strips_markdown() {
+        let body = "## What's New\n\n---\n- **Faster** startup, see [#123](https://github.com/tw93/Kaku/pull/123)\n- Other";
+        assert_eq!(
+            release_notes_summary(body).as_deref(),
+            Some("Faster startup, see #123")
+        );
+        assert_eq!(
+            release_notes_summary("1. Use `kaku update` to upgrade").as_deref(),
+            Some("Use kaku update to upgrade")
+        );
+        assert_eq!(release_notes_summary("# Title only\n\n"), None);
+        assert_eq!(release_notes_summary(""), None);
+    }
+
+    #[test]
+    fn release_notes_summary_is_truncated() {
+        let body = "word ".repeat(60);
+        let summary = release_notes_summary(&body).expect("summary");
+        assert!(summary.chars().count() <= MAX_NOTES_SUMMARY_CHARS);
+        assert!(summary.ends_with('…'));
+    }
 }
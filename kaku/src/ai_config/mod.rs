@@ -1,13 +1,33 @@
 pub mod tui;
 
+use crate::assistant_config;
+use crate::utils::abbreviate_home;
 use anyhow::Context;
 use clap::Parser;
 
 #[derive(Debug, Parser, Clone, Default)]
-pub struct AiConfigCommand {}
+pub struct AiConfigCommand {
+    /// Print the effective Kaku Assistant settings and where each one comes
+    /// from, without opening the TUI. The API key is masked.
+    #[arg(long)]
+    show: bool,
+}
 
 impl AiConfigCommand {
     pub fn run(&self) -> anyhow::Result<()> {
+        if self.show {
+            return show_assistant_config();
+        }
         tui::run().context("ai config tui")
     }
 }
+
+fn show_assistant_config() -> anyhow::Result<()> {
+    let path = assistant_config::ensure_assistant_toml_exists()?;
+    let raw = std::fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+    println!("Kaku Assistant config: {}", abbreviate_home(&path));
+    for line in assistant_config::describe_effective_config(&raw) {
+        println!("  {}", line);
+    }
+    Ok(())
+}
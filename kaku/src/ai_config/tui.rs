@@ -2767,10 +2767,6 @@ fn extract_kaku_assistant_fields(raw: &str) -> Vec<FieldEntry> {
     ]
 }
 
-fn render_toml_string(value: &str) -> String {
-    toml::Value::String(value.to_string()).to_string()
-}
-
 fn write_kaku_assistant_config(path: &Path, cfg: &KakuAssistantConfig) -> anyhow::Result<()> {
     let mut out = String::new();
     out.push_str("# Kaku Assistant configuration\n");
@@ -2791,20 +2787,21 @@ fn write_kaku_assistant_config(path: &Path, cfg: &KakuAssistantConfig) -> anyhow
         "enabled = false\n"
     });
     if cfg.api_key().trim().is_empty() {
-        out.push_str("# api_key = \"<your_api_key>\"\n");
+        out.push_str(assistant_config::API_KEY_PLACEHOLDER);
+        out.push('\n');
     } else {
         out.push_str(&format!(
             "api_key = {}\n",
-            render_toml_string(cfg.api_key().trim())
+            assistant_config::render_toml_string(cfg.api_key().trim())
         ));
     }
     out.push_str(&format!(
         "model = {}\n",
-        render_toml_string(cfg.model().trim())
+        assistant_config::render_toml_string(cfg.model().trim())
     ));
     out.push_str(&format!(
         "base_url = {}\n",
-        render_toml_string(cfg.base_url().trim())
+        assistant_config::render_toml_string(cfg.base_url().trim())
     ));
     if cfg.custom_headers().is_empty() {
         out.push_str("# custom_headers = [\"X-Customer-ID: your-customer-id\"]\n");
@@ -2887,7 +2884,34 @@ fn save_kaku_assistant_field(field_key: &str, new_val: &str) -> anyhow::Result<(
         _ => return Ok(()),
     };
 
-    write_kaku_assistant_config(&path, &updated)
+    if raw.trim().is_empty() {
+        return write_kaku_assistant_config(&path, &updated);
+    }
+
+    // Only touch the changed key so user comments and unknown keys survive.
+    let content = match field_key {
+        "Enabled" => assistant_config::set_top_level_bool_key_in_content(
+            &raw,
+            "enabled",
+            updated.is_enabled(),
+        ),
+        "Model" => {
+            assistant_config::set_top_level_string_key_in_content(&raw, "model", updated.model())
+        }
+        "Base URL" => assistant_config::set_top_level_string_key_in_content(
+            &raw,
+            "base_url",
+            updated.base_url(),
+        ),
+        _ if updated.api_key().is_empty() => assistant_config::clear_api_key_in_content(&raw),
+        _ => assistant_config::set_top_level_string_key_in_content(
+            &raw,
+            "api_key",
+            updated.api_key(),
+        ),
+    };
+    write_atomic(&path, content.as_bytes()).with_context(|| format!("write {}", path.display()))?;
+    Ok(())
 }
 
 /// Get Gemini account email from google_accounts.json
@@ -4424,7 +4448,7 @@ impl App {
         let AppMode::Editing {
             field_idx,
             buffer,
-            cursor: _,
+            cursor,
         } = std::mem::replace(&mut self.mode, AppMode::Browsing)
         else {
             return;
@@ -4454,6 +4478,20 @@ impl App {
             return;
         }
 
+        if tool_kind == Tool::KakuAssistant && field_key == "Base URL" && !new_val.is_empty() {
+            if let Err(e) = assistant_config::validate_base_url(&new_val) {
+                // Keep the popup open so the URL can be corrected in place.
+                self.mode = AppMode::Editing {
+                    field_idx,
+                    buffer,
+                    cursor,
+                };
+                self.focus = Focus::Editor;
+                self.set_error(format!("Base URL: {}", e));
+                return;
+            }
+        }
+
         if let Some(tool) = self.current_tool_mut() {
             tool.fields[field_idx].value = new_val.clone();
         }
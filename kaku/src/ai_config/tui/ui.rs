@@ -449,6 +449,14 @@ pub(super) fn render_editor(frame: &mut ratatui::Frame, area: Rect, app: &App) {
 
     let content_area = inner.inner(Margin::new(1, 0));
 
+    let masked;
+    let (edit_buf, edit_cursor) = if tool.tool == Tool::KakuAssistant && field.key == "API Key" {
+        masked = mask_edit_buffer(edit_buf, edit_cursor);
+        (masked.0.as_str(), masked.1)
+    } else {
+        (edit_buf, edit_cursor)
+    };
+
     let line = if edit_buf.is_empty() {
         Line::from(Span::styled(" ", Style::default().bg(primary())))
     } else {
@@ -481,6 +489,14 @@ pub(super) fn render_editor(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     frame.render_widget(input, content_area);
 }
 
+/// Replaces every character of a secret being edited with a bullet, mapping
+/// the byte cursor onto the masked text so cursor movement still lines up.
+fn mask_edit_buffer(buffer: &str, cursor: usize) -> (String, usize) {
+    let masked: String = buffer.chars().map(|_| '•').collect();
+    let masked_cursor = buffer[..cursor].chars().count() * '•'.len_utf8();
+    (masked, masked_cursor)
+}
+
 fn render_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     let Some(tool) = app.tools.get(app.tool_index) else {
         return;
@@ -562,7 +578,7 @@ fn render_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
 
 #[cfg(test)]
 mod tests {
-    use super::{resolve_main_layout, MainLayoutMode};
+    use super::{mask_edit_buffer, resolve_main_layout, MainLayoutMode};
 
     #[test]
     fn keeps_spacer_in_compact_layout() {
@@ -580,4 +596,12 @@ mod tests {
         assert_eq!(resolve_main_layout(2, 1), MainLayoutMode::HeaderOnly);
         assert_eq!(resolve_main_layout(3, 1), MainLayoutMode::HeaderAndFooter);
     }
+
+    #[test]
+    fn masks_secret_input_and_keeps_cursor_position() {
+        let (masked, cursor) = mask_edit_buffer("sk-abc", 3);
+        assert_eq!(masked, "••••••");
+        assert_eq!(&masked[..cursor], "•••");
+        assert_eq!(mask_edit_buffer("", 0), (String::new(), 0));
+    }
 }
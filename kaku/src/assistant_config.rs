@@ -16,6 +16,12 @@ pub const DEFAULT_MODEL: &str = "DeepSeek-V3.2";
 /// Default API base URL for the AI service.
 pub const DEFAULT_BASE_URL: &str = "https://api.vivgrid.com/v1";
 
+/// Commented-out `api_key` line written while no key is configured.
+pub const API_KEY_PLACEHOLDER: &str = "# api_key = \"<your_api_key>\"";
+
+/// Top-level keys that must be present in assistant.toml, with their defaults.
+const REQUIRED_KEYS: [(&str, &str); 2] = [("model", DEFAULT_MODEL), ("base_url", DEFAULT_BASE_URL)];
+
 /// Returns the path to the assistant.toml configuration file.
 ///
 /// The file is located in the same directory as the user's Kaku config,
@@ -98,6 +104,98 @@ pub fn write_enabled(enabled: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Checks that `value` is an `http` or `https` URL with a host, as expected
+/// for `base_url`. Returns a short message suitable for the TUI on failure.
+pub fn validate_base_url(value: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(value.trim()).map_err(|e| format!("invalid URL: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!(
+            "unsupported scheme `{}`, expected http or https",
+            parsed.scheme()
+        ));
+    }
+    match parsed.host_str() {
+        Some(host) if !host.is_empty() => Ok(()),
+        _ => Err("URL has no host".to_string()),
+    }
+}
+
+/// Describes the settings the assistant will actually use, one line per key
+/// with where the value came from (`file` or `default`). The API key is
+/// masked and never printed in full.
+pub fn describe_effective_config(raw: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let parsed = match raw.parse::<toml::Table>() {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            lines.push(format!("warning: malformed TOML, using defaults: {}", err));
+            toml::Table::new()
+        }
+    };
+    let string_value = |key: &str| {
+        parsed
+            .get(key)
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let mut push = |key: &str, value: String, from_file: bool| {
+        let source = if from_file { "file" } else { "default" };
+        lines.push(format!("{:<15}{:<40} ({})", key, value, source));
+    };
+
+    let enabled = parsed.get("enabled").and_then(|value| value.as_bool());
+    push(
+        "enabled",
+        enabled.unwrap_or(true).to_string(),
+        enabled.is_some(),
+    );
+    let model = string_value("model");
+    push(
+        "model",
+        model.unwrap_or(DEFAULT_MODEL).to_string(),
+        model.is_some(),
+    );
+    let base_url = string_value("base_url");
+    push(
+        "base_url",
+        base_url.unwrap_or(DEFAULT_BASE_URL).to_string(),
+        base_url.is_some(),
+    );
+    let api_key = string_value("api_key");
+    push(
+        "api_key",
+        api_key.map_or_else(|| "not set".to_string(), mask_api_key),
+        api_key.is_some(),
+    );
+    let headers = parsed
+        .get("custom_headers")
+        .and_then(|value| value.as_array())
+        .map(|headers| headers.len());
+    push(
+        "custom_headers",
+        format!("{} set", headers.unwrap_or(0)),
+        headers.is_some(),
+    );
+    lines
+}
+
+/// Masks an API key as `sk-...abcd`: the vendor prefix, if any, and the last
+/// four characters. Short keys are hidden entirely.
+fn mask_api_key(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 8 {
+        return "****".into();
+    }
+    let prefix = key
+        .split_once('-')
+        .filter(|(prefix, _)| !prefix.is_empty() && prefix.len() <= 4)
+        .map(|(prefix, _)| format!("{}-", prefix))
+        .unwrap_or_default();
+    let last4: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}...{}", prefix, last4)
+}
+
 /// Returns the default assistant.toml configuration template.
 ///
 /// This template includes documentation comments explaining each configuration option
@@ -118,7 +216,7 @@ pub fn default_assistant_toml_template() -> String {
 #                 note: Authorization and Content-Type are reserved and cannot be overridden.\n\
 \n\
 enabled = true\n\
-# api_key = \"<your_api_key>\"\n\
+{API_KEY_PLACEHOLDER}\n\
 model = \"{DEFAULT_MODEL}\"\n\
 base_url = \"{DEFAULT_BASE_URL}\"\n\
 # custom_headers = [\"X-Customer-ID: your-customer-id\"]\n"
@@ -146,14 +244,21 @@ fn ensure_required_keys(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Returns the required top-level keys that are missing from assistant.toml content.
+pub fn missing_required_keys(raw: &str) -> Vec<&'static str> {
+    REQUIRED_KEYS
+        .iter()
+        .filter(|(key, _)| !top_level_toml_has_key(raw, key))
+        .map(|(key, _)| *key)
+        .collect()
+}
+
 fn ensure_required_keys_in_content(raw: &str) -> (String, bool) {
-    let mut insert_lines = Vec::new();
-    if !top_level_toml_has_key(raw, "model") {
-        insert_lines.push(format!("model = \"{DEFAULT_MODEL}\""));
-    }
-    if !top_level_toml_has_key(raw, "base_url") {
-        insert_lines.push(format!("base_url = \"{DEFAULT_BASE_URL}\""));
-    }
+    let insert_lines: Vec<String> = REQUIRED_KEYS
+        .iter()
+        .filter(|(key, _)| !top_level_toml_has_key(raw, key))
+        .map(|(key, default)| format!("{key} = \"{default}\""))
+        .collect();
 
     if insert_lines.is_empty() {
         return (raw.to_string(), false);
@@ -177,8 +282,56 @@ fn ensure_required_keys_in_content(raw: &str) -> (String, bool) {
     (updated, true)
 }
 
-fn set_top_level_bool_key_in_content(content: &str, key: &str, value: bool) -> String {
-    let replacement = format!("{key} = {value}");
+/// Sets a top-level boolean key, replacing its line in place or inserting it
+/// before the first table header. Comments and other keys are left untouched.
+pub fn set_top_level_bool_key_in_content(content: &str, key: &str, value: bool) -> String {
+    set_top_level_key_in_content(content, key, &value.to_string())
+}
+
+/// Sets a top-level string key the same way as
+/// [`set_top_level_bool_key_in_content`], quoting and escaping the value.
+pub fn set_top_level_string_key_in_content(content: &str, key: &str, value: &str) -> String {
+    set_top_level_key_in_content(content, key, &render_toml_string(value))
+}
+
+/// Replaces a top-level `api_key` line with the commented placeholder the
+/// template ships with. Content without the key is returned unchanged.
+pub fn clear_api_key_in_content(content: &str) -> String {
+    replace_top_level_key_line(content, "api_key", API_KEY_PLACEHOLDER)
+        .unwrap_or_else(|| content.to_string())
+}
+
+/// Quotes and escapes `value` as a TOML basic string.
+pub fn render_toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn set_top_level_key_in_content(content: &str, key: &str, rendered_value: &str) -> String {
+    let replacement = format!("{key} = {rendered_value}");
+    if let Some(updated) = replace_top_level_key_line(content, key, &replacement) {
+        return updated;
+    }
+
+    let insert_block = format!("{replacement}\n");
+    let insert_at = first_table_header_offset(content).unwrap_or(content.len());
+    let (before, after) = content.split_at(insert_at);
+    let mut updated = String::with_capacity(content.len() + insert_block.len() + 2);
+
+    let before_trimmed = before.trim_end_matches(['\r', '\n']);
+    updated.push_str(before_trimmed);
+    if !before_trimmed.is_empty() {
+        updated.push('\n');
+    }
+    updated.push_str(&insert_block);
+    if !after.is_empty() {
+        updated.push_str(after.trim_start_matches(['\r', '\n']));
+    }
+    updated
+}
+
+/// Swaps every top-level line assigning `key` for `replacement`. Returns
+/// None when no such line exists.
+fn replace_top_level_key_line(content: &str, key: &str, replacement: &str) -> Option<String> {
     let mut updated_lines = Vec::new();
     let mut replaced = false;
     let mut in_top_level = true;
@@ -193,7 +346,7 @@ fn set_top_level_bool_key_in_content(content: &str, key: &str, value: bool) -> S
             let raw_head = line.split('#').next().unwrap_or("").trim();
             if let Some((name, _)) = raw_head.split_once('=') {
                 if name.trim() == key {
-                    updated_lines.push(replacement.clone());
+                    updated_lines.push(replacement.to_string());
                     replaced = true;
                     continue;
                 }
@@ -203,29 +356,14 @@ fn set_top_level_bool_key_in_content(content: &str, key: &str, value: bool) -> S
         updated_lines.push(line.to_string());
     }
 
-    if replaced {
-        let mut updated = updated_lines.join("\n");
-        if content.ends_with('\n') {
-            updated.push('\n');
-        }
-        updated
-    } else {
-        let insert_block = format!("{replacement}\n");
-        let insert_at = first_table_header_offset(content).unwrap_or(content.len());
-        let (before, after) = content.split_at(insert_at);
-        let mut updated = String::with_capacity(content.len() + insert_block.len() + 2);
-
-        let before_trimmed = before.trim_end_matches(['\r', '\n']);
-        updated.push_str(before_trimmed);
-        if !before_trimmed.is_empty() {
-            updated.push('\n');
-        }
-        updated.push_str(&insert_block);
-        if !after.is_empty() {
-            updated.push_str(after.trim_start_matches(['\r', '\n']));
-        }
-        updated
+    if !replaced {
+        return None;
+    }
+    let mut updated = updated_lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
     }
+    Some(updated)
 }
 
 fn first_table_header_offset(content: &str) -> Option<usize> {
@@ -279,6 +417,35 @@ fn top_level_toml_has_key(content: &str, key: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn mask_api_key_keeps_prefix_and_last_four() {
+        assert_eq!(mask_api_key("sk-abcdefghijklmnop1234"), "sk-...1234");
+        assert_eq!(mask_api_key("abcdefghijklmnop1234"), "...1234");
+        assert_eq!(mask_api_key("sk-short"), "****");
+    }
+
+    #[test]
+    fn effective_config_reports_sources_and_masks_key() {
+        let lines = describe_effective_config(
+            "enabled = false\napi_key = \"sk-abcdefghijklmnop1234\"\nmodel = \"\"\n",
+        );
+        let line = |key: &str| {
+            lines
+                .iter()
+                .find(|line| line.starts_with(&format!("{key} ")))
+                .cloned()
+                .unwrap()
+        };
+
+        assert!(line("enabled").contains("false"));
+        assert!(line("enabled").ends_with("(file)"));
+        assert!(line("model").contains(DEFAULT_MODEL));
+        assert!(line("model").ends_with("(default)"));
+        assert!(line("api_key").contains("sk-...1234"));
+        assert!(!lines.iter().any(|line| line.contains("abcdefghijklmnop")));
+        assert!(line("custom_headers").ends_with("(default)"));
+    }
+
     #[test]
     fn top_level_key_check_ignores_table_keys() {
         let content = r#"
@@ -341,4 +508,43 @@ api_key = "x"
         let table_pos = updated.find("[provider]").expect("table exists");
         assert!(enabled_pos < table_pos);
     }
+
+    #[test]
+    fn set_string_key_keeps_comments_and_escapes_value() {
+        let content = "# my notes\nenabled = true\n# pinned for work\nmodel = \"x\"\n";
+        let updated = set_top_level_string_key_in_content(content, "model", "gpt-5 \"mini\"");
+        assert!(updated.starts_with("# my notes\nenabled = true\n# pinned for work\nmodel = "));
+        let parsed = updated.parse::<toml::Value>().expect("valid toml");
+        assert_eq!(
+            parsed.get("model").and_then(|v| v.as_str()),
+            Some("gpt-5 \"mini\"")
+        );
+
+        let inserted = set_top_level_string_key_in_content(content, "api_key", "sk-test");
+        assert!(inserted.starts_with(content));
+        assert!(inserted.ends_with("api_key = \"sk-test\"\n"));
+    }
+
+    #[test]
+    fn clearing_api_key_restores_the_placeholder() {
+        let content = "enabled = true\napi_key = \"sk-test\"\nmodel = \"x\"\n";
+        let cleared = clear_api_key_in_content(content);
+        assert_eq!(
+            cleared,
+            format!("enabled = true\n{API_KEY_PLACEHOLDER}\nmodel = \"x\"\n")
+        );
+        let parsed = cleared.parse::<toml::Value>().expect("valid toml");
+        assert!(parsed.get("api_key").is_none());
+
+        assert_eq!(clear_api_key_in_content(&cleared), cleared);
+    }
+
+    #[test]
+    fn validate_base_url_requires_http_scheme_and_host() {
+        assert!(validate_base_url(DEFAULT_BASE_URL).is_ok());
+        assert!(validate_base_url("http://localhost:8080/v1").is_ok());
+        assert!(validate_base_url("api.example.com/v1").is_err());
+        assert!(validate_base_url("ftp://example.com").is_err());
+        assert!(validate_base_url("https://").is_err());
+    }
 }
@@ -1,25 +1,106 @@
 use anyhow::Context;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
+use crate::config_migrate;
 use crate::config_tui;
+use crate::utils::abbreviate_home;
 
 #[derive(Debug, Parser, Clone, Default)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct ConfigCommand {
     /// Ensure an editable Kaku config file exists, but do not open it.
     #[arg(long, hide = true)]
     ensure_only: bool,
+
+    /// Print the config the settings TUI would save to stdout instead of
+    /// writing it.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Browse settings without being able to change or save them.
+    #[arg(long, conflicts_with = "dry_run")]
+    read_only: bool,
+
+    /// Start the settings TUI with the cursor on the field with this label,
+    /// e.g. "Font Size".
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["export", "import"])]
+    field: Option<String>,
+
+    /// Write the settings managed by the settings TUI to FILE as TOML.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["import", "dry_run"])]
+    export: Option<PathBuf>,
+
+    /// Apply settings previously written by --export and save them.
+    #[arg(long, value_name = "FILE", conflicts_with = "dry_run")]
+    import: Option<PathBuf>,
+
+    #[command(subcommand)]
+    action: Option<ConfigAction>,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+enum ConfigAction {
+    /// Change one setting without opening the settings TUI,
+    /// e.g. `kaku config set font_size 18`.
+    Set {
+        /// Setting label or Lua key, e.g. "Font Size" or font_size.
+        key: String,
+        /// New value; an empty string restores the default.
+        value: String,
+    },
 }
 
 impl ConfigCommand {
     pub fn run(&self, config_path: Option<PathBuf>) -> anyhow::Result<()> {
         let config_path = config_tui::ensure_editable_config_exists(config_path.as_deref())?;
         if self.ensure_only {
-            println!("Ensured config: {}", config_path.display());
+            println!("Ensured config: {}", abbreviate_home(&config_path));
+            return Ok(());
+        }
+
+        if let Some(ConfigAction::Set { key, value }) = &self.action {
+            let (changed, warning) = config_tui::set_setting(config_path.clone(), key, value)?;
+            if let Some(warning) = warning {
+                eprintln!("Warning: {}", warning);
+            }
+            if changed {
+                println!("Updated {} in {}", key, abbreviate_home(&config_path));
+            } else {
+                println!("{} is already set to that value", key);
+            }
+            return Ok(());
+        }
+
+        if let Some(path) = &self.export {
+            let count = config_tui::export_settings(config_path, path)?;
+            println!("Exported {} settings to {}", count, abbreviate_home(path));
             return Ok(());
         }
+        if let Some(path) = &self.import {
+            let (applied, warnings) = config_tui::import_settings(config_path.clone(), path)?;
+            for warning in warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            println!(
+                "Imported {} settings into {}",
+                applied,
+                abbreviate_home(&config_path)
+            );
+            return Ok(());
+        }
+
+        if !self.dry_run && !self.read_only {
+            config_migrate::offer_config_migrations(&config_path)?;
+        }
 
         // Launch TUI
-        config_tui::run(config_path).context("config tui")
+        config_tui::run(
+            config_path,
+            self.dry_run,
+            self.read_only,
+            self.field.as_deref(),
+        )
+        .context("config tui")
     }
 }
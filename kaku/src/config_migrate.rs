@@ -0,0 +1,135 @@
+use anyhow::Context;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use crate::utils::{abbreviate_home, write_atomic};
+
+/// Header of the theme block Kaku manages in `kaku.lua`.
+pub(crate) const THEME_MARKER: &str = "-- ===== Kaku Theme Defaults (managed) =====";
+/// Header older Kaku versions wrote in place of `THEME_MARKER`.
+pub(crate) const LEGACY_THEME_MARKER: &str = "-- ===== Kaku Theme =====";
+
+/// A block older Kaku versions wrote into `kaku.lua`, and how to bring it up
+/// to date. `apply` returns `None` when the config has nothing to migrate.
+struct Migration {
+    summary: &'static str,
+    apply: fn(&str) -> Option<String>,
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    summary: "renamed the legacy `-- ===== Kaku Theme =====` marker to the managed form",
+    apply: rename_legacy_theme_marker,
+}];
+
+/// Runs every migration over `content`, returning the rewritten config and
+/// the summary of each migration that changed it.
+fn migrate_content(content: &str) -> (String, Vec<&'static str>) {
+    let mut content = content.to_string();
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS {
+        if let Some(migrated) = (migration.apply)(&content) {
+            content = migrated;
+            applied.push(migration.summary);
+        }
+    }
+    (content, applied)
+}
+
+/// Older versions marked the theme block with a bare `Kaku Theme` header.
+/// Only a header line of its own, followed by the `return config` that closes
+/// the managed block, is rewritten; anything else is left to the user.
+fn rename_legacy_theme_marker(content: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.iter().any(|line| line.trim() == THEME_MARKER) {
+        return None;
+    }
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == LEGACY_THEME_MARKER)?;
+    lines
+        .iter()
+        .skip(start + 1)
+        .find(|line| line.trim() == "return config")?;
+
+    let indent = &lines[start][..lines[start].len() - lines[start].trim_start().len()];
+    let mut out: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    out[start] = format!("{}{}", indent, THEME_MARKER);
+
+    let mut merged = out.join("\n");
+    if content.ends_with('\n') {
+        merged.push('\n');
+    }
+    Some(merged)
+}
+
+/// Checks the config at `config_path` for legacy Kaku-managed blocks and, on
+/// an interactive terminal, offers to rewrite them. A missing or unreadable
+/// config is left alone since the caller reports those on its own.
+pub fn offer_config_migrations(config_path: &Path) -> anyhow::Result<()> {
+    let Ok(original) = std::fs::read_to_string(config_path) else {
+        return Ok(());
+    };
+    let (migrated, applied) = migrate_content(&original);
+    if applied.is_empty() {
+        return Ok(());
+    }
+
+    let label = abbreviate_home(config_path);
+    println!("Found legacy Kaku config in {}:", label);
+    for summary in &applied {
+        println!("  - {}", summary);
+    }
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        println!("Run `kaku config` in a terminal to migrate it.");
+        return Ok(());
+    }
+
+    print!("Migrate now? [Y/n] ");
+    io::stdout().flush().context("flush stdout")?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .context("read migration confirmation")?;
+    let answer = input.trim().to_ascii_lowercase();
+    if !(answer.is_empty() || answer == "y" || answer == "yes") {
+        println!("Left {} unchanged.", label);
+        return Ok(());
+    }
+
+    write_atomic(config_path, migrated.as_bytes())
+        .with_context(|| format!("write {}", config_path.display()))?;
+    println!("Migrated {}.", label);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renames_legacy_theme_marker_before_return() {
+        let content = "local config = {}\nconfig.font_size = 15\n\n-- ===== Kaku Theme =====\nconfig.color_scheme = 'Kaku Dark'\nreturn config\n";
+        let (migrated, applied) = migrate_content(content);
+
+        assert_eq!(applied.len(), 1);
+        assert_eq!(
+            migrated,
+            "local config = {}\nconfig.font_size = 15\n\n-- ===== Kaku Theme Defaults (managed) =====\nconfig.color_scheme = 'Kaku Dark'\nreturn config\n"
+        );
+        assert_eq!(migrate_content(&migrated).1.len(), 0);
+    }
+
+    #[test]
+    fn leaves_unterminated_or_inline_markers_alone() {
+        let unterminated = "local config = {}\n-- ===== Kaku Theme =====\nconfig.font_size = 15\n";
+        assert_eq!(migrate_content(unterminated).1.len(), 0);
+
+        let inline =
+            "local config = {}\nconfig.font_size = 15 -- ===== Kaku Theme =====\nreturn config\n";
+        assert_eq!(migrate_content(inline).1.len(), 0);
+
+        let both = "local config = {}\n-- ===== Kaku Theme =====\n-- ===== Kaku Theme Defaults (managed) =====\nreturn config\n";
+        assert_eq!(migrate_content(both).1.len(), 0);
+    }
+}
@@ -1,7 +1,10 @@
 mod ui;
 
 use crate::assistant_config;
-use crate::utils::open_path_in_editor;
+use crate::utils::{
+    abbreviate_home, copy_to_clipboard, file_modified_time, open_path_in_editor,
+    open_path_in_editor_read_only, signal_config_changed,
+};
 use anyhow::Context;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use crossterm::terminal::{
@@ -10,46 +13,319 @@ use crossterm::terminal::{
 use crossterm::ExecutableCommand;
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use std::io;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, IsTerminal};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 const KAKU_AUTO_COLOR_SCHEME_EXPR: &str =
     "(wezterm.gui and wezterm.gui.get_appearance() or 'Dark'):find('Dark') and 'Kaku Dark' or 'Kaku Light'";
 
+/// Copy on select destinations: TUI label and `ClipboardCopyDestination` variant.
+const COPY_DESTINATIONS: &[(&str, &str)] = &[
+    ("Clipboard + Primary", "ClipboardAndPrimarySelection"),
+    ("Clipboard", "Clipboard"),
+    ("Primary Selection", "PrimarySelection"),
+];
+
+/// Fields edited as raw Lua tables, with the table offered when none is set.
+const TABLE_FIELDS: &[(&str, &str)] = &[(
+    "window_padding",
+    "{\n  left = '40px',\n  right = '40px',\n  top = '40px',\n  bottom = '0px',\n}",
+)];
+
+/// Checks text typed for a field, returning the normalized value to store or
+/// a message saying why it was rejected.
+type Validator = fn(&str) -> Result<String, String>;
+
+/// Validators for fields edited as free text, keyed by `lua_key`. Fields
+/// without an entry accept any text.
+const VALIDATORS: &[(&str, Validator)] = &[
+    ("font_size", validate_number),
+    ("line_height", validate_number),
+    ("window_background_opacity", validate_number),
+    ("macos_window_background_blur", validate_number),
+    ("macos_global_hotkey", validate_hotkey),
+];
+
+/// File under `config::DATA_DIR` that remembers the cursor and collapsed
+/// sections between runs.
+const VIEW_STATE_FILE: &str = "config_tui_state";
+
+/// A `kaku.lua.tmp` older than this was left behind by a save that never got
+/// to rename it, rather than one still in progress.
+const STALE_TEMP_AGE: std::time::Duration = std::time::Duration::from_secs(5);
+
+const HOTKEY_MODIFIER_ORDER: [&str; 4] = ["CTRL", "ALT", "SUPER", "SHIFT"];
+
+/// System shortcuts that a global hotkey would shadow, with what they do.
+const RESERVED_HOTKEYS: &[(&str, &str)] = &[
+    ("Cmd+Q", "quits the frontmost app"),
+    ("Cmd+W", "closes the frontmost window"),
+    ("Cmd+H", "hides the frontmost app"),
+    ("Cmd+M", "minimizes the frontmost window"),
+    ("Cmd+Tab", "switches apps"),
+    ("Cmd+Space", "opens Spotlight"),
+    ("Ctrl+Space", "switches input sources"),
+    ("Cmd+Shift+3", "takes a screenshot"),
+    ("Cmd+Shift+4", "takes a screenshot"),
+    ("Cmd+Shift+5", "opens the screenshot toolbar"),
+];
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum NormalModeAction {
     ExitAndSave,
     ExitDiscard,
     OpenEditor,
+    PreviewChanges,
+    CopyValue,
     MoveUp,
     MoveDown,
+    PageUp,
+    PageDown,
+    MoveFirst,
+    MoveLast,
     StartEdit,
     Noop,
 }
 
-pub fn run(config_path: PathBuf) -> anyhow::Result<()> {
+type PanicHook = Box<dyn Fn(&std::panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// Undoes raw mode and the alternate screen when dropped, so early returns
+/// and unwinding panics never leave the user's terminal garbled.
+struct TerminalGuard {
+    raw_mode: bool,
+    alternate_screen: bool,
+    restore: fn(raw_mode: bool, alternate_screen: bool),
+}
+
+impl TerminalGuard {
+    fn new() -> Self {
+        Self {
+            raw_mode: false,
+            alternate_screen: false,
+            restore: restore_terminal,
+        }
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        (self.restore)(self.raw_mode, self.alternate_screen);
+    }
+}
+
+fn restore_terminal(raw_mode: bool, alternate_screen: bool) {
+    if raw_mode {
+        let _ = disable_raw_mode();
+    }
+    if alternate_screen {
+        let _ = io::stdout().execute(LeaveAlternateScreen);
+    }
+}
+
+/// Compiles `source` without running it. Only syntax is checked, so
+/// `require` and friends never execute.
+#[cfg(feature = "lua-check")]
+fn check_lua_syntax(source: &str) -> Result<(), String> {
+    mlua::Lua::new()
+        .load(source)
+        .set_name("kaku.lua")
+        .into_function()
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "lua-check"))]
+fn check_lua_syntax(_source: &str) -> Result<(), String> {
+    Ok(())
+}
+
+/// Re-reads the file just saved at `path` and puts `original` back if the
+/// save turned valid Lua into something kaku-gui cannot load. A config that
+/// was already broken before the save is left to the user.
+fn verify_saved_lua(path: &Path, original: &str) -> anyhow::Result<()> {
+    let saved =
+        std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    let Err(err) = check_lua_syntax(&saved) else {
+        return Ok(());
+    };
+    if check_lua_syntax(original).is_err() {
+        return Ok(());
+    }
+
+    crate::utils::write_atomic(path, original.as_bytes())
+        .with_context(|| format!("restore {} after an invalid save", path.display()))?;
+    anyhow::bail!(
+        "saved config is not valid Lua, previous version restored: {}",
+        err
+    )
+}
+
+/// Word motions stop at whitespace and at the `+` between hotkey parts.
+fn is_word_separator(c: char) -> bool {
+    c.is_whitespace() || c == '+'
+}
+
+/// Says why the TUI cannot run on this terminal, or `None` when it can.
+fn unsupported_terminal_reason(
+    stdin_is_terminal: bool,
+    stdout_is_terminal: bool,
+    term: Option<&str>,
+) -> Option<&'static str> {
+    if !stdin_is_terminal || !stdout_is_terminal {
+        Some("stdin or stdout is not a terminal")
+    } else if term == Some("dumb") {
+        Some("TERM is dumb")
+    } else {
+        None
+    }
+}
+
+/// Release builds abort on panic, so Drop guards never run. Restore the
+/// terminal from a panic hook before the previous hook prints the message.
+fn install_panic_hook() -> Arc<PanicHook> {
+    let previous: Arc<PanicHook> = Arc::new(std::panic::take_hook());
+    let chained = Arc::clone(&previous);
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal(true, true);
+        chained(info);
+    }));
+    previous
+}
+
+fn restore_panic_hook(previous: Arc<PanicHook>) {
+    let _ = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| previous(info)));
+}
+
+/// Runs the settings TUI. With `dry_run`, saving prints the rendered config
+/// to stdout after the TUI exits instead of writing `kaku.lua`. With
+/// `read_only`, values can be browsed but never changed or saved. With
+/// `focus_field`, the cursor starts on the field with that label.
+pub fn run(
+    config_path: PathBuf,
+    dry_run: bool,
+    read_only: bool,
+    focus_field: Option<&str>,
+) -> anyhow::Result<()> {
+    let term = std::env::var("TERM").ok();
+    if let Some(reason) = unsupported_terminal_reason(
+        io::stdin().is_terminal(),
+        io::stdout().is_terminal(),
+        term.as_deref(),
+    ) {
+        anyhow::bail!(
+            "the settings TUI needs an interactive terminal ({}); edit {} in your editor instead",
+            reason,
+            abbreviate_home(&config_path)
+        );
+    }
+
+    // The guard undoes whatever part of the setup succeeded, so a failure
+    // between raw mode and the alternate screen leaves the terminal usable.
+    let mut guard = TerminalGuard::new();
     enable_raw_mode().context("enable raw mode")?;
+    guard.raw_mode = true;
     let mut stdout = io::stdout();
     stdout
         .execute(EnterAlternateScreen)
         .context("enter alternate screen")?;
+    guard.alternate_screen = true;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).context("create terminal")?;
 
+    let previous_hook = install_panic_hook();
+
     let mut app = App::new(config_path);
+    app.dry_run = dry_run;
+    app.read_only = read_only;
+    app.explicit_defaults = std::env::var("KAKU_CONFIG_EXPLICIT_DEFAULTS")
+        .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "yes" | "on"));
+    app.view_state_path = Some(config::DATA_DIR.join(VIEW_STATE_FILE));
     app.load_config();
+    if !dry_run && !read_only && app.read_error.is_none() {
+        if let Err(err) = check_config_writable(&app.config_path) {
+            app.read_error = Some(format!(
+                "Cannot write {}: {}, saving is disabled",
+                abbreviate_home(&app.config_path),
+                err
+            ));
+        }
+    }
+    if let Some(name) = focus_field {
+        app.focus_field(name);
+    }
 
     let result = run_app(&mut terminal, &mut app);
+    // Losing the remembered cursor is harmless; never fail the exit over it.
+    let _ = app.persist_view_state();
 
-    disable_raw_mode().context("disable raw mode")?;
-    terminal
-        .backend_mut()
-        .execute(LeaveAlternateScreen)
-        .context("leave alternate screen")?;
+    restore_panic_hook(previous_hook);
+    drop(guard);
+
+    if let Some(output) = app.dry_run_output.take() {
+        print!("{}", output);
+    }
 
     result
 }
 
+/// Writes the settings the TUI manages to `path` as TOML, so they can be
+/// shared without copying the whole Lua config. Returns how many were written.
+pub fn export_settings(config_path: PathBuf, path: &Path) -> anyhow::Result<usize> {
+    let mut app = App::new(config_path);
+    app.load_config();
+    if let Some(err) = &app.read_error {
+        anyhow::bail!("{}", err);
+    }
+
+    let values = app.export_values();
+    let content = toml::to_string(&values).context("serialize settings")?;
+    std::fs::write(path, content).with_context(|| format!("write {}", path.display()))?;
+    Ok(values.len())
+}
+
+/// Loads settings written by `export_settings` and saves them into the config.
+/// Returns how many settings changed and a warning per skipped entry.
+pub fn import_settings(config_path: PathBuf, path: &Path) -> anyhow::Result<(usize, Vec<String>)> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    let values: toml::Table = raw
+        .parse()
+        .with_context(|| format!("parse {}", path.display()))?;
+
+    let mut app = App::new(config_path);
+    app.load_config();
+    if let Some(err) = &app.read_error {
+        anyhow::bail!("{}", err);
+    }
+
+    let (applied, warnings) = app.import_values(&values);
+    app.save_if_dirty()?;
+    Ok((applied, warnings))
+}
+
+/// Changes one setting without the TUI, through the same field table,
+/// validation, and save path. `name` is a label or Lua key, e.g. "Font Size"
+/// or `font_size`. Returns whether the config changed plus any warning about
+/// the new value.
+pub fn set_setting(
+    config_path: PathBuf,
+    name: &str,
+    raw: &str,
+) -> anyhow::Result<(bool, Option<String>)> {
+    let mut app = App::new(config_path);
+    app.load_config();
+    if let Some(err) = &app.read_error {
+        anyhow::bail!("{}", err);
+    }
+
+    let changed = app.set_value(name, raw).map_err(anyhow::Error::msg)?;
+    let warning = app.notice.take();
+    app.save_if_dirty()?;
+    Ok((changed, warning))
+}
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
@@ -64,10 +340,20 @@ fn run_app(
             Err(e) => return Err(e.into()),
         };
 
-        let Event::Key(key) = event else { continue };
+        let key = match event {
+            Event::Key(key) => key,
+            // Redraw right away at the new size instead of waiting for a key.
+            Event::Resize(_, _) => {
+                terminal.autoresize().context("resize terminal")?;
+                continue;
+            }
+            _ => continue,
+        };
         if key.kind != KeyEventKind::Press {
             continue;
         }
+        app.saved_flash = false;
+        app.copied_flash = false;
 
         if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
             app.finalize_active_input();
@@ -77,6 +363,14 @@ fn run_app(
             return Ok(());
         }
 
+        if app.mode == Mode::Normal
+            && key.code == KeyCode::Char('s')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            app.save_now();
+            continue;
+        }
+
         match app.mode {
             Mode::Normal => match normal_mode_action(key.code) {
                 NormalModeAction::ExitAndSave => {
@@ -93,19 +387,38 @@ fn run_app(
                         return Err(e);
                     }
                     let config_path = app.config_path();
-                    if let Err(e) =
-                        with_terminal_suspended(terminal, || open_config_in_editor(&config_path))
-                    {
+                    let read_only = app.read_only;
+                    if let Err(e) = with_terminal_suspended(terminal, || {
+                        open_config_in_editor(&config_path, read_only)
+                    }) {
                         return Err(e);
                     }
                     return Ok(());
                 }
+                NormalModeAction::PreviewChanges => {
+                    app.open_preview();
+                }
+                NormalModeAction::CopyValue => {
+                    app.copy_selected_value(copy_to_clipboard);
+                }
                 NormalModeAction::MoveUp => {
                     app.move_up();
                 }
                 NormalModeAction::MoveDown => {
                     app.move_down();
                 }
+                NormalModeAction::PageUp => {
+                    app.page_up();
+                }
+                NormalModeAction::PageDown => {
+                    app.page_down();
+                }
+                NormalModeAction::MoveFirst => {
+                    app.move_first();
+                }
+                NormalModeAction::MoveLast => {
+                    app.move_last();
+                }
                 NormalModeAction::StartEdit => {
                     app.start_edit();
                 }
@@ -118,9 +431,28 @@ fn run_app(
                 KeyCode::Enter => {
                     app.confirm_edit();
                 }
+                KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.edit_clear();
+                }
                 KeyCode::Backspace => {
                     app.edit_backspace();
                 }
+                KeyCode::Delete => {
+                    app.edit_delete();
+                }
+                KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.edit_delete();
+                }
+                // Terminals send Option+Left/Right either as Alt+arrow or as
+                // the readline Alt+b / Alt+f sequences.
+                KeyCode::Left | KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    app.edit_word_left();
+                }
+                KeyCode::Right | KeyCode::Char('f')
+                    if key.modifiers.contains(KeyModifiers::ALT) =>
+                {
+                    app.edit_word_right();
+                }
                 KeyCode::Left => {
                     app.edit_cursor_left();
                 }
@@ -137,11 +469,58 @@ fn run_app(
                 }
                 _ => {}
             },
+            Mode::EditingTable => match key.code {
+                KeyCode::Esc => {
+                    app.cancel_edit();
+                }
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.confirm_table_edit();
+                }
+                KeyCode::Enter => {
+                    app.edit_insert('\n');
+                }
+                KeyCode::Tab => {
+                    app.edit_insert(' ');
+                    app.edit_insert(' ');
+                }
+                KeyCode::Backspace => {
+                    app.edit_backspace();
+                }
+                KeyCode::Delete => {
+                    app.edit_delete();
+                }
+                KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.edit_delete();
+                }
+                KeyCode::Left => {
+                    app.edit_cursor_left();
+                }
+                KeyCode::Right => {
+                    app.edit_cursor_right();
+                }
+                KeyCode::Up => {
+                    app.edit_cursor_up();
+                }
+                KeyCode::Down => {
+                    app.edit_cursor_down();
+                }
+                KeyCode::Char(c) => {
+                    if !key.modifiers.contains(KeyModifiers::CONTROL)
+                        && !key.modifiers.contains(KeyModifiers::SUPER)
+                    {
+                        app.edit_insert(c);
+                    }
+                }
+                _ => {}
+            },
             Mode::Selecting => match key.code {
                 KeyCode::Esc => {
                     // ESC in selector = confirm the highlighted option and exit,
                     // matching the "ESC saves and exits" mental model of Normal mode.
                     app.confirm_select();
+                    if app.mode == Mode::ConfirmingOverwrite {
+                        continue;
+                    }
                     if let Err(e) = app.save_if_dirty() {
                         return Err(e);
                     }
@@ -158,6 +537,27 @@ fn run_app(
                 }
                 _ => {}
             },
+            Mode::ConfirmingOverwrite => match key.code {
+                KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    app.confirm_overwrite();
+                }
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                    app.cancel_overwrite();
+                }
+                _ => {}
+            },
+            Mode::Previewing => match key.code {
+                KeyCode::Enter => {
+                    if let Err(e) = app.save_if_dirty() {
+                        return Err(e);
+                    }
+                    return Ok(());
+                }
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('d') | KeyCode::Char('D') => {
+                    app.close_preview();
+                }
+                _ => {}
+            },
         }
     }
 }
@@ -167,8 +567,14 @@ fn normal_mode_action(key: KeyCode) -> NormalModeAction {
         KeyCode::Esc => NormalModeAction::ExitAndSave,
         KeyCode::Char('q') | KeyCode::Char('Q') => NormalModeAction::ExitDiscard,
         KeyCode::Char('e') | KeyCode::Char('E') => NormalModeAction::OpenEditor,
+        KeyCode::Char('d') | KeyCode::Char('D') => NormalModeAction::PreviewChanges,
+        KeyCode::Char('y') | KeyCode::Char('Y') => NormalModeAction::CopyValue,
         KeyCode::Up | KeyCode::Char('k') => NormalModeAction::MoveUp,
         KeyCode::Down | KeyCode::Char('j') => NormalModeAction::MoveDown,
+        KeyCode::PageUp => NormalModeAction::PageUp,
+        KeyCode::PageDown => NormalModeAction::PageDown,
+        KeyCode::Home | KeyCode::Char('g') => NormalModeAction::MoveFirst,
+        KeyCode::End | KeyCode::Char('G') => NormalModeAction::MoveLast,
         KeyCode::Enter | KeyCode::Char(' ') => NormalModeAction::StartEdit,
         _ => NormalModeAction::Noop,
     }
@@ -202,6 +608,82 @@ where
     action_result.and(restore_result)
 }
 
+/// True when `content` declares the `config` table that `config.xxx = ...`
+/// lines assign into, e.g. `local config = wezterm.config_builder()`.
+fn has_config_scaffold(content: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("--"))
+        .any(|line| {
+            let line = line.strip_prefix("local ").unwrap_or(line).trim_start();
+            line.strip_prefix("config")
+                .map(str::trim_start)
+                .is_some_and(|rest| rest.starts_with('=') && !rest.starts_with("=="))
+        })
+}
+
+/// A blank config has nowhere to put new lines, so saving starts from the
+/// same template a fresh config gets.
+fn with_config_scaffold(content: &str) -> &str {
+    if content.trim().is_empty() {
+        config::minimal_user_config_template()
+    } else {
+        content
+    }
+}
+
+/// Runs the validator registered for `lua_key` over `raw`. Empty input always
+/// passes since it clears the field back to its default.
+fn validate(lua_key: &str, raw: &str) -> Result<String, String> {
+    if raw.is_empty() {
+        return Ok(String::new());
+    }
+    match VALIDATORS.iter().find(|(key, _)| *key == lua_key) {
+        Some((_, validator)) => validator(raw),
+        None => Ok(raw.to_string()),
+    }
+}
+
+fn validate_number(raw: &str) -> Result<String, String> {
+    if App::is_number_literal(raw) {
+        Ok(raw.trim().to_string())
+    } else {
+        Err(format!("{:?} is not a number", raw.trim()))
+    }
+}
+
+fn validate_hotkey(raw: &str) -> Result<String, String> {
+    App::canonical_hotkey(raw).ok_or_else(|| {
+        format!(
+            "{:?} is not a hotkey, use a form like Cmd+Shift+K",
+            raw.trim()
+        )
+    })
+}
+
+/// `KAKU_CONFIG_FILE` as an explicit config for the settings TUI. kaku-gui
+/// exports the file it loaded under the same name, which is the bundled
+/// defaults when the user has no config; that file is never edited in place.
+pub(crate) fn config_file_from_env() -> Option<PathBuf> {
+    let path = PathBuf::from(std::env::var_os("KAKU_CONFIG_FILE").filter(|v| !v.is_empty())?);
+    (!is_bundled_config(&path)).then_some(path)
+}
+
+fn is_bundled_config(path: &Path) -> bool {
+    path.parent()
+        .is_some_and(|dir| dir.ends_with("Contents/Resources"))
+}
+
+/// Checks that the config can be opened for writing, so a session on an
+/// unwritable file is browse-only from the start instead of failing on save.
+fn check_config_writable(path: &Path) -> io::Result<()> {
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(path)
+        .map(drop)
+}
+
 pub(crate) fn ensure_editable_config_exists(config_path: Option<&Path>) -> anyhow::Result<PathBuf> {
     if let Some(path) = config_path {
         return config::ensure_config_exists_at_path(path);
@@ -214,7 +696,20 @@ pub(crate) fn ensure_editable_config_exists(config_path: Option<&Path>) -> anyho
 enum Mode {
     Normal,
     Editing,
+    /// Multi-line editor for table-valued fields, see `TABLE_FIELDS`.
+    EditingTable,
     Selecting,
+    Previewing,
+    /// Asking before a new value replaces a config line the TUI could not
+    /// parse, see `App::pending_overwrite`.
+    ConfirmingOverwrite,
+}
+
+/// A row in the settings list: either a section header or a field (by index).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ListRow {
+    Section(&'static str),
+    Field(usize),
 }
 
 #[derive(Clone)]
@@ -223,32 +718,103 @@ struct ConfigField {
     key: &'static str,
     lua_key: &'static str,
     value: String,
+    /// Value as read by `load_config`, used to flag fields modified this session.
+    original_value: String,
     default: String,
     options: Vec<&'static str>,
     /// If true, the field's config line exists but could not be fully parsed.
     /// save_config will leave the line untouched to avoid corrupting user config.
     skip_write: bool,
+    /// Right-hand side of the config line that could not be parsed, shown in
+    /// place of the default so the preserved setting stays visible.
+    raw_value: Option<String>,
 }
 
 impl ConfigField {
     fn has_options(&self) -> bool {
         !self.options.is_empty()
     }
+
+    /// True when the effective value differs from what was loaded from disk.
+    fn is_modified(&self) -> bool {
+        let effective = |value: &str| {
+            if value.is_empty() {
+                self.default.clone()
+            } else {
+                value.to_string()
+            }
+        };
+        effective(&self.value) != effective(&self.original_value)
+    }
+}
+
+/// Source around the primary family of a `wezterm.font_with_fallback` line,
+/// so saving a new font swaps only that name and keeps the fallback list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct FontFallbackTemplate {
+    prefix: String,
+    suffix: String,
+}
+
+/// A config line that saving would add, remove, or replace.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct PendingChange {
+    key: &'static str,
+    before: Option<String>,
+    after: Option<String>,
 }
 
 struct App {
     config_path: PathBuf,
     fields: Vec<ConfigField>,
     selected: usize,
+    /// Section header under the cursor. When set, Enter toggles that section
+    /// instead of editing `fields[selected]`.
+    selected_section: Option<&'static str>,
+    /// Collapsed sections; their fields are hidden from the list.
+    collapsed_sections: BTreeSet<&'static str>,
+    /// Where the cursor and collapsed sections are kept between runs. Unset
+    /// in tests so they never touch the real data directory.
+    view_state_path: Option<PathBuf>,
+    /// Height of the field list as last drawn, the PageUp/PageDown step.
+    list_height: u16,
     mode: Mode,
     edit_buffer: String,
     edit_cursor: usize,
     /// Original value before editing, used to revert on invalid input.
     edit_original: String,
     select_index: usize,
+    /// Changes shown in the preview popup, computed when it is opened.
+    preview: Vec<PendingChange>,
+    /// Value for the selected `skip_write` field, applied once the user
+    /// confirms replacing its custom config line.
+    pending_overwrite: Option<String>,
+    /// Problems found while loading, shown above the footer.
+    warnings: Vec<String>,
+    /// Message from the last edit; shown above the footer in place of `warnings`.
+    notice: Option<String>,
+    /// Set when the config exists but could not be read, or has no `config`
+    /// table for new lines to assign into. Saving is disabled so the file is
+    /// never replaced with something broken.
+    read_error: Option<String>,
     dirty: bool,
+    /// Set by Ctrl+S; shows "Saved" in the footer until the next key press.
+    saved_flash: bool,
+    /// Set after `y` copies a value, cleared on the next key like `saved_flash`.
+    copied_flash: bool,
     /// True if save_config() was called at least once (for signaling on exit)
     has_saved: bool,
+    /// Render saves into `dry_run_output` instead of writing the config file.
+    dry_run: bool,
+    dry_run_output: Option<String>,
+    /// Browse-only session: edits are refused and nothing is ever saved.
+    read_only: bool,
+    /// Set when the font is configured with `wezterm.font_with_fallback`.
+    font_fallback: Option<FontFallbackTemplate>,
+    /// Write every managed key, defaults included, instead of removing lines
+    /// that match the default. Set by `config.kaku_config_explicit_defaults`
+    /// or `KAKU_CONFIG_EXPLICIT_DEFAULTS`.
+    explicit_defaults: bool,
 }
 
 impl App {
@@ -260,144 +826,220 @@ impl App {
                 key: "Theme",
                 lua_key: "color_scheme",
                 value: String::new(),
+                original_value: String::new(),
                 default: "Kaku Dark".into(),
                 options: vec!["Kaku Dark", "Kaku Light", "Auto"],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Appearance",
                 key: "Font",
                 lua_key: "font",
                 value: String::new(),
+                original_value: String::new(),
                 default: "JetBrains Mono".into(),
                 options: vec![],
                 skip_write: false,
+                raw_value: None,
+            },
+            ConfigField {
+                section: "Appearance",
+                key: "Ligatures",
+                lua_key: "harfbuzz_features",
+                value: String::new(),
+                original_value: String::new(),
+                default: "Off".into(),
+                options: vec!["On", "Off"],
+                skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Appearance",
                 key: "Font Size",
                 lua_key: "font_size",
                 value: String::new(),
+                original_value: String::new(),
                 default: "17".into(),
                 options: vec![],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Appearance",
                 key: "Line Height",
                 lua_key: "line_height",
                 value: String::new(),
+                original_value: String::new(),
                 default: "1.28".into(),
                 options: vec![],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Integrations",
                 key: "Global Hotkey",
                 lua_key: "macos_global_hotkey",
                 value: String::new(),
+                original_value: String::new(),
                 default: "Ctrl+Alt+Cmd+K".into(),
                 options: vec![],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Integrations",
                 key: "Kaku Assistant",
                 lua_key: "__assistant_enabled__",
                 value: String::new(),
+                original_value: String::new(),
                 default: "On".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Window",
                 key: "Tab Bar Position",
                 lua_key: "tab_bar_at_bottom",
                 value: String::new(),
+                original_value: String::new(),
                 default: "Bottom".into(),
                 options: vec!["Bottom", "Top"],
                 skip_write: false,
+                raw_value: None,
+            },
+            ConfigField {
+                section: "Window",
+                key: "Tab Bar Visibility",
+                lua_key: "hide_tab_bar_if_only_one_tab",
+                value: String::new(),
+                original_value: String::new(),
+                default: "Auto".into(),
+                options: vec!["Auto", "Always"],
+                skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Window",
                 key: "Scrollbar",
                 lua_key: "enable_scroll_bar",
                 value: String::new(),
+                original_value: String::new(),
                 default: "Off".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Window",
                 key: "Shadow",
                 lua_key: "window_decorations",
                 value: String::new(),
+                original_value: String::new(),
                 default: "On".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Window",
                 key: "Background Opacity",
                 lua_key: "window_background_opacity",
                 value: String::new(),
+                original_value: String::new(),
                 default: "1.0".into(),
                 options: vec![],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Window",
                 key: "Background Blur",
                 lua_key: "macos_window_background_blur",
                 value: String::new(),
+                original_value: String::new(),
                 default: "0".into(),
                 options: vec![],
                 skip_write: false,
+                raw_value: None,
+            },
+            ConfigField {
+                section: "Window",
+                key: "Padding",
+                lua_key: "window_padding",
+                value: String::new(),
+                original_value: String::new(),
+                default: "Auto".into(),
+                options: vec![],
+                skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Behavior",
                 key: "Copy on Select",
                 lua_key: "copy_on_select",
                 value: String::new(),
+                original_value: String::new(),
                 default: "On".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
+            },
+            ConfigField {
+                section: "Behavior",
+                key: "Copy Destination",
+                lua_key: "copy_on_select_destination",
+                value: String::new(),
+                original_value: String::new(),
+                default: "Clipboard + Primary".into(),
+                options: vec!["Clipboard + Primary", "Clipboard", "Primary Selection"],
+                skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Behavior",
                 key: "Confirm Tab Close",
                 lua_key: "tab_close_confirmation",
                 value: String::new(),
+                original_value: String::new(),
                 default: "Off".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Behavior",
                 key: "Confirm Pane Close",
                 lua_key: "pane_close_confirmation",
                 value: String::new(),
+                original_value: String::new(),
                 default: "Off".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Behavior",
                 key: "Bell Tab Indicator",
                 lua_key: "bell_tab_indicator",
                 value: String::new(),
+                original_value: String::new(),
                 default: "On".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
             },
             ConfigField {
                 section: "Behavior",
                 key: "Bell Dock Badge",
                 lua_key: "bell_dock_badge",
                 value: String::new(),
+                original_value: String::new(),
                 default: "Off".into(),
                 options: vec!["On", "Off"],
                 skip_write: false,
+                raw_value: None,
             },
         ];
 
@@ -405,17 +1047,41 @@ impl App {
             config_path,
             fields,
             selected: 0,
+            selected_section: None,
+            collapsed_sections: BTreeSet::new(),
+            view_state_path: None,
+            list_height: 0,
             mode: Mode::Normal,
             edit_buffer: String::new(),
             edit_cursor: 0,
             edit_original: String::new(),
             select_index: 0,
+            preview: Vec::new(),
+            pending_overwrite: None,
+            warnings: Vec::new(),
+            notice: None,
+            read_error: None,
             dirty: false,
+            saved_flash: false,
+            copied_flash: false,
             has_saved: false,
+            dry_run: false,
+            dry_run_output: None,
+            read_only: false,
+            font_fallback: None,
+            explicit_defaults: false,
         }
     }
 
     fn load_config(&mut self) {
+        self.read_config_values();
+        for field in &mut self.fields {
+            field.original_value = field.value.clone();
+        }
+        self.restore_view_state();
+    }
+
+    fn read_config_values(&mut self) {
         if let Some(field) = self
             .fields
             .iter_mut()
@@ -435,51 +1101,118 @@ impl App {
 
         let content = match std::fs::read_to_string(&config_path) {
             Ok(c) => c,
-            Err(_) => return,
+            Err(err) => {
+                self.read_error = Some(format!(
+                    "Cannot read {}: {}, saving is disabled",
+                    abbreviate_home(&config_path),
+                    err
+                ));
+                return;
+            }
         };
 
+        // `config.xxx = ...` lines need a `config` table to assign into. A blank
+        // file gets the starter template on save; anything else is left alone.
+        if !content.trim().is_empty() && !has_config_scaffold(&content) {
+            self.read_error = Some(format!(
+                "No `local config = ...` in {}, saving is disabled",
+                abbreviate_home(&config_path)
+            ));
+        }
+
+        self.font_fallback = Self::find_value_part(&content, "font")
+            .and_then(Self::split_font_with_fallback)
+            .map(|(_, template)| template);
+        if Self::extract_lua_value(&content, "kaku_config_explicit_defaults")
+            .and_then(|raw| Self::parse_lua_bool(&raw))
+            == Some(true)
+        {
+            self.explicit_defaults = true;
+        }
+
+        let mut duplicate_keys: Vec<&str> = Vec::new();
         for i in 0..self.fields.len() {
             let lua_key = self.fields[i].lua_key;
-            match Self::extract_lua_value(&content, lua_key) {
+            // extract_lua_value only sees the first assignment, so a later duplicate
+            // would silently win in the GUI. Leave such keys untouched on save.
+            if Self::count_config_lines(&content, lua_key) > 1 {
+                self.fields[i].skip_write = true;
+                duplicate_keys.push(lua_key);
+            }
+            let unparsed = match Self::extract_lua_value(&content, lua_key) {
                 Some(val) => match Self::normalize_value(lua_key, &val) {
-                    Some(normalized) => self.fields[i].value = normalized,
+                    Some(normalized) => {
+                        self.fields[i].value = normalized;
+                        false
+                    }
                     // Recognized key, but value format is unsupported.
                     // Mark skip_write so save never corrupts this line.
-                    None => self.fields[i].skip_write = true,
+                    None => true,
                 },
-                None => {
-                    // extract_lua_value returns None when the wezterm.* guard fires
-                    // (line exists but value is an unsupported API call).
-                    // Only set skip_write when a config line actually exists for this key.
-                    if Self::has_config_line(&content, lua_key) {
-                        self.fields[i].skip_write = true;
-                    }
-                }
+                // extract_lua_value returns None when the wezterm.* guard fires
+                // (line exists but value is an unsupported API call).
+                // Only set skip_write when a config line actually exists for this key.
+                None => Self::has_config_line(&content, lua_key),
+            };
+            if unparsed {
+                self.fields[i].skip_write = true;
+                self.fields[i].raw_value =
+                    Self::find_value_part(&content, lua_key).map(str::to_string);
             }
         }
+
+        if !duplicate_keys.is_empty() {
+            self.warnings.push(format!(
+                "Duplicate entries for {} left unchanged, press E to clean up",
+                duplicate_keys.join(", ")
+            ));
+        }
     }
 
     /// Returns true if a non-commented `config.<key>` assignment exists in content.
     fn has_config_line(content: &str, key: &str) -> bool {
+        Self::count_config_lines(content, key) > 0
+    }
+
+    /// Counts non-commented `config.<key>` assignments in content.
+    fn count_config_lines(content: &str, key: &str) -> usize {
         let pattern = format!("config.{}", key);
-        content.lines().any(|line| {
-            let trimmed = line.trim();
-            if trimmed.starts_with("--") {
-                return false;
-            }
-            if !trimmed.starts_with(&pattern) {
-                return false;
-            }
-            let after = &trimmed[pattern.len()..];
-            after.starts_with(|c: char| c.is_whitespace() || c == '=')
-        })
+        content
+            .lines()
+            .filter(|line| {
+                let trimmed = line.trim();
+                if trimmed.starts_with("--") {
+                    return false;
+                }
+                if !trimmed.starts_with(&pattern) {
+                    return false;
+                }
+                let after = &trimmed[pattern.len()..];
+                after.starts_with(|c: char| c.is_whitespace() || c == '=')
+            })
+            .count()
     }
 
     fn config_path(&self) -> PathBuf {
         self.config_path.clone()
     }
 
-    fn extract_lua_value(content: &str, key: &str) -> Option<String> {
+    /// Path of the file being edited for the header, with `~` for the home dir.
+    fn config_path_label(&self) -> String {
+        let path = abbreviate_home(&self.config_path);
+        if self.read_error.is_some() {
+            format!("{} (read-only)", path)
+        } else if self.dry_run {
+            format!("{} (dry run)", path)
+        } else if self.config_path.exists() {
+            path
+        } else {
+            format!("{} (will be created)", path)
+        }
+    }
+
+    /// Returns the raw right-hand side of the first `config.<key> = ...` line.
+    fn find_value_part<'a>(content: &'a str, key: &str) -> Option<&'a str> {
         let pattern = format!("config.{}", key);
         for line in content.lines() {
             let trimmed = line.trim();
@@ -496,55 +1229,116 @@ impl App {
                 continue;
             }
             let eq_pos = trimmed.find('=')?;
-            let value_part = trimmed[eq_pos + 1..].trim();
+            return Some(trimmed[eq_pos + 1..].trim());
+        }
+        None
+    }
 
-            // Handle different value types
-            if value_part.starts_with("wezterm.font(") {
-                // Extract font name from wezterm.font('Name') or wezterm.font("Name")
-                return Self::extract_quoted_arg(value_part, "wezterm.font(");
-            }
-            // Unknown wezterm API call (e.g. wezterm.font_with_fallback): skip to
-            // avoid corrupting the value on write-back via to_lua_value.
-            if value_part.starts_with("wezterm.") {
-                return None;
-            }
-            if value_part.starts_with('{') {
-                // Table value - return as-is up to end or comment
-                return Some(Self::strip_trailing_comment(value_part));
-            }
-            if value_part.starts_with('\'') || value_part.starts_with('"') {
-                // Quoted string
-                let quote = value_part.chars().next().unwrap();
-                if let Some(end) = value_part[1..].find(quote) {
-                    return Some(value_part[1..1 + end].to_string());
-                }
-            }
-            let value = Self::strip_trailing_comment(value_part);
-            if key == "color_scheme" && Self::is_kaku_auto_color_scheme_expr(&value) {
-                return Some("Auto".to_string());
+    fn extract_lua_value(content: &str, key: &str) -> Option<String> {
+        let value_part = Self::find_value_part(content, key)?;
+
+        // Handle different value types
+        if value_part.starts_with("wezterm.font(") {
+            // Extract font name from wezterm.font('Name') or wezterm.font("Name")
+            return Self::extract_quoted_arg(value_part, "wezterm.font(");
+        }
+        if value_part.starts_with("wezterm.font_with_fallback(") {
+            // Show the primary family; to_lua_value keeps the rest of the list.
+            return Self::split_font_with_fallback(value_part).map(|(primary, _)| primary);
+        }
+        // Unknown wezterm API call: skip to avoid corrupting the value on
+        // write-back via to_lua_value.
+        if value_part.starts_with("wezterm.") {
+            return None;
+        }
+        if value_part.starts_with('{') {
+            if Self::count_brace_depth(value_part) > 0 {
+                // Multi-line table: take every line up to the closing brace.
+                let block = Self::find_config_block(content, key)?;
+                let eq_pos = block.find('=')?;
+                return Some(block[eq_pos + 1..].trim().to_string());
             }
-            // Number, boolean, or identifier
-            if Self::is_scalar_literal(&value) {
+            // Table value - return as-is up to end or comment
+            return Some(Self::strip_trailing_comment(value_part));
+        }
+        if value_part.starts_with('\'') || value_part.starts_with('"') {
+            // Quoted string
+            if let Some(value) = Self::parse_lua_string(value_part) {
                 return Some(value);
             }
-            return None;
+        }
+        let value = Self::strip_trailing_comment(value_part);
+        if key == "color_scheme" && Self::is_kaku_auto_color_scheme_expr(&value) {
+            return Some("Auto".to_string());
+        }
+        // Number, boolean, or identifier
+        if Self::is_scalar_literal(&value) {
+            return Some(value);
         }
         None
     }
 
+    /// Splits a one-line `wezterm.font_with_fallback({ 'Primary', ... })` into
+    /// the primary family and the surrounding source. Returns None for other
+    /// shapes (table entries, multi-line lists) so the line stays untouched.
+    fn split_font_with_fallback(value_part: &str) -> Option<(String, FontFallbackTemplate)> {
+        let rest = value_part.strip_prefix("wezterm.font_with_fallback(")?;
+        let rest = rest.trim_start().strip_prefix('{')?.trim_start();
+        let prefix = &value_part[..value_part.len() - rest.len()];
+        let (primary, consumed) = Self::parse_lua_string_prefix(rest)?;
+        let suffix = Self::strip_trailing_comment(&rest[consumed..]);
+        if !suffix.ends_with(')') {
+            return None;
+        }
+        Some((
+            primary,
+            FontFallbackTemplate {
+                prefix: prefix.to_string(),
+                suffix,
+            },
+        ))
+    }
+
     fn is_kaku_auto_color_scheme_expr(raw: &str) -> bool {
         raw.trim() == KAKU_AUTO_COLOR_SCHEME_EXPR
     }
 
     fn extract_quoted_arg(s: &str, prefix: &str) -> Option<String> {
-        let rest = s.strip_prefix(prefix)?;
-        let quote = rest.chars().next()?;
+        Self::parse_lua_string(s.strip_prefix(prefix)?)
+    }
+
+    /// Reads the quoted Lua string at the start of `s`, resolving backslash
+    /// escapes. Returns None if `s` does not start with a closed string.
+    fn parse_lua_string(s: &str) -> Option<String> {
+        Self::parse_lua_string_prefix(s).map(|(value, _)| value)
+    }
+
+    /// Like `parse_lua_string`, also returning the byte length of the literal.
+    fn parse_lua_string_prefix(s: &str) -> Option<(String, usize)> {
+        let mut chars = s.char_indices();
+        let (_, quote) = chars.next()?;
         if quote != '\'' && quote != '"' {
             return None;
         }
-        let inner = &rest[1..];
-        let end = inner.find(quote)?;
-        Some(inner[..end].to_string())
+        let mut value = String::new();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    other => value.push(other),
+                },
+                c if c == quote => return Some((value, idx + c.len_utf8())),
+                c => value.push(c),
+            }
+        }
+        None
+    }
+
+    /// Renders `value` as a single-quoted Lua string literal.
+    fn lua_string(value: &str) -> String {
+        let escaped = value.replace('\\', "\\\\").replace('\'', "\\'");
+        format!("'{}'", escaped)
     }
 
     fn strip_trailing_comment(s: &str) -> String {
@@ -598,10 +1392,61 @@ impl App {
                 _ => {}
             }
         }
-        parts.push(key.to_ascii_uppercase());
+        parts.push(Self::hotkey_key_display(&key)?);
         Some(parts.join("+"))
     }
 
+    /// Maps a user-typed key name to the identifier WezTerm's key parser accepts.
+    /// Returns None for anything that would not resolve to a single key.
+    fn hotkey_key_to_lua(token: &str) -> Option<String> {
+        let upper = token.to_ascii_uppercase();
+        let named = match upper.as_str() {
+            "LEFT" | "LEFTARROW" => Some("LeftArrow"),
+            "RIGHT" | "RIGHTARROW" => Some("RightArrow"),
+            "UP" | "UPARROW" => Some("UpArrow"),
+            "DOWN" | "DOWNARROW" => Some("DownArrow"),
+            "SPACE" => Some("Space"),
+            "TAB" => Some("Tab"),
+            "ENTER" | "RETURN" => Some("Enter"),
+            "ESC" | "ESCAPE" => Some("Escape"),
+            _ => None,
+        };
+        if let Some(named) = named {
+            return Some(named.to_string());
+        }
+
+        if let Some(n) = upper.strip_prefix('F') {
+            if let Ok(n) = n.parse::<u8>() {
+                return (1..=24).contains(&n).then(|| format!("F{}", n));
+            }
+        }
+
+        let mut chars = upper.chars();
+        match (chars.next(), chars.next()) {
+            // Quotes and backslashes would break the single-quoted Lua string.
+            (Some(c), None) if !c.is_whitespace() && !matches!(c, '\'' | '"' | '\\') => {
+                Some(c.to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Inverse of `hotkey_key_to_lua`: renders a WezTerm key identifier for display.
+    fn hotkey_key_display(key: &str) -> Option<String> {
+        let display = match key {
+            "LeftArrow" => "Left",
+            "RightArrow" => "Right",
+            "UpArrow" => "Up",
+            "DownArrow" => "Down",
+            "Space" => "Space",
+            "Tab" => "Tab",
+            "Enter" | "Return" => "Enter",
+            "Escape" => "Esc",
+            _ => return Self::hotkey_key_to_lua(key),
+        };
+        Some(display.to_string())
+    }
+
     fn hotkey_to_lua(value: &str) -> Option<String> {
         let parts: Vec<&str> = value
             .split('+')
@@ -612,7 +1457,7 @@ impl App {
             return None;
         }
 
-        let key = parts.last()?.to_ascii_uppercase();
+        let key = Self::hotkey_key_to_lua(parts.last()?)?;
         let mut mods: Vec<&str> = Vec::new();
         for token in &parts[..parts.len() - 1] {
             match token.to_ascii_uppercase().as_str() {
@@ -623,7 +1468,11 @@ impl App {
                 _ => {}
             }
         }
-        if mods.is_empty() {
+        // Canonical order so "Cmd+Ctrl+K" and "Ctrl+Cmd+K" write the same table.
+        mods.sort_by_key(|m| HOTKEY_MODIFIER_ORDER.iter().position(|o| o == m));
+        mods.dedup();
+        // A bare or Shift-only key would fire while typing, so it is never valid.
+        if mods.is_empty() || mods == ["SHIFT"] {
             return None;
         }
 
@@ -634,6 +1483,23 @@ impl App {
         ))
     }
 
+    /// Rewrites a valid hotkey into its canonical display form.
+    fn canonical_hotkey(value: &str) -> Option<String> {
+        Self::normalize_hotkey_table(&Self::hotkey_to_lua(value)?)
+    }
+
+    /// Returns a warning when the hotkey shadows a well-known macOS shortcut.
+    /// Users may rebind these on purpose, so this never rejects the value.
+    fn hotkey_warning(value: &str) -> Option<String> {
+        let lua = Self::hotkey_to_lua(value)?;
+        RESERVED_HOTKEYS
+            .iter()
+            .find(|(combo, _)| Self::hotkey_to_lua(combo).as_deref() == Some(lua.as_str()))
+            .map(|(combo, purpose)| {
+                format!("{} {} on macOS and may not reach Kaku", combo, purpose)
+            })
+    }
+
     /// Converts a raw Lua value string into the TUI's internal display format.
     /// Returns None when the value exists but cannot be parsed into a supported
     /// format; the caller should set skip_write=true to protect the original line.
@@ -666,33 +1532,9 @@ impl App {
             | "tab_close_confirmation"
             | "pane_close_confirmation"
             | "bell_tab_indicator"
-            | "bell_dock_badge" => {
-                if raw == "true" {
-                    Some("On".into())
-                } else if raw == "false" {
-                    Some("Off".into())
-                } else {
-                    None
-                }
-            }
-            "hide_tab_bar_if_only_one_tab" => {
-                if raw == "true" {
-                    Some("Auto".into())
-                } else if raw == "false" {
-                    Some("Always".into())
-                } else {
-                    None
-                }
-            }
-            "tab_bar_at_bottom" => {
-                if raw == "true" {
-                    Some("Bottom".into())
-                } else if raw == "false" {
-                    Some("Top".into())
-                } else {
-                    None
-                }
-            }
+            | "bell_dock_badge" => Self::bool_option(raw, "On", "Off"),
+            "hide_tab_bar_if_only_one_tab" => Self::bool_option(raw, "Auto", "Always"),
+            "tab_bar_at_bottom" => Self::bool_option(raw, "Bottom", "Top"),
             "harfbuzz_features" => {
                 let stripped = raw.replace([' ', '\'', '"'], "");
                 if stripped == "{calt=0,clig=0,liga=0}" {
@@ -726,10 +1568,58 @@ impl App {
                     None
                 }
             }
+            "copy_on_select_destination" => COPY_DESTINATIONS
+                .iter()
+                .find(|(_, variant)| *variant == raw.trim())
+                .map(|(label, _)| label.to_string()),
+            "window_padding" => {
+                if Self::is_balanced_table(raw) {
+                    Some(raw.to_string())
+                } else {
+                    None
+                }
+            }
             _ => None,
         }
     }
 
+    /// Help shown after choosing an option, for choices whose effect is not
+    /// obvious from the label alone.
+    fn select_notice(&self, lua_key: &str) -> Option<String> {
+        if lua_key != "copy_on_select_destination" {
+            return None;
+        }
+        let copy_on_select = self
+            .fields
+            .iter()
+            .find(|f| f.lua_key == "copy_on_select")
+            .map(|f| self.display_value(f) == "On")
+            .unwrap_or(true);
+        if copy_on_select {
+            Some("macOS has a single pasteboard; Primary Selection matters over SSH/X11".into())
+        } else {
+            Some("Copy Destination applies only while Copy on Select is On".into())
+        }
+    }
+
+    /// Reads a boolean literal, ignoring case and surrounding whitespace.
+    /// Anything else is None so the caller protects the line with skip_write.
+    fn parse_lua_bool(raw: &str) -> Option<bool> {
+        let value = raw.trim();
+        if value.eq_ignore_ascii_case("true") {
+            Some(true)
+        } else if value.eq_ignore_ascii_case("false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Maps a boolean literal to the field's `on` or `off` option label.
+    fn bool_option(raw: &str, on: &str, off: &str) -> Option<String> {
+        Self::parse_lua_bool(raw).map(|value| if value { on } else { off }.to_string())
+    }
+
     fn is_number_literal(raw: &str) -> bool {
         let value = raw.trim();
         !value.is_empty() && (value.parse::<i64>().is_ok() || value.parse::<f64>().is_ok())
@@ -751,49 +1641,351 @@ impl App {
         }
     }
 
+    /// Value as shown in the settings list, with tables folded onto one line.
+    fn list_value(&self, field: &ConfigField) -> String {
+        if Self::is_table_field(field.lua_key) && !field.value.is_empty() {
+            field
+                .value
+                .lines()
+                .map(Self::strip_trailing_comment)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            self.display_value(field).to_string()
+        }
+    }
+
+    fn is_table_field(lua_key: &str) -> bool {
+        TABLE_FIELDS.iter().any(|(key, _)| *key == lua_key)
+    }
+
+    /// True when `raw` is a single Lua table: it opens with `{` and its braces
+    /// only close on the last line. Strings and `--` comments are skipped.
+    fn is_balanced_table(raw: &str) -> bool {
+        let raw = raw.trim();
+        if !raw.starts_with('{') {
+            return false;
+        }
+        let lines: Vec<&str> = raw.lines().collect();
+        let mut depth = 0;
+        for (idx, line) in lines.iter().enumerate() {
+            depth += Self::count_brace_depth(line);
+            let is_last = idx + 1 == lines.len();
+            if (is_last && depth != 0) || (!is_last && depth <= 0) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Rows currently shown in the list, in display order. Fields of collapsed
+    /// sections are omitted, but their section header is always present.
+    fn visible_rows(&self) -> Vec<ListRow> {
+        let mut rows = Vec::new();
+        let mut last_section: Option<&str> = None;
+        for (idx, field) in self.fields.iter().enumerate() {
+            if last_section != Some(field.section) {
+                rows.push(ListRow::Section(field.section));
+                last_section = Some(field.section);
+            }
+            if !self.is_section_collapsed(field.section) {
+                rows.push(ListRow::Field(idx));
+            }
+        }
+        rows
+    }
+
+    fn is_section_collapsed(&self, section: &str) -> bool {
+        self.collapsed_sections.contains(section)
+    }
+
+    fn cursor_row(&self) -> ListRow {
+        match self.selected_section {
+            Some(section) => ListRow::Section(section),
+            None => ListRow::Field(self.selected),
+        }
+    }
+
+    fn set_cursor_row(&mut self, row: ListRow) {
+        match row {
+            ListRow::Section(section) => self.selected_section = Some(section),
+            ListRow::Field(idx) => {
+                self.selected_section = None;
+                self.selected = idx;
+            }
+        }
+    }
+
     fn move_up(&mut self) {
-        if self.selected > 0 {
-            self.selected -= 1;
+        let rows = self.visible_rows();
+        if let Some(pos) = rows.iter().position(|row| *row == self.cursor_row()) {
+            if pos > 0 {
+                self.set_cursor_row(rows[pos - 1]);
+            }
         }
     }
 
     fn move_down(&mut self) {
-        if self.selected + 1 < self.item_count() {
-            self.selected += 1;
+        let rows = self.visible_rows();
+        if let Some(pos) = rows.iter().position(|row| *row == self.cursor_row()) {
+            if pos + 1 < rows.len() {
+                self.set_cursor_row(rows[pos + 1]);
+            }
+        }
+    }
+
+    fn page_up(&mut self) {
+        self.move_by(-self.page_size());
+    }
+
+    fn page_down(&mut self) {
+        self.move_by(self.page_size());
+    }
+
+    fn move_first(&mut self) {
+        self.move_by(isize::MIN);
+    }
+
+    fn move_last(&mut self) {
+        self.move_by(isize::MAX);
+    }
+
+    fn page_size(&self) -> isize {
+        self.list_height.saturating_sub(1).max(1) as isize
+    }
+
+    /// Moves the cursor `delta` visible rows, landing on a field rather than a
+    /// section header.
+    fn move_by(&mut self, delta: isize) {
+        let rows = self.visible_rows();
+        let from = rows
+            .iter()
+            .position(|row| *row == self.cursor_row())
+            .unwrap_or(0);
+        if let Some(pos) = clamp_to_field(&rows, from, delta) {
+            self.set_cursor_row(rows[pos]);
+        }
+    }
+
+    /// Collapse or expand a section. When the cursor sits on a field inside a
+    /// section being collapsed, it moves onto that section's header so the
+    /// selection never points at a hidden field.
+    fn toggle_section(&mut self, section: &'static str) {
+        if !self.collapsed_sections.remove(section) {
+            self.collapsed_sections.insert(section);
+            if self.selected_section.is_none() && self.fields[self.selected].section == section {
+                self.selected_section = Some(section);
+            }
+        }
+    }
+
+    /// Moves the cursor onto the field labelled `name` (case-insensitive,
+    /// the Lua key also matches), expanding its section. An unknown name
+    /// keeps the current cursor and shows a notice instead.
+    fn focus_field(&mut self, name: &str) -> bool {
+        let Some(idx) = self.find_field(name) else {
+            self.notice = Some(format!("Unknown setting: {}", name.trim()));
+            return false;
+        };
+        self.collapsed_sections.remove(self.fields[idx].section);
+        self.set_cursor_row(ListRow::Field(idx));
+        true
+    }
+
+    /// Index of the field labelled `name` or with that Lua key, ignoring case
+    /// and treating `_` and `-` as spaces, so `tab_bar_position` also works.
+    fn find_field(&self, name: &str) -> Option<usize> {
+        let normalize = |text: &str| text.trim().to_ascii_lowercase().replace(['_', '-'], " ");
+        let name = normalize(name);
+        self.fields
+            .iter()
+            .position(|field| normalize(field.key) == name || normalize(field.lua_key) == name)
+    }
+
+    /// Sets the field named `name` to `raw` after the editors' validation.
+    /// Returns whether the value changed, or why it was rejected. Config
+    /// lines the TUI could not parse are never replaced from here.
+    fn set_value(&mut self, name: &str, raw: &str) -> Result<bool, String> {
+        let idx = self.find_field(name).ok_or_else(|| {
+            format!(
+                "Unknown setting `{}`, expected one of: {}",
+                name.trim(),
+                config::SETTINGS_FIELD_LABELS.join(", ")
+            )
+        })?;
+        let field = &self.fields[idx];
+        if field.skip_write {
+            return Err(format!(
+                "{} has a custom line in the config that cannot be edited here, edit the file instead",
+                field.key
+            ));
+        }
+        let value = Self::validated_value(field, raw)?;
+        if field.lua_key == "macos_global_hotkey" && !value.is_empty() {
+            self.notice = Self::hotkey_warning(&value);
+        }
+        if value == self.display_value(field) {
+            return Ok(false);
+        }
+        self.fields[idx].value = value;
+        self.dirty = true;
+        Ok(true)
+    }
+
+    fn restore_view_state(&mut self) {
+        let Some(path) = self.view_state_path.as_ref() else {
+            return;
+        };
+        if let Ok(content) = std::fs::read_to_string(path) {
+            self.apply_view_state(&content);
+        }
+    }
+
+    /// Restores a cursor saved by `view_state`. The cursor is stored as the
+    /// field's Lua key so it survives fields being added or reordered; an
+    /// unknown key or unparsable state leaves it on the first field.
+    fn apply_view_state(&mut self, content: &str) {
+        let mut selected = None;
+        let mut collapsed = BTreeSet::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let Some((key, value)) = line.split_once('=') else {
+                return;
+            };
+            match key.trim() {
+                "selected" => selected = Some(value.trim().to_string()),
+                "collapsed" => {
+                    for name in value.split(',').map(str::trim) {
+                        if let Some(field) = self.fields.iter().find(|f| f.section == name) {
+                            collapsed.insert(field.section);
+                        }
+                    }
+                }
+                _ => {}
+            }
         }
+        let Some(idx) = selected.and_then(|key| self.find_field(&key)) else {
+            return;
+        };
+
+        self.selected = idx;
+        self.collapsed_sections = collapsed;
+        let section = self.fields[self.selected].section;
+        self.selected_section = self.is_section_collapsed(section).then_some(section);
+    }
+
+    fn view_state(&self) -> String {
+        let collapsed: Vec<&str> = self.collapsed_sections.iter().copied().collect();
+        let selected = self.fields.get(self.selected).map_or("", |f| f.lua_key);
+        format!("selected={}\ncollapsed={}\n", selected, collapsed.join(","))
     }
 
-    fn item_count(&self) -> usize {
-        self.fields.len()
+    fn persist_view_state(&self) -> anyhow::Result<()> {
+        let Some(path) = self.view_state_path.as_ref() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            config::create_user_owned_dirs(dir).context("create data directory")?;
+        }
+        std::fs::write(path, self.view_state()).with_context(|| format!("write {}", path.display()))
     }
 
     /// Save config if there are pending changes. Returns Err on save failure.
     /// Also signals kaku-gui immediately after a successful write so it reloads
     /// without waiting for the file-watcher grace period.
     fn save_if_dirty(&mut self) -> anyhow::Result<()> {
+        if self.read_error.is_some() || self.read_only {
+            return Ok(());
+        }
         if self.dirty {
+            let changed_keys: Vec<&'static str> = self
+                .fields
+                .iter()
+                .filter(|field| field.lua_key != "__assistant_enabled__" && field.is_modified())
+                .map(|field| field.lua_key)
+                .collect();
             self.save_config()?;
             self.dirty = false;
             self.has_saved = true;
             // Signal immediately while the pane's stdout is still being read by
             // kaku-gui. Sending after LeaveAlternateScreen is unreliable because
             // the terminal may have already closed the child's output stream.
-            signal_config_changed();
+            if !self.dry_run {
+                signal_config_changed(&changed_keys);
+            }
         }
         Ok(())
     }
 
+    /// Saves pending changes without leaving the TUI. Failures are shown as a
+    /// notice so the session and its edits stay open.
+    fn save_now(&mut self) {
+        if self.read_error.is_some() || self.read_only {
+            return;
+        }
+        if let Err(err) = self.save_if_dirty() {
+            self.notice = Some(format!("Save failed: {:#}", err));
+            return;
+        }
+        // The file now matches the fields, so nothing is modified any more.
+        for field in &mut self.fields {
+            field.original_value = field.value.clone();
+        }
+        self.notice = None;
+        self.saved_flash = true;
+    }
+
     fn finalize_active_input(&mut self) {
         match self.mode {
             Mode::Editing => self.confirm_edit(),
+            Mode::EditingTable => {
+                // Unbalanced input is dropped rather than written on exit.
+                if !self.confirm_table_edit() {
+                    self.cancel_edit();
+                }
+            }
             Mode::Selecting => self.confirm_select(),
-            Mode::Normal => {}
+            // Leaving without an answer keeps the custom config line.
+            Mode::ConfirmingOverwrite => self.cancel_overwrite(),
+            Mode::Normal | Mode::Previewing => {}
+        }
+    }
+
+    /// Copies the selected field's value with `copy`; section headers have
+    /// nothing to copy. Never touches the config.
+    fn copy_selected_value(&mut self, copy: impl FnOnce(&str) -> io::Result<()>) {
+        self.notice = None;
+        if self.selected_section.is_some() {
+            return;
+        }
+        let value = self.list_value(&self.fields[self.selected]);
+        match copy(&value) {
+            Ok(()) => self.copied_flash = true,
+            Err(err) => self.notice = Some(format!("Copy failed: {}", err)),
         }
     }
 
     fn start_edit(&mut self) {
+        self.notice = None;
+        if let Some(section) = self.selected_section {
+            self.toggle_section(section);
+            return;
+        }
+        if self.read_only {
+            self.notice = Some("Read-only session, settings cannot be changed".into());
+            return;
+        }
+
         let field = &self.fields[self.selected];
-        if field.has_options() {
+        if let Some((_, template)) = TABLE_FIELDS.iter().find(|(key, _)| *key == field.lua_key) {
+            self.mode = Mode::EditingTable;
+            self.edit_buffer = if field.value.is_empty() {
+                template.to_string()
+            } else {
+                field.value.clone()
+            };
+            self.edit_cursor = self.edit_buffer.chars().count();
+        } else if field.has_options() {
             if field.options.len() == 2 {
                 // Binary field: toggle directly without a popup.
                 let current = self.display_value(field);
@@ -804,6 +1996,9 @@ impl App {
                     .unwrap_or(0);
                 let next_idx = (current_idx + 1) % 2;
                 let next_value = field.options[next_idx].to_string();
+                if self.defer_overwrite(&next_value) {
+                    return;
+                }
                 self.fields[self.selected].value = next_value;
                 self.fields[self.selected].skip_write = false;
                 self.dirty = true;
@@ -848,23 +2043,33 @@ impl App {
     }
 
     fn confirm_edit(&mut self) {
-        let mut new_value = self.edit_buffer.clone();
-        let field = &self.fields[self.selected];
-
-        if Self::expects_numeric_input(field.lua_key)
-            && !new_value.is_empty()
-            && !Self::is_number_literal(&new_value)
-        {
-            new_value = self.edit_original.clone();
+        if self.read_only {
+            self.cancel_edit();
+            return;
         }
-
-        // Validate hotkey input: if invalid, revert to original value
-        // so UI display matches what will be saved to file.
-        if field.lua_key == "macos_global_hotkey"
-            && !new_value.is_empty()
-            && Self::hotkey_to_lua(&new_value).is_none()
-        {
-            new_value = self.edit_original.clone();
+        let field = &self.fields[self.selected];
+        // Invalid input reverts to the original value so the UI display
+        // matches what will be saved to file.
+        let new_value = match Self::validated_value(field, &self.edit_buffer) {
+            Ok(value) => {
+                if field.lua_key == "macos_global_hotkey" && !value.is_empty() {
+                    self.notice = Self::hotkey_warning(&value);
+                }
+                value
+            }
+            Err(err) if field.skip_write => {
+                // Reverting would still replace the custom line, so keep it.
+                self.notice = Some(err);
+                self.cancel_edit();
+                return;
+            }
+            Err(err) => {
+                self.notice = Some(err);
+                self.edit_original.clone()
+            }
+        };
+        if self.defer_overwrite(&new_value) {
+            return;
         }
 
         self.fields[self.selected].value = new_value;
@@ -876,21 +2081,179 @@ impl App {
         self.dirty = true;
     }
 
+    /// Checks `raw` the way the editors do for `field`, returning the value to
+    /// store or why it was rejected. Empty clears the field.
+    fn validated_value(field: &ConfigField, raw: &str) -> Result<String, String> {
+        if raw.is_empty() {
+            return Ok(String::new());
+        }
+        if Self::is_table_field(field.lua_key) {
+            let raw = raw.trim();
+            if !Self::is_balanced_table(raw) {
+                return Err(format!(
+                    "{} must be one table with balanced braces",
+                    field.key
+                ));
+            }
+            return Ok(raw.to_string());
+        }
+        if field.has_options() {
+            return field
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(raw.trim()))
+                .map(|option| option.to_string())
+                .ok_or_else(|| {
+                    format!("{} must be one of {}", field.key, field.options.join(", "))
+                });
+        }
+        validate(field.lua_key, raw)
+    }
+
+    /// Kaku-managed settings as `lua_key = value` pairs, using the values the
+    /// list shows. Fields whose config line could not be parsed are left out,
+    /// as is the assistant toggle, which lives in assistant.toml.
+    fn export_values(&self) -> BTreeMap<String, String> {
+        self.fields
+            .iter()
+            .filter(|field| !field.skip_write && field.lua_key != "__assistant_enabled__")
+            .map(|field| {
+                (
+                    field.lua_key.to_string(),
+                    self.display_value(field).to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// Applies exported values through the same validation as the editors.
+    /// Returns how many fields changed plus a warning for every entry that
+    /// was skipped.
+    fn import_values(&mut self, values: &toml::Table) -> (usize, Vec<String>) {
+        let mut applied = 0;
+        let mut warnings = Vec::new();
+        for (key, value) in values {
+            let Some(idx) = self.fields.iter().position(|f| f.lua_key == key) else {
+                warnings.push(format!("Ignoring unknown setting `{key}`"));
+                continue;
+            };
+            let field = &self.fields[idx];
+            if field.skip_write {
+                warnings.push(format!(
+                    "Leaving `{key}` unchanged: its line in the config is not editable here"
+                ));
+                continue;
+            }
+            let raw = match value {
+                toml::Value::String(raw) => raw.clone(),
+                toml::Value::Integer(n) => n.to_string(),
+                toml::Value::Float(n) => n.to_string(),
+                _ => {
+                    warnings.push(format!("Ignoring `{key}`: expected a string value"));
+                    continue;
+                }
+            };
+            let value = match Self::validated_value(field, &raw) {
+                Ok(value) => value,
+                Err(err) => {
+                    warnings.push(format!("Ignoring `{key}`: {err}"));
+                    continue;
+                }
+            };
+            if value != self.display_value(field) {
+                self.fields[idx].value = value;
+                self.dirty = true;
+                applied += 1;
+            }
+        }
+        (applied, warnings)
+    }
+
+    /// Applies the table editor buffer verbatim. Returns false and stays in
+    /// the editor when the braces do not form a single table; an empty buffer
+    /// clears the field so the bundled default applies again.
+    fn confirm_table_edit(&mut self) -> bool {
+        if self.read_only {
+            self.cancel_edit();
+            return false;
+        }
+        let raw = self.edit_buffer.trim();
+        if !raw.is_empty() && !Self::is_balanced_table(raw) {
+            self.notice = Some(format!(
+                "{} must be one table with balanced braces",
+                self.fields[self.selected].key
+            ));
+            return false;
+        }
+        let raw = raw.to_string();
+        if self.defer_overwrite(&raw) {
+            return true;
+        }
+
+        self.fields[self.selected].value = raw;
+        self.fields[self.selected].skip_write = false;
+        self.notice = None;
+        self.mode = Mode::Normal;
+        self.edit_buffer.clear();
+        self.dirty = true;
+        true
+    }
+
     fn confirm_select(&mut self) {
+        if self.read_only {
+            self.mode = Mode::Normal;
+            return;
+        }
         let selected_option = self.fields[self.selected].options[self.select_index];
         let current_value = self.display_value(&self.fields[self.selected]).to_string();
         if current_value == selected_option {
             self.mode = Mode::Normal;
             return;
         }
+        if self.defer_overwrite(selected_option) {
+            return;
+        }
 
         self.fields[self.selected].value = selected_option.to_string();
         // Same: explicit user choice overrides the skip_write protection.
         self.fields[self.selected].skip_write = false;
+        self.notice = self.select_notice(self.fields[self.selected].lua_key);
         self.mode = Mode::Normal;
         self.dirty = true;
     }
 
+    /// Holds `value` back when the selected field keeps a config line the TUI
+    /// could not parse, and asks before replacing it. Returns true if deferred.
+    fn defer_overwrite(&mut self, value: &str) -> bool {
+        if !self.fields[self.selected].skip_write {
+            return false;
+        }
+        self.pending_overwrite = Some(value.to_string());
+        self.mode = Mode::ConfirmingOverwrite;
+        self.edit_buffer.clear();
+        true
+    }
+
+    fn confirm_overwrite(&mut self) {
+        if let Some(value) = self.pending_overwrite.take() {
+            let field = &mut self.fields[self.selected];
+            field.value = value;
+            field.skip_write = false;
+            self.dirty = true;
+        }
+        self.mode = Mode::Normal;
+    }
+
+    fn cancel_overwrite(&mut self) {
+        self.pending_overwrite = None;
+        self.mode = Mode::Normal;
+    }
+
+    fn edit_clear(&mut self) {
+        self.edit_buffer.clear();
+        self.edit_cursor = 0;
+    }
+
     fn edit_backspace(&mut self) {
         if self.edit_cursor > 0 {
             // Convert char index to byte index
@@ -905,6 +2268,13 @@ impl App {
         }
     }
 
+    /// Removes the character under the cursor, leaving the cursor in place.
+    fn edit_delete(&mut self) {
+        if let Some((byte_idx, _)) = self.edit_buffer.char_indices().nth(self.edit_cursor) {
+            self.edit_buffer.remove(byte_idx);
+        }
+    }
+
     fn edit_cursor_left(&mut self) {
         if self.edit_cursor > 0 {
             self.edit_cursor -= 1;
@@ -917,53 +2287,94 @@ impl App {
         }
     }
 
-    fn edit_insert(&mut self, c: char) {
-        // Convert char index to byte index for insertion
-        let byte_idx = self
-            .edit_buffer
-            .char_indices()
-            .nth(self.edit_cursor)
-            .map(|(i, _)| i)
-            .unwrap_or(self.edit_buffer.len());
+    /// Moves the cursor to the start of the word before it.
+    fn edit_word_left(&mut self) {
+        let chars: Vec<char> = self.edit_buffer.chars().collect();
+        let mut cursor = self.edit_cursor.min(chars.len());
+        while cursor > 0 && is_word_separator(chars[cursor - 1]) {
+            cursor -= 1;
+        }
+        while cursor > 0 && !is_word_separator(chars[cursor - 1]) {
+            cursor -= 1;
+        }
+        self.edit_cursor = cursor;
+    }
+
+    /// Moves the cursor to the end of the word after it.
+    fn edit_word_right(&mut self) {
+        let chars: Vec<char> = self.edit_buffer.chars().collect();
+        let mut cursor = self.edit_cursor.min(chars.len());
+        while cursor < chars.len() && is_word_separator(chars[cursor]) {
+            cursor += 1;
+        }
+        while cursor < chars.len() && !is_word_separator(chars[cursor]) {
+            cursor += 1;
+        }
+        self.edit_cursor = cursor;
+    }
+
+    fn edit_cursor_up(&mut self) {
+        self.edit_cursor_vertical(-1);
+    }
+
+    fn edit_cursor_down(&mut self) {
+        self.edit_cursor_vertical(1);
+    }
+
+    /// Moves the cursor `delta` lines in a multi-line buffer, keeping the
+    /// column where the target line is long enough.
+    fn edit_cursor_vertical(&mut self, delta: isize) {
+        let lengths: Vec<usize> = self
+            .edit_buffer
+            .split('\n')
+            .map(|line| line.chars().count())
+            .collect();
+        let mut line = 0;
+        let mut line_start = 0;
+        while line + 1 < lengths.len() && line_start + lengths[line] < self.edit_cursor {
+            line_start += lengths[line] + 1;
+            line += 1;
+        }
+        let column = self.edit_cursor - line_start;
+        let target = match line.checked_add_signed(delta) {
+            Some(target) if target < lengths.len() => target,
+            _ => return,
+        };
+        let target_start: usize = lengths[..target].iter().map(|len| len + 1).sum();
+        self.edit_cursor = target_start + column.min(lengths[target]);
+    }
+
+    fn edit_insert(&mut self, c: char) {
+        // Convert char index to byte index for insertion
+        let byte_idx = self
+            .edit_buffer
+            .char_indices()
+            .nth(self.edit_cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.edit_buffer.len());
         self.edit_buffer.insert(byte_idx, c);
         self.edit_cursor += 1;
     }
 
-    fn save_config(&self) -> anyhow::Result<()> {
+    fn save_config(&mut self) -> anyhow::Result<()> {
+        if self.dry_run {
+            let original = std::fs::read_to_string(self.config_path()).unwrap_or_default();
+            self.dry_run_output = Some(self.render_config(with_config_scaffold(&original)));
+            return Ok(());
+        }
+
         // Ensure config file exists with proper structure first
         ensure_editable_config_exists(Some(&self.config_path))?;
 
         let config_path = self.config_path();
         let original_content = std::fs::read_to_string(&config_path).unwrap_or_default();
-        let mut content = original_content.clone();
+        let content = self.render_config(with_config_scaffold(&original_content));
         let assistant_enabled = self
             .fields
             .iter()
             .find(|field| field.lua_key == "__assistant_enabled__")
             .map(|field| self.display_value(field) == "On");
 
-        for field in &self.fields {
-            if field.lua_key == "__assistant_enabled__" {
-                continue;
-            }
-
-            // Never touch lines we couldn't fully parse — preserve user's original.
-            if field.skip_write {
-                continue;
-            }
-            let is_default = field.value.is_empty() || field.value == field.default;
-            // Keep tab bar position explicit so switching back to Bottom
-            // does not depend on removing a line and inheriting bundled defaults.
-            let always_write = field.lua_key == "tab_bar_at_bottom";
-            if is_default && !always_write {
-                // Remove the config line if it exists
-                content = self.remove_lua_config(&content, field.lua_key);
-            } else {
-                // Update or add the config line
-                content = self.update_lua_config(&content, field);
-            }
-        }
-
         // Atomic write: write to a temp file then rename so the file watcher
         // always sees a fully-written config (never a truncated intermediate).
         //
@@ -973,6 +2384,7 @@ impl App {
         // Preserve the original file's permissions on the replacement.
         let original_perms = std::fs::metadata(&real_path).ok().map(|m| m.permissions());
         let temp_path = real_path.with_extension("lua.tmp");
+        remove_stale_temp(&temp_path);
         {
             use std::io::Write;
             let mut file = std::fs::File::create(&temp_path)?;
@@ -983,7 +2395,8 @@ impl App {
                 let _ = file.set_permissions(perms);
             }
         }
-        std::fs::rename(&temp_path, &real_path)?;
+        replace_with_temp(&temp_path, &real_path)?;
+        verify_saved_lua(&real_path, &original_content)?;
 
         if let Some(enabled) = assistant_enabled {
             if let Err(err) = assistant_config::write_enabled(enabled) {
@@ -1002,6 +2415,126 @@ impl App {
         Ok(())
     }
 
+    /// Applies every writable field to `original` and returns the new Lua source.
+    /// Pure: does not touch the filesystem, so dry runs and previews share it.
+    fn render_config(&self, original: &str) -> String {
+        let mut content = original.to_string();
+        for field in &self.fields {
+            if field.lua_key == "__assistant_enabled__" {
+                continue;
+            }
+
+            // Never touch lines we couldn't fully parse — preserve user's original.
+            if field.skip_write {
+                continue;
+            }
+            let is_default = field.value.is_empty() || field.value == field.default;
+            // Keep tab bar position explicit so switching back to Bottom
+            // does not depend on removing a line and inheriting bundled defaults.
+            // A fallback font list is kept even when the primary is the default.
+            // A table field's default only names what the bundled config
+            // computes, so explicit defaults have no literal to write for it.
+            let always_write = (self.explicit_defaults && !Self::is_table_field(field.lua_key))
+                || field.lua_key == "tab_bar_at_bottom"
+                || (field.lua_key == "font" && self.font_fallback.is_some());
+            if is_default && !always_write {
+                // Remove the config line if it exists
+                content = self.remove_lua_config(&content, field.lua_key);
+            } else if field.value.is_empty() && self.explicit_defaults {
+                // Unset fields are spelled out with their default value.
+                let mut explicit = field.clone();
+                explicit.value = field.default.clone();
+                content = self.update_lua_config(&content, &explicit);
+            } else {
+                // Update or add the config line
+                content = self.update_lua_config(&content, field);
+            }
+        }
+        content
+    }
+
+    /// Lists the config lines that saving now would add, remove, or replace,
+    /// derived from the same rendering path `save_config` uses.
+    fn pending_changes(&self) -> Vec<PendingChange> {
+        let original = std::fs::read_to_string(self.config_path()).unwrap_or_default();
+        let rendered = self.render_config(&original);
+
+        let mut changes = Vec::new();
+        for field in &self.fields {
+            if field.lua_key == "__assistant_enabled__" {
+                if field.is_modified() {
+                    let enabled =
+                        |value: &str| format!("assistant.toml: enabled = {}", value == "On");
+                    let before: &str = if field.original_value.is_empty() {
+                        &field.default
+                    } else {
+                        &field.original_value
+                    };
+                    changes.push(PendingChange {
+                        key: field.key,
+                        before: Some(enabled(before)),
+                        after: Some(enabled(self.display_value(field))),
+                    });
+                }
+                continue;
+            }
+
+            let before = Self::find_config_block(&original, field.lua_key);
+            let after = Self::find_config_block(&rendered, field.lua_key);
+            if before != after {
+                changes.push(PendingChange {
+                    key: field.key,
+                    before,
+                    after,
+                });
+            }
+        }
+        changes
+    }
+
+    /// Returns the full `config.<key> = ...` assignment, including continuation
+    /// lines of a multi-line table, or None when the key is not set.
+    fn find_config_block(content: &str, lua_key: &str) -> Option<String> {
+        let pattern = format!("config.{}", lua_key);
+        let lines: Vec<&str> = content.lines().collect();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let trimmed = lines[i].trim();
+            if !trimmed.starts_with("--") && trimmed.starts_with(&pattern) {
+                let after_pattern = &trimmed[pattern.len()..];
+                if after_pattern.starts_with(|c: char| c.is_whitespace() || c == '=') {
+                    let start = i;
+                    if let Some(eq_pos) = trimmed.find('=') {
+                        let mut brace_depth = Self::count_brace_depth(trimmed[eq_pos + 1..].trim());
+                        while brace_depth > 0 && i + 1 < lines.len() {
+                            i += 1;
+                            brace_depth += Self::count_brace_depth(lines[i]);
+                        }
+                    }
+                    return Some(lines[start..=i].join("\n"));
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+
+    fn open_preview(&mut self) {
+        // Nothing is written on exit unless dirty, so a clean session has no changes.
+        self.preview = if self.dirty {
+            self.pending_changes()
+        } else {
+            Vec::new()
+        };
+        self.mode = Mode::Previewing;
+    }
+
+    fn close_preview(&mut self) {
+        self.preview.clear();
+        self.mode = Mode::Normal;
+    }
+
     fn remove_lua_config(&self, content: &str, lua_key: &str) -> String {
         let pattern = format!("config.{}", lua_key);
         let lines: Vec<&str> = content.lines().collect();
@@ -1054,7 +2587,14 @@ impl App {
     }
 
     fn count_brace_depth(s: &str) -> i32 {
+        Self::count_bracket_depths(s).0
+    }
+
+    /// Net `{}` and `()` nesting opened by `s`, ignoring quoted strings and
+    /// a trailing `--` comment.
+    fn count_bracket_depths(s: &str) -> (i32, i32) {
         let mut depth = 0i32;
+        let mut paren_depth = 0i32;
         let mut in_string = false;
         let mut quote_char = ' ';
         let chars: Vec<char> = s.chars().collect();
@@ -1079,10 +2619,14 @@ impl App {
                 depth += 1;
             } else if c == '}' {
                 depth -= 1;
+            } else if c == '(' {
+                paren_depth += 1;
+            } else if c == ')' {
+                paren_depth -= 1;
             }
             i += 1;
         }
-        depth
+        (depth, paren_depth)
     }
 
     fn update_lua_config(&self, content: &str, field: &ConfigField) -> String {
@@ -1134,8 +2678,10 @@ impl App {
         }
 
         if !found {
-            // Find "return config" and insert before it
-            if let Some(pos) = result.iter().position(|l| l.trim() == "return config") {
+            // Prefer a spot next to a related setting, then "return config"
+            if let Some(pos) = self.related_insert_position(&result, field) {
+                result.insert(pos, config_line);
+            } else if let Some(pos) = result.iter().position(|l| l.trim() == "return config") {
                 result.insert(pos, config_line);
             } else {
                 result.push(config_line);
@@ -1151,16 +2697,100 @@ impl App {
         }
     }
 
+    /// Where a `field` missing from the config should be inserted so it sits
+    /// with its section: right after the closest earlier field of the same
+    /// section the config sets, otherwise right before the closest later one.
+    /// Only unindented `config.x = ...` statements count as anchors, and an
+    /// anchor's multi-line table is skipped whole, so an insert never lands
+    /// inside a table or block. Anchors past `return config` are ignored.
+    fn related_insert_position(&self, lines: &[String], field: &ConfigField) -> Option<usize> {
+        let idx = self
+            .fields
+            .iter()
+            .position(|f| f.lua_key == field.lua_key)?;
+        let section = self.fields[idx].section;
+        let statements = Self::top_level_statements(lines);
+        let return_pos = lines
+            .iter()
+            .position(|l| l.trim() == "return config")
+            .unwrap_or(lines.len());
+        let span_of = |key: &str| {
+            statements
+                .iter()
+                .find(|(name, _, end)| *name == key && *end <= return_pos)
+                .map(|(_, start, end)| (*start, *end))
+        };
+
+        let earlier = self.fields[..idx].iter().rev();
+        for related in earlier.filter(|f| f.section == section) {
+            if let Some((_, end)) = span_of(related.lua_key) {
+                return Some(end);
+            }
+        }
+        let later = self.fields[idx + 1..].iter();
+        for related in later.filter(|f| f.section == section) {
+            if let Some((start, _)) = span_of(related.lua_key) {
+                return Some(start);
+            }
+        }
+        None
+    }
+
+    /// Unindented `config.<key> = ...` statements as `(key, first line,
+    /// line after the last)`, following multi-line tables and call arguments
+    /// to their close.
+    fn top_level_statements(lines: &[String]) -> Vec<(&str, usize, usize)> {
+        let mut statements = Vec::new();
+        let mut i = 0;
+        while i < lines.len() {
+            let start = i;
+            let line = lines[i].as_str();
+            i += 1;
+            let Some(rest) = line.strip_prefix("config.") else {
+                continue;
+            };
+            let key_len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let (key, after_key) = rest.split_at(key_len);
+            let Some(value) = after_key.trim_start().strip_prefix('=') else {
+                continue;
+            };
+            if key.is_empty() || value.starts_with('=') {
+                continue;
+            }
+            let open = |s: &str| {
+                let (braces, parens) = Self::count_bracket_depths(s);
+                braces + parens
+            };
+            let mut depth = open(value);
+            while depth > 0 && i < lines.len() {
+                depth += open(&lines[i]);
+                i += 1;
+            }
+            statements.push((key, start, i));
+        }
+        statements
+    }
+
     fn to_lua_value(&self, field: &ConfigField) -> String {
         match field.lua_key {
             "color_scheme" => {
                 if field.value == "Auto" {
                     KAKU_AUTO_COLOR_SCHEME_EXPR.into()
                 } else {
-                    format!("'{}'", field.value)
+                    Self::lua_string(&field.value)
                 }
             }
-            "font" => format!("wezterm.font('{}')", field.value),
+            "font" => match &self.font_fallback {
+                Some(template) => format!(
+                    "{}{}{}",
+                    template.prefix,
+                    Self::lua_string(self.display_value(field)),
+                    template.suffix
+                ),
+                None => format!("wezterm.font({})", Self::lua_string(&field.value)),
+            },
             "font_size"
             | "line_height"
             | "window_background_opacity"
@@ -1211,6 +2841,16 @@ impl App {
                     "'INTEGRATED_BUTTONS|RESIZE|MACOS_FORCE_DISABLE_SHADOW'".into()
                 }
             }
+            "copy_on_select_destination" => {
+                let variant = COPY_DESTINATIONS
+                    .iter()
+                    .find(|(label, _)| *label == field.value)
+                    .map(|(_, variant)| *variant)
+                    .unwrap_or("ClipboardAndPrimarySelection");
+                format!("'{}'", variant)
+            }
+            // Written back verbatim; confirm_table_edit() checked the braces.
+            "window_padding" => field.value.clone(),
             "macos_global_hotkey" => {
                 if field.value.is_empty() {
                     "nil".into()
@@ -1219,20 +2859,10 @@ impl App {
                     Self::hotkey_to_lua(&field.value).unwrap_or_else(|| "nil".into())
                 }
             }
-            _ => format!("'{}'", field.value),
+            _ => Self::lua_string(&field.value),
         }
     }
 
-    fn expects_numeric_input(lua_key: &str) -> bool {
-        matches!(
-            lua_key,
-            "font_size"
-                | "line_height"
-                | "window_background_opacity"
-                | "macos_window_background_blur"
-        )
-    }
-
     fn selecting_view(&self) -> Option<(&ConfigField, usize)> {
         if self.mode == Mode::Selecting {
             Some((&self.fields[self.selected], self.select_index))
@@ -1241,8 +2871,16 @@ impl App {
         }
     }
 
+    fn preview_view(&self) -> Option<&[PendingChange]> {
+        if self.mode == Mode::Previewing {
+            Some(self.preview.as_slice())
+        } else {
+            None
+        }
+    }
+
     fn editing_view(&self) -> Option<(&ConfigField, &str, usize)> {
-        if self.mode == Mode::Editing {
+        if matches!(self.mode, Mode::Editing | Mode::EditingTable) {
             Some((
                 &self.fields[self.selected],
                 &self.edit_buffer,
@@ -1254,40 +2892,209 @@ impl App {
     }
 }
 
-fn open_config_in_editor(config_path: &Path) -> anyhow::Result<()> {
-    open_path_in_editor(&config_path)
+/// Removes a temp file a crashed save left next to the config, so file
+/// watchers and users never see it. A fresh one may belong to a concurrent
+/// save and is left to be overwritten.
+fn remove_stale_temp(temp_path: &Path) {
+    let Ok(modified) = std::fs::metadata(temp_path).and_then(|m| m.modified()) else {
+        return;
+    };
+    let age = modified.elapsed().unwrap_or_default();
+    if age >= STALE_TEMP_AGE {
+        if let Err(err) = std::fs::remove_file(temp_path) {
+            log::warn!("remove stale {}: {}", temp_path.display(), err);
+        }
+    }
+}
+
+/// Moves `temp_path` over `target`. The temp file lives next to the config,
+/// but a bind-mounted config can still sit on another filesystem; then the
+/// contents are copied and synced before the temp file is removed.
+fn replace_with_temp(temp_path: &Path, target: &Path) -> io::Result<()> {
+    match std::fs::rename(temp_path, target) {
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices => {
+            std::fs::copy(temp_path, target)?;
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(target)?
+                .sync_all()?;
+            std::fs::remove_file(temp_path)
+        }
+        result => result,
+    }
 }
 
-/// Send an OSC 1337 SetUserVar to signal kaku-gui that config has changed.
-/// This triggers an immediate config reload instead of waiting for the file watcher.
-fn signal_config_changed() {
-    use std::io::Write;
-    // OSC 1337 ; SetUserVar=name=base64(value) ST
-    // name: KAKU_CONFIG_CHANGED, value: "1" -> base64 "MQ=="
-    let seq = if std::env::var("TMUX").is_ok() {
-        // tmux passthrough: wrap OSC in DCS tmux; ... ST
-        b"\x1bPtmux;\x1b\x1b]1337;SetUserVar=KAKU_CONFIG_CHANGED=MQ==\x07\x1b\\" as &[u8]
+/// Index in `rows` reached by moving `delta` rows from `from`, clamped to the
+/// list and nudged onto a field row in the direction of travel. Returns None
+/// when no field is visible, e.g. every section is collapsed.
+fn clamp_to_field(rows: &[ListRow], from: usize, delta: isize) -> Option<usize> {
+    let last = rows.len().checked_sub(1)?;
+    let target = from.saturating_add_signed(delta).min(last);
+    let is_field = |idx: &usize| matches!(rows[*idx], ListRow::Field(_));
+    let forward = (target..=last).find(is_field);
+    let backward = (0..=target).rev().find(is_field);
+    if delta < 0 {
+        backward.or(forward)
     } else {
-        b"\x1b]1337;SetUserVar=KAKU_CONFIG_CHANGED=MQ==\x07" as &[u8]
-    };
-    let _ = std::io::stdout().write_all(seq);
-    let _ = std::io::stdout().flush();
+        forward.or(backward)
+    }
+}
+
+/// Opens the config in the user's editor. Terminal editors block until they
+/// exit, so a changed mtime afterwards means the user saved and kaku-gui can
+/// reload right away instead of waiting for the file watcher.
+fn open_config_in_editor(config_path: &Path, read_only: bool) -> anyhow::Result<()> {
+    if read_only {
+        return open_path_in_editor_read_only(config_path);
+    }
+    let before = file_modified_time(config_path);
+    open_path_in_editor(config_path)?;
+    if file_modified_time(config_path) != before {
+        signal_config_changed(&[]);
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        ensure_editable_config_exists, normal_mode_action, App, Mode, NormalModeAction,
-        KAKU_AUTO_COLOR_SCHEME_EXPR,
+        check_config_writable, clamp_to_field, ensure_editable_config_exists, export_settings,
+        has_config_scaffold, is_bundled_config, normal_mode_action, remove_stale_temp,
+        unsupported_terminal_reason, validate, validate_hotkey, validate_number, App, ListRow,
+        Mode, NormalModeAction, PendingChange, TerminalGuard, COPY_DESTINATIONS,
+        KAKU_AUTO_COLOR_SCHEME_EXPR, STALE_TEMP_AGE, VIEW_STATE_FILE,
     };
     use crossterm::event::KeyCode;
-    use std::path::PathBuf;
+    use std::io;
+    use std::path::{Path, PathBuf};
     use tempfile::tempdir;
 
     fn test_app() -> App {
         App::new(PathBuf::from("/tmp/kaku-config-tui-test.lua"))
     }
 
+    #[test]
+    fn validate_number_accepts_only_number_literals() {
+        assert_eq!(validate_number(" 14.5 "), Ok("14.5".to_string()));
+        assert_eq!(validate_number("16"), Ok("16".to_string()));
+        assert!(validate_number("big").is_err());
+        assert!(validate_number("1.2.3").is_err());
+    }
+
+    #[test]
+    fn validate_hotkey_canonicalizes_or_explains() {
+        assert_eq!(
+            validate_hotkey("cmd+opt+ctrl+k"),
+            Ok("Ctrl+Alt+Cmd+K".to_string())
+        );
+        assert_eq!(validate_hotkey("ctrl+alt+k"), Ok("Ctrl+Alt+K".to_string()));
+        assert_eq!(
+            validate_hotkey("shift+cmd+f5"),
+            Ok("Cmd+Shift+F5".to_string())
+        );
+        assert!(validate_hotkey("not a hotkey")
+            .unwrap_err()
+            .contains("not a hotkey"));
+    }
+
+    #[test]
+    fn validate_dispatches_by_lua_key() {
+        assert!(validate("font_size", "big").is_err());
+        assert!(validate("window_background_opacity", "0.9").is_ok());
+        assert_eq!(validate("font_size", ""), Ok(String::new()));
+        assert_eq!(
+            validate("font", "JetBrains Mono"),
+            Ok("JetBrains Mono".to_string())
+        );
+    }
+
+    #[test]
+    fn confirm_edit_reverts_invalid_input_with_notice() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+        app.selected = idx;
+        app.start_edit();
+        app.edit_buffer = "huge".into();
+
+        app.confirm_edit();
+
+        assert_eq!(app.fields[idx].value, "");
+        assert!(app.notice.as_deref().unwrap_or("").contains("not a number"));
+    }
+
+    #[test]
+    fn replacing_unparsed_line_asks_first() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "copy_on_select")
+            .expect("copy_on_select field to exist");
+        app.selected = idx;
+        app.fields[idx].skip_write = true;
+
+        app.start_edit();
+        assert!(matches!(app.mode, Mode::ConfirmingOverwrite));
+        assert!(app.fields[idx].skip_write);
+        assert!(!app.dirty);
+
+        app.cancel_overwrite();
+        assert!(matches!(app.mode, Mode::Normal));
+        assert!(app.fields[idx].skip_write);
+        assert_eq!(app.fields[idx].value, "");
+
+        app.start_edit();
+        app.confirm_overwrite();
+        assert!(!app.fields[idx].skip_write);
+        assert_eq!(app.fields[idx].value, "Off");
+        assert!(app.dirty);
+    }
+
+    #[test]
+    fn invalid_edit_keeps_unparsed_line() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+        app.selected = idx;
+        app.fields[idx].skip_write = true;
+        app.mode = Mode::Editing;
+        app.edit_buffer = "huge".into();
+
+        app.confirm_edit();
+
+        assert!(matches!(app.mode, Mode::Normal));
+        assert!(app.fields[idx].skip_write);
+        assert!(!app.dirty);
+    }
+
+    #[test]
+    fn unparsed_config_line_keeps_its_raw_value() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &config_path,
+            "local config = {}\nconfig.font_size = base_size + 2\nconfig.line_height = 1.2\nreturn config\n",
+        )
+        .expect("write config");
+        let mut app = App::new(config_path);
+        app.load_config();
+
+        let field = |key: &str| app.fields.iter().find(|f| f.lua_key == key).unwrap();
+        assert!(field("font_size").skip_write);
+        assert_eq!(
+            field("font_size").raw_value.as_deref(),
+            Some("base_size + 2")
+        );
+        assert_eq!(field("line_height").raw_value, None);
+    }
+
     #[test]
     fn tab_bar_at_bottom_uses_default_when_value_is_empty() {
         let app = test_app();
@@ -1378,20 +3185,290 @@ mod tests {
     }
 
     #[test]
-    fn normalize_scrollbar_bool_values() {
+    fn string_values_with_quotes_round_trip_through_lua() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font")
+            .expect("font field to exist");
+        app.fields[idx].value = "O'Brien Mono".into();
+
+        let lua = app.to_lua_value(&app.fields[idx]);
+        assert_eq!(lua, r"wezterm.font('O\'Brien Mono')");
+
+        let content = format!("config.font = {} -- primary font\n", lua);
         assert_eq!(
-            App::normalize_value("enable_scroll_bar", "true"),
-            Some("On".into())
+            App::extract_lua_value(&content, "font"),
+            Some("O'Brien Mono".to_string())
         );
+
+        assert_eq!(App::lua_string(r"C:\fonts"), r"'C:\\fonts'");
         assert_eq!(
-            App::normalize_value("enable_scroll_bar", "false"),
-            Some("Off".into())
+            App::parse_lua_string(r"'C:\\fonts'"),
+            Some(r"C:\fonts".to_string())
         );
+        assert_eq!(App::parse_lua_string("'unterminated"), None);
     }
 
     #[test]
-    fn close_confirmation_fields_default_to_off() {
-        let app = test_app();
+    fn font_with_fallback_shows_primary_and_keeps_fallbacks() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        let line = "config.font = wezterm.font_with_fallback({ 'Fira Code', 'Symbols Nerd Font' })";
+        std::fs::write(&config_path, format!("{line} -- fonts\n")).expect("write config");
+
+        let mut app = App::new(config_path);
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font")
+            .expect("font field to exist");
+        assert_eq!(app.fields[idx].value, "Fira Code");
+        assert!(!app.fields[idx].skip_write);
+
+        let original = format!("{line}\n");
+        assert!(app.render_config(&original).contains(&original));
+
+        // Switching back to the default primary must not drop the fallbacks.
+        app.fields[idx].value = "JetBrains Mono".into();
+        assert!(app.render_config(&original).contains(
+            "config.font = wezterm.font_with_fallback({ 'JetBrains Mono', 'Symbols Nerd Font' })\n"
+        ));
+    }
+
+    #[test]
+    fn unsupported_font_with_fallback_forms_are_protected() {
+        for content in [
+            "config.font = wezterm.font_with_fallback({ { family = 'Fira Code' }, 'Noto' })\n",
+            "config.font = wezterm.font_with_fallback({\n  'Fira Code',\n})\n",
+        ] {
+            assert_eq!(App::extract_lua_value(content, "font"), None);
+            assert!(App::has_config_line(content, "font"));
+        }
+    }
+
+    #[test]
+    fn copy_destination_round_trips_through_lua() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "copy_on_select_destination")
+            .expect("copy_on_select_destination field to exist");
+
+        for (label, variant) in COPY_DESTINATIONS {
+            app.fields[idx].value = label.to_string();
+            let lua = app.to_lua_value(&app.fields[idx]);
+            assert_eq!(lua, format!("'{}'", variant));
+
+            let content = format!("config.copy_on_select_destination = {}\n", lua);
+            let raw = App::extract_lua_value(&content, "copy_on_select_destination")
+                .expect("value to be extracted");
+            assert_eq!(
+                App::normalize_value("copy_on_select_destination", &raw),
+                Some(label.to_string())
+            );
+        }
+        assert_eq!(
+            App::normalize_value("copy_on_select_destination", "Pasteboard"),
+            None
+        );
+    }
+
+    #[test]
+    fn multi_line_table_is_edited_and_written_back_verbatim() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &path,
+            "config.window_padding = {\n  left = '24px',\n  right = '24px',\n}\nconfig.font_size = 15\n",
+        )
+        .expect("write config");
+        let mut app = App::new(path);
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "window_padding")
+            .expect("window_padding field to exist");
+        assert_eq!(
+            app.fields[idx].value,
+            "{\n  left = '24px',\n  right = '24px',\n}"
+        );
+        assert!(!app.fields[idx].skip_write);
+        assert_eq!(
+            app.list_value(&app.fields[idx]),
+            "{ left = '24px', right = '24px', }"
+        );
+
+        app.selected = idx;
+        app.start_edit();
+        assert!(app.mode == Mode::EditingTable);
+        app.edit_cursor_left();
+        for c in "  top = '8px',\n".chars() {
+            app.edit_insert(c);
+        }
+        assert!(app.confirm_table_edit());
+
+        let rendered = app.render_config(&std::fs::read_to_string(app.config_path()).unwrap());
+        assert!(rendered.starts_with(
+            "config.window_padding = {\n  left = '24px',\n  right = '24px',\n  top = '8px',\n}\nconfig.font_size = 15\n"
+        ));
+    }
+
+    #[test]
+    fn table_edit_with_unbalanced_braces_is_rejected() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "window_padding")
+            .expect("window_padding field to exist");
+        app.selected = idx;
+        app.start_edit();
+        assert!(app.edit_buffer.starts_with('{'));
+
+        app.edit_buffer = "{ left = 10,\n".into();
+        assert!(!app.confirm_table_edit());
+        assert!(app.mode == Mode::EditingTable);
+        assert!(app.notice.is_some());
+        assert!(!app.dirty);
+
+        app.finalize_active_input();
+        assert!(app.mode == Mode::Normal);
+        assert!(app.fields[idx].value.is_empty());
+
+        assert!(!App::is_balanced_table("{ left = 1 }\n{ top = 2 }"));
+        assert!(!App::is_balanced_table("left = 1"));
+        assert!(App::is_balanced_table("{\n  left = '{', -- }\n}"));
+    }
+
+    #[test]
+    fn clearing_table_edit_removes_the_config_line() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "window_padding")
+            .expect("window_padding field to exist");
+        app.fields[idx].value = "{ left = 0 }".into();
+        app.selected = idx;
+        app.start_edit();
+        app.edit_buffer.clear();
+        assert!(app.confirm_table_edit());
+        assert!(app.fields[idx].value.is_empty());
+
+        let rendered = app.render_config("config.window_padding = {\n  left = 0,\n}\n");
+        assert!(!rendered.contains("window_padding"));
+    }
+
+    #[test]
+    fn table_editor_cursor_moves_between_lines() {
+        let mut app = test_app();
+        app.edit_buffer = "{\n  left = 1,\n}".into();
+        app.edit_cursor = app.edit_buffer.chars().count();
+
+        app.edit_cursor_up();
+        assert_eq!(app.edit_cursor, 3);
+        app.edit_cursor_right();
+        app.edit_cursor_right();
+        app.edit_cursor_up();
+        assert_eq!(app.edit_cursor, 1);
+        app.edit_cursor_down();
+        app.edit_cursor_down();
+        assert_eq!(app.edit_cursor, app.edit_buffer.chars().count());
+        app.edit_cursor_down();
+        assert_eq!(app.edit_cursor, app.edit_buffer.chars().count());
+    }
+
+    #[test]
+    fn save_now_writes_changes_and_stays_open() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &path,
+            "local config = {}\nconfig.font_size = 15\nreturn config\n",
+        )
+        .expect("write config");
+        let mut app = App::new(path.clone());
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+        app.fields[idx].value = "18".into();
+        app.dirty = true;
+        app.dry_run = true;
+
+        app.save_now();
+
+        assert!(!app.dirty);
+        assert!(app.saved_flash);
+        assert!(app.mode == Mode::Normal);
+        assert!(!app.fields[idx].is_modified());
+        let output = app.dry_run_output.as_deref().expect("rendered config");
+        assert!(output.contains("config.font_size = 18"));
+    }
+
+    #[test]
+    fn normalize_scrollbar_bool_values() {
+        assert_eq!(
+            App::normalize_value("enable_scroll_bar", "true"),
+            Some("On".into())
+        );
+        assert_eq!(
+            App::normalize_value("enable_scroll_bar", "false"),
+            Some("Off".into())
+        );
+    }
+
+    #[test]
+    fn bool_values_ignore_case_and_protect_garbage() {
+        assert_eq!(
+            App::normalize_value("copy_on_select", "TRUE"),
+            Some("On".into())
+        );
+        assert_eq!(
+            App::normalize_value("hide_tab_bar_if_only_one_tab", "false"),
+            Some("Always".into())
+        );
+        assert_eq!(
+            App::normalize_value("tab_bar_at_bottom", " True "),
+            Some("Bottom".into())
+        );
+        assert_eq!(App::normalize_value("copy_on_select", "yes"), None);
+
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &path,
+            "config.copy_on_select = TRUE\nconfig.hide_tab_bar_if_only_one_tab = maybe\n",
+        )
+        .expect("write config");
+        let mut app = App::new(path);
+        app.load_config();
+        let copy = app
+            .fields
+            .iter()
+            .find(|f| f.lua_key == "copy_on_select")
+            .expect("copy_on_select field to exist");
+        assert_eq!(copy.value, "On");
+        assert!(!copy.skip_write);
+        let tab_bar = app
+            .fields
+            .iter()
+            .find(|f| f.lua_key == "hide_tab_bar_if_only_one_tab")
+            .expect("hide_tab_bar_if_only_one_tab field to exist");
+        assert!(tab_bar.value.is_empty());
+        assert!(tab_bar.skip_write);
+    }
+
+    #[test]
+    fn close_confirmation_fields_default_to_off() {
+        let app = test_app();
         let tab_field = app
             .fields
             .iter()
@@ -1429,6 +3506,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn edit_clear_empties_buffer_and_resets_cursor() {
+        let mut app = test_app();
+        app.edit_buffer = "JetBrains Mono".into();
+        app.edit_cursor = 5;
+
+        app.edit_clear();
+
+        assert_eq!(app.edit_buffer, "");
+        assert_eq!(app.edit_cursor, 0);
+    }
+
+    #[test]
+    fn edit_delete_removes_one_char_under_cursor() {
+        let mut app = test_app();
+        app.edit_buffer = "霞鹜文楷 Mono".into();
+        app.edit_cursor = 1;
+
+        app.edit_delete();
+        assert_eq!(app.edit_buffer, "霞文楷 Mono");
+        assert_eq!(app.edit_cursor, 1);
+
+        app.edit_cursor = app.edit_buffer.chars().count();
+        app.edit_delete();
+        assert_eq!(app.edit_buffer, "霞文楷 Mono");
+        assert_eq!(app.edit_cursor, 8);
+    }
+
     #[test]
     fn finalize_active_input_commits_edit_buffer() {
         let mut app = test_app();
@@ -1447,6 +3552,33 @@ mod tests {
         assert!(app.dirty);
     }
 
+    #[test]
+    fn copy_selected_value_flashes_or_reports_failure() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+        app.selected = idx;
+        app.fields[idx].value = "17".to_string();
+
+        let mut copied = String::new();
+        app.copy_selected_value(|text| {
+            copied = text.to_string();
+            Ok(())
+        });
+        assert_eq!(copied, "17");
+        assert!(app.copied_flash);
+        assert!(app.notice.is_none());
+        assert!(!app.dirty);
+
+        app.copied_flash = false;
+        app.copy_selected_value(|_| Err(io::Error::other("stdout closed")));
+        assert!(!app.copied_flash);
+        assert_eq!(app.notice.as_deref(), Some("Copy failed: stdout closed"));
+    }
+
     #[test]
     fn start_edit_toggles_binary_option_fields() {
         let mut app = test_app();
@@ -1464,6 +3596,36 @@ mod tests {
         assert!(app.dirty);
     }
 
+    #[test]
+    fn read_only_session_refuses_edits_and_saves() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        let original = "local config = {}\nconfig.copy_on_select = true\nreturn config\n";
+        std::fs::write(&config_path, original).expect("write config");
+        let mut app = App::new(config_path.clone());
+        app.read_only = true;
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "copy_on_select")
+            .expect("copy_on_select field to exist");
+        app.selected = idx;
+
+        app.start_edit();
+        assert_eq!(app.fields[idx].value, "On");
+        assert!(matches!(app.mode, Mode::Normal));
+        assert!(app.notice.is_some());
+
+        app.fields[idx].value = "Off".into();
+        app.dirty = true;
+        app.save_if_dirty().expect("save is a no-op");
+        assert_eq!(
+            std::fs::read_to_string(&config_path).expect("read config"),
+            original
+        );
+    }
+
     #[test]
     fn start_edit_opens_selector_for_multi_option_fields() {
         let mut app = test_app();
@@ -1616,6 +3778,162 @@ mod tests {
         assert_eq!(app.fields[idx].value, "0.9");
     }
 
+    #[test]
+    fn unreadable_config_disables_saving() {
+        let dir = tempdir().expect("tempdir");
+        // A directory exists at the path but cannot be read as a file.
+        let path = dir.path().join("kaku.lua");
+        std::fs::create_dir(&path).expect("create dir");
+        let mut app = App::new(path.clone());
+        app.load_config();
+
+        let error = app.read_error.clone().expect("read error to be recorded");
+        assert!(error.contains("saving is disabled"));
+        assert!(app.config_path_label().ends_with("(read-only)"));
+
+        app.fields[0].value = "Kaku Light".into();
+        app.dirty = true;
+        app.save_if_dirty().expect("save is skipped, not failed");
+        app.save_now();
+        assert!(path.is_dir());
+        assert!(!app.has_saved);
+        assert!(!app.saved_flash);
+    }
+
+    #[test]
+    fn config_without_scaffold_disables_saving() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        let original = "local wezterm = require 'wezterm'\nreturn { font_size = 14 }\n";
+        std::fs::write(&path, original).expect("write config");
+
+        let mut app = App::new(path.clone());
+        app.load_config();
+
+        let error = app
+            .read_error
+            .clone()
+            .expect("missing scaffold to be recorded");
+        assert!(error.contains("saving is disabled"));
+
+        app.fields[0].value = "Kaku Light".into();
+        app.dirty = true;
+        app.save_if_dirty().expect("save is skipped, not failed");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+    }
+
+    #[test]
+    fn blank_config_is_saved_with_scaffold() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        std::fs::write(&path, "\n").expect("write config");
+
+        let mut app = App::new(path.clone());
+        app.load_config();
+        assert!(app.read_error.is_none());
+
+        app.fields[0].value = "Kaku Light".into();
+        app.save_config().expect("save_config");
+
+        let written = std::fs::read_to_string(&path).expect("read back");
+        assert!(has_config_scaffold(&written));
+        let config_at = written.find("local config =").unwrap();
+        let scheme_at = written.find("config.color_scheme = ").unwrap();
+        let return_at = written.rfind("return config").unwrap();
+        assert!(config_at < scheme_at && scheme_at < return_at);
+    }
+
+    #[test]
+    fn config_scaffold_detection() {
+        assert!(has_config_scaffold(
+            "local config = wezterm.config_builder()"
+        ));
+        assert!(has_config_scaffold("local config={}"));
+        assert!(has_config_scaffold("config = {}"));
+        assert!(!has_config_scaffold("-- local config = {}\nreturn {}"));
+        assert!(!has_config_scaffold("config.font_size = 14"));
+        assert!(!has_config_scaffold("if config == nil then end"));
+    }
+
+    #[test]
+    fn exported_settings_import_into_another_config() {
+        let dir = tempdir().expect("tempdir");
+        let source = dir.path().join("source.lua");
+        std::fs::write(
+            &source,
+            "local config = {}\nconfig.font_size = 18\nconfig.copy_on_select = false\nreturn config\n",
+        )
+        .expect("write source");
+        let export = dir.path().join("settings.toml");
+        export_settings(source, &export).expect("export");
+
+        let exported = std::fs::read_to_string(&export).expect("read export");
+        assert!(exported.contains("font_size = \"18\""));
+        assert!(exported.contains("copy_on_select = \"Off\""));
+
+        assert!(!exported.contains("__assistant_enabled__"));
+
+        let mut app = test_app();
+        let values: toml::Table = exported.parse().expect("parse export");
+        let (applied, warnings) = app.import_values(&values);
+
+        assert!(applied >= 2);
+        let font_size = app
+            .fields
+            .iter()
+            .find(|f| f.lua_key == "font_size")
+            .unwrap();
+        assert_eq!(font_size.value, "18");
+        assert!(app.dirty);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn import_validates_values_and_respects_protected_fields() {
+        let mut app = test_app();
+        let padding = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "window_padding")
+            .unwrap();
+        app.fields[padding].skip_write = true;
+        let values: toml::Table = toml::toml! {
+            font_size = "big"
+            macos_global_hotkey = "not a hotkey"
+            copy_on_select = "off"
+            line_height = 1.4
+            window_padding = "{ left = 0 }"
+            made_up_key = "1"
+        };
+
+        let (applied, warnings) = app.import_values(&values);
+
+        let value = |key: &str| {
+            app.fields
+                .iter()
+                .find(|f| f.lua_key == key)
+                .unwrap()
+                .value
+                .clone()
+        };
+        assert_eq!(applied, 2);
+        assert_eq!(value("copy_on_select"), "Off");
+        assert_eq!(value("line_height"), "1.4");
+        assert_eq!(value("font_size"), "");
+        assert_eq!(value("macos_global_hotkey"), "");
+        assert_eq!(value("window_padding"), "");
+        assert_eq!(warnings.len(), 4, "{warnings:?}");
+        assert!(warnings.iter().any(|w| w.contains("made_up_key")));
+    }
+
+    #[test]
+    fn missing_config_still_loads_defaults() {
+        let dir = tempdir().expect("tempdir");
+        let mut app = App::new(dir.path().join("kaku.lua"));
+        app.load_config();
+        assert!(app.read_error.is_none());
+    }
+
     #[test]
     fn save_config_produces_trailing_newline() {
         let dir = tempdir().expect("tempdir");
@@ -1648,4 +3966,856 @@ mod tests {
             &written[written.len().saturating_sub(10)..]
         );
     }
+
+    #[cfg(feature = "lua-check")]
+    #[test]
+    fn invalid_lua_save_restores_previous_config() {
+        use super::verify_saved_lua;
+
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        let original = "local config = {}\nconfig.font_family = 'Menlo'\nreturn config\n";
+        let broken = "local config = {}\nconfig.font_family = 'Men'lo'\nreturn config\n";
+
+        std::fs::write(&config_path, broken).expect("write config");
+        let err = verify_saved_lua(&config_path, original).expect_err("broken save");
+        assert!(err.to_string().contains("previous version restored"));
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+
+        // A config that was already broken is not "restored" to itself.
+        std::fs::write(&config_path, broken).expect("write config");
+        verify_saved_lua(&config_path, broken).expect("already broken");
+
+        std::fs::write(&config_path, original).expect("write config");
+        verify_saved_lua(&config_path, "").expect("valid save");
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+    }
+
+    #[test]
+    fn save_config_cleans_up_stale_temp_file() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(&config_path, "local config = {}\nreturn config\n").expect("write config");
+        let temp_path = dir.path().join("kaku.lua.tmp");
+        std::fs::write(&temp_path, "-- partial").expect("write temp");
+
+        // A fresh temp file may belong to a save still in progress.
+        remove_stale_temp(&temp_path);
+        assert!(temp_path.exists());
+
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&temp_path)
+            .expect("open temp");
+        file.set_modified(std::time::SystemTime::now() - STALE_TEMP_AGE * 2)
+            .expect("age temp");
+        drop(file);
+
+        let mut app = App::new(config_path.clone());
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+        app.fields[idx].value = "18".into();
+        app.save_config().expect("save_config");
+
+        assert!(!temp_path.exists());
+        let written = std::fs::read_to_string(&config_path).expect("read back");
+        assert!(written.contains("config.font_size = 18\n"));
+    }
+
+    #[test]
+    fn enter_on_section_header_collapses_and_hides_fields() {
+        let mut app = test_app();
+        app.selected_section = Some("Window");
+
+        app.start_edit();
+
+        assert!(app.is_section_collapsed("Window"));
+        assert!(!app.dirty);
+        assert!(!app
+            .visible_rows()
+            .iter()
+            .any(|row| matches!(row, ListRow::Field(idx) if app.fields[*idx].section == "Window")));
+        assert!(app.visible_rows().contains(&ListRow::Section("Window")));
+
+        app.start_edit();
+        assert!(!app.is_section_collapsed("Window"));
+    }
+
+    #[test]
+    fn view_state_round_trips_cursor_and_collapsed_sections() {
+        let dir = tempdir().expect("tempdir");
+        let state_path = dir.path().join(VIEW_STATE_FILE);
+
+        let mut app = test_app();
+        app.view_state_path = Some(state_path.clone());
+        app.selected = 3;
+        app.toggle_section("Window");
+        app.persist_view_state().expect("persist view state");
+
+        let mut restored = test_app();
+        restored.view_state_path = Some(state_path);
+        restored.restore_view_state();
+
+        assert_eq!(restored.selected, 3);
+        assert!(restored.is_section_collapsed("Window"));
+    }
+
+    #[test]
+    fn view_state_follows_field_when_fields_move() {
+        let mut app = test_app();
+        app.selected = 3;
+        let lua_key = app.fields[3].lua_key;
+        let state = app.view_state();
+
+        let mut restored = test_app();
+        restored.fields.remove(0);
+        restored.apply_view_state(&state);
+
+        assert_eq!(restored.fields[restored.selected].lua_key, lua_key);
+    }
+
+    #[test]
+    fn view_state_ignores_unknown_fields_and_corrupt_files() {
+        let mut app = test_app();
+        app.apply_view_state("selected=no_such_setting\ncollapsed=Nope\n");
+        assert_eq!(app.selected, 0);
+        assert!(app.collapsed_sections.is_empty());
+
+        let mut app = test_app();
+        app.apply_view_state("selected=9999\n");
+        assert_eq!(app.selected, 0);
+
+        let mut app = test_app();
+        app.apply_view_state("\u{0}garbage");
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn view_state_puts_cursor_on_header_of_collapsed_section() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.section == "Window")
+            .expect("window field");
+        let lua_key = app.fields[idx].lua_key;
+
+        app.apply_view_state(&format!("selected={lua_key}\ncollapsed=Window\n"));
+
+        assert_eq!(app.selected, idx);
+        assert_eq!(app.selected_section, Some("Window"));
+    }
+
+    #[test]
+    fn navigation_skips_fields_in_collapsed_sections() {
+        let mut app = test_app();
+        app.toggle_section("Window");
+        app.selected_section = Some("Window");
+
+        app.move_down();
+
+        assert_eq!(app.cursor_row(), ListRow::Section("Behavior"));
+        app.move_up();
+        assert_eq!(app.cursor_row(), ListRow::Section("Window"));
+        app.move_up();
+        let last_integration = app
+            .fields
+            .iter()
+            .rposition(|f| f.section == "Integrations")
+            .expect("integrations field to exist");
+        assert_eq!(app.cursor_row(), ListRow::Field(last_integration));
+    }
+
+    #[test]
+    fn page_clamping_handles_empty_and_single_field_lists() {
+        assert_eq!(clamp_to_field(&[], 0, 5), None);
+        assert_eq!(clamp_to_field(&[ListRow::Section("Window")], 0, 5), None);
+
+        let single = [ListRow::Section("Window"), ListRow::Field(0)];
+        for delta in [isize::MIN, -5, 0, 5, isize::MAX] {
+            assert_eq!(clamp_to_field(&single, 0, delta), Some(1));
+            assert_eq!(clamp_to_field(&single, 1, delta), Some(1));
+        }
+    }
+
+    #[test]
+    fn page_and_jump_keys_land_on_fields() {
+        let mut app = test_app();
+        app.list_height = 6;
+        assert_eq!(
+            normal_mode_action(KeyCode::PageDown),
+            NormalModeAction::PageDown
+        );
+        assert_eq!(
+            normal_mode_action(KeyCode::Char('G')),
+            NormalModeAction::MoveLast
+        );
+
+        app.move_last();
+        assert_eq!(app.selected, app.fields.len() - 1);
+        assert_eq!(app.selected_section, None);
+
+        app.move_first();
+        assert_eq!(app.selected, 0);
+        assert_eq!(app.selected_section, None);
+
+        // Five rows down from the first field is the Integrations header, so
+        // the cursor continues onto that section's first field.
+        app.page_down();
+        assert_eq!(app.selected_section, None);
+        assert_eq!(app.fields[app.selected].section, "Integrations");
+        app.page_up();
+        assert_eq!(app.selected, 1);
+    }
+
+    #[test]
+    fn collapsing_section_of_selected_field_moves_cursor_to_header() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "enable_scroll_bar")
+            .expect("enable_scroll_bar field to exist");
+        app.selected = idx;
+
+        app.toggle_section("Window");
+
+        assert_eq!(app.cursor_row(), ListRow::Section("Window"));
+    }
+
+    #[test]
+    fn load_config_captures_original_value_for_modified_marker() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(&config_path, "config.font_size = 15\n").expect("write config");
+
+        let mut app = App::new(config_path);
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+
+        assert_eq!(app.fields[idx].original_value, "15");
+        assert!(!app.fields[idx].is_modified());
+
+        app.fields[idx].value = "18".into();
+        assert!(app.fields[idx].is_modified());
+    }
+
+    #[test]
+    fn pending_changes_reports_changed_added_and_removed_lines() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &config_path,
+            "local config = {}\nconfig.tab_bar_at_bottom = true\nconfig.font_size = 15\nconfig.line_height = 1.5\nreturn config\n",
+        )
+        .expect("write config");
+
+        let mut app = App::new(config_path);
+        app.load_config();
+        for field in &mut app.fields {
+            match field.lua_key {
+                "font_size" => field.value = "18".into(),
+                "line_height" => field.value = String::new(),
+                "enable_scroll_bar" => field.value = "On".into(),
+                _ => {}
+            }
+        }
+        app.dirty = true;
+        app.open_preview();
+
+        assert!(matches!(app.mode, Mode::Previewing));
+        assert_eq!(
+            app.preview,
+            vec![
+                PendingChange {
+                    key: "Font Size",
+                    before: Some("config.font_size = 15".into()),
+                    after: Some("config.font_size = 18".into()),
+                },
+                PendingChange {
+                    key: "Line Height",
+                    before: Some("config.line_height = 1.5".into()),
+                    after: None,
+                },
+                PendingChange {
+                    key: "Scrollbar",
+                    before: None,
+                    after: Some("config.enable_scroll_bar = true".into()),
+                },
+            ]
+        );
+
+        app.close_preview();
+        assert!(matches!(app.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn tab_bar_visibility_toggles_and_round_trips() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &config_path,
+            "local wezterm = require 'wezterm'\nlocal config = {}\nreturn config\n",
+        )
+        .expect("write config");
+
+        let mut app = App::new(config_path.clone());
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "hide_tab_bar_if_only_one_tab")
+            .expect("hide_tab_bar_if_only_one_tab field to exist");
+        assert_eq!(app.fields[idx].default, "Auto");
+        app.selected = idx;
+
+        app.start_edit();
+        assert_eq!(app.fields[idx].value, "Always");
+        assert!(matches!(app.mode, Mode::Normal));
+
+        app.save_config().expect("save_config");
+        let written = std::fs::read_to_string(&config_path).expect("read back");
+        assert!(written.contains("config.hide_tab_bar_if_only_one_tab = false\n"));
+        // Tab bar position stays explicit alongside the visibility setting.
+        assert!(written.contains("config.tab_bar_at_bottom = true\n"));
+
+        let mut reloaded = App::new(config_path);
+        reloaded.load_config();
+        assert_eq!(reloaded.fields[idx].value, "Always");
+        assert!(!reloaded.fields[idx].skip_write);
+    }
+
+    #[test]
+    fn ligatures_field_round_trips_harfbuzz_features() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &config_path,
+            "local config = {}\nconfig.harfbuzz_features = { 'calt=0', 'clig=0', 'liga=0' }\nreturn config\n",
+        )
+        .expect("write config");
+
+        let mut app = App::new(config_path.clone());
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "harfbuzz_features")
+            .expect("harfbuzz_features field to exist");
+        assert_eq!(app.fields[idx].value, "Off");
+        assert!(!app.fields[idx].skip_write);
+
+        app.selected = idx;
+        app.start_edit();
+        assert_eq!(app.fields[idx].value, "On");
+        assert_eq!(app.to_lua_value(&app.fields[idx]), "{}");
+
+        app.save_config().expect("save_config");
+        let written = std::fs::read_to_string(&config_path).expect("read back");
+        assert!(written.contains("config.harfbuzz_features = {}\n"));
+    }
+
+    #[test]
+    fn duplicate_keys_are_protected_and_reported() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &config_path,
+            "config.font_size = 15\n-- config.line_height = 1.2\nconfig.font_size = 18\nconfig.line_height = 1.4\n",
+        )
+        .expect("write config");
+
+        let mut app = App::new(config_path);
+        app.load_config();
+
+        let font_size = app
+            .fields
+            .iter()
+            .find(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+        let line_height = app
+            .fields
+            .iter()
+            .find(|f| f.lua_key == "line_height")
+            .expect("line_height field to exist");
+
+        assert!(font_size.skip_write);
+        assert!(!line_height.skip_write);
+        assert_eq!(app.warnings.len(), 1);
+        assert!(app.warnings[0].contains("font_size"));
+        assert!(!app.warnings[0].contains("line_height"));
+    }
+
+    #[test]
+    fn terminal_guard_restores_on_early_return() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static RESTORED: AtomicUsize = AtomicUsize::new(0);
+
+        fn record_restore(raw_mode: bool, alternate_screen: bool) {
+            assert!(raw_mode);
+            assert!(alternate_screen);
+            RESTORED.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn enter_then_fail() -> anyhow::Result<()> {
+            let mut guard = TerminalGuard::new();
+            guard.restore = record_restore;
+            guard.raw_mode = true;
+            guard.alternate_screen = true;
+            anyhow::bail!("draw failed");
+        }
+
+        assert!(enter_then_fail().is_err());
+        assert_eq!(RESTORED.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn word_motions_stop_at_hotkey_parts() {
+        let mut app = test_app();
+        app.edit_buffer = "Ctrl+Alt+Cmd+K".to_string();
+        app.edit_cursor = app.edit_buffer.chars().count();
+
+        let mut stops = Vec::new();
+        for _ in 0..5 {
+            app.edit_word_left();
+            stops.push(app.edit_cursor);
+        }
+        assert_eq!(stops, vec![13, 9, 5, 0, 0]);
+
+        stops.clear();
+        for _ in 0..5 {
+            app.edit_word_right();
+            stops.push(app.edit_cursor);
+        }
+        assert_eq!(stops, vec![4, 8, 12, 14, 14]);
+    }
+
+    #[test]
+    fn word_motions_count_multibyte_chars() {
+        let mut app = test_app();
+        app.edit_buffer = "霞鹜文楷  Mono".to_string();
+        app.edit_cursor = app.edit_buffer.chars().count();
+
+        app.edit_word_left();
+        assert_eq!(app.edit_cursor, 6);
+        app.edit_word_left();
+        assert_eq!(app.edit_cursor, 0);
+        app.edit_word_right();
+        assert_eq!(app.edit_cursor, 4);
+
+        // The cursor stays a char index, so inserting lands between the words.
+        app.edit_insert('!');
+        assert_eq!(app.edit_buffer, "霞鹜文楷!  Mono");
+    }
+
+    #[test]
+    fn terminal_guard_undoes_raw_mode_when_alternate_screen_fails() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static RESTORED: AtomicUsize = AtomicUsize::new(0);
+
+        fn record_restore(raw_mode: bool, alternate_screen: bool) {
+            assert!(raw_mode);
+            assert!(!alternate_screen);
+            RESTORED.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn raw_then_fail() -> anyhow::Result<()> {
+            let mut guard = TerminalGuard::new();
+            guard.restore = record_restore;
+            guard.raw_mode = true;
+            anyhow::bail!("enter alternate screen");
+        }
+
+        assert!(raw_then_fail().is_err());
+        assert_eq!(RESTORED.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn unsupported_terminals_are_rejected_up_front() {
+        assert_eq!(
+            unsupported_terminal_reason(true, true, Some("xterm-256color")),
+            None
+        );
+        assert_eq!(unsupported_terminal_reason(true, true, None), None);
+        assert!(unsupported_terminal_reason(true, false, Some("xterm")).is_some());
+        assert!(unsupported_terminal_reason(false, true, Some("xterm")).is_some());
+        assert!(unsupported_terminal_reason(true, true, Some("dumb")).is_some());
+    }
+
+    #[test]
+    fn hotkey_modifier_order_is_normalized() {
+        assert_eq!(
+            App::hotkey_to_lua("Cmd+Ctrl+K"),
+            App::hotkey_to_lua("Ctrl+Cmd+K")
+        );
+        assert_eq!(
+            App::hotkey_to_lua("Shift+Cmd+Alt+Ctrl+K").as_deref(),
+            Some("{ key = 'K', mods = 'CTRL|ALT|SUPER|SHIFT' }")
+        );
+        assert_eq!(
+            App::canonical_hotkey("cmd+opt+ctrl+k").as_deref(),
+            Some("Ctrl+Alt+Cmd+K")
+        );
+    }
+
+    #[test]
+    fn hotkey_rejects_bare_and_shift_only_keys() {
+        assert_eq!(App::hotkey_to_lua("K"), None);
+        assert_eq!(App::hotkey_to_lua("Shift+K"), None);
+    }
+
+    #[test]
+    fn reserved_hotkeys_warn_without_rejecting() {
+        assert!(App::hotkey_warning("Cmd+Q").is_some());
+        assert!(App::hotkey_warning("Command+w").is_some());
+        assert!(App::hotkey_warning("Ctrl+Alt+Cmd+K").is_none());
+
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "macos_global_hotkey")
+            .expect("macos_global_hotkey field to exist");
+        app.selected = idx;
+        app.start_edit();
+        app.edit_buffer = "command+q".into();
+        app.confirm_edit();
+
+        assert_eq!(app.fields[idx].value, "Cmd+Q");
+        assert!(app.notice.as_deref().unwrap_or_default().contains("Cmd+Q"));
+    }
+
+    #[test]
+    fn hotkey_named_keys_map_to_wezterm_identifiers() {
+        assert_eq!(
+            App::hotkey_to_lua("Ctrl+Alt+f5").as_deref(),
+            Some("{ key = 'F5', mods = 'CTRL|ALT' }")
+        );
+        assert_eq!(
+            App::hotkey_to_lua("Cmd+Left").as_deref(),
+            Some("{ key = 'LeftArrow', mods = 'SUPER' }")
+        );
+        assert_eq!(
+            App::hotkey_to_lua("Ctrl+space").as_deref(),
+            Some("{ key = 'Space', mods = 'CTRL' }")
+        );
+        assert_eq!(App::hotkey_to_lua("Ctrl+F25"), None);
+        assert_eq!(App::hotkey_to_lua("Ctrl+Banana"), None);
+        assert_eq!(App::hotkey_to_lua("Ctrl+'"), None);
+    }
+
+    #[test]
+    fn hotkey_named_keys_round_trip_to_display_form() {
+        assert_eq!(
+            App::normalize_hotkey_table("{ key = 'F12', mods = 'CTRL|SUPER' }").as_deref(),
+            Some("Ctrl+Cmd+F12")
+        );
+        assert_eq!(
+            App::normalize_hotkey_table("{ key = 'UpArrow', mods = 'ALT' }").as_deref(),
+            Some("Alt+Up")
+        );
+        assert_eq!(
+            App::normalize_hotkey_table("{ key = 'k', mods = 'CTRL' }").as_deref(),
+            Some("Ctrl+K")
+        );
+        for display in ["Ctrl+Alt+F5", "Cmd+Down", "Ctrl+Esc", "Alt+Tab"] {
+            let lua = App::hotkey_to_lua(display).expect("valid hotkey");
+            assert_eq!(App::normalize_hotkey_table(&lua).as_deref(), Some(display));
+        }
+    }
+
+    #[test]
+    fn header_path_label_abbreviates_home_and_flags_missing_file() {
+        let app = App::new(config::HOME_DIR.join(".config/kaku/kaku-tui-missing-test.lua"));
+        assert_eq!(
+            app.config_path_label(),
+            "~/.config/kaku/kaku-tui-missing-test.lua (will be created)"
+        );
+
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(&config_path, "").expect("write config");
+        let app = App::new(config_path.clone());
+        assert_eq!(app.config_path_label(), config_path.display().to_string());
+    }
+
+    #[test]
+    fn render_config_applies_field_states_without_touching_disk() {
+        let config_path = PathBuf::from("/tmp/kaku-config-tui-render-test/kaku.lua");
+        let mut app = App::new(config_path.clone());
+        for field in &mut app.fields {
+            match field.lua_key {
+                "font_size" => field.value = "18".into(),
+                "line_height" => field.value = field.default.clone(),
+                _ => {}
+            }
+        }
+
+        let rendered = app.render_config(
+            "local config = {}\nconfig.line_height = 1.5\nconfig.custom = 1\nreturn config\n",
+        );
+        assert!(rendered.contains("config.font_size = 18\n"));
+        assert!(!rendered.contains("config.line_height"));
+        assert!(rendered.contains("config.custom = 1\n"));
+        assert!(rendered.ends_with("return config\n"));
+        assert!(!config_path.exists());
+    }
+
+    #[test]
+    fn missing_field_is_inserted_after_related_key() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .unwrap();
+        app.fields[idx].value = "16".into();
+
+        let content = "local config = {}\nconfig.font = wezterm.font_with_fallback({\n  'JetBrains Mono',\n  'PingFang SC',\n})\nconfig.copy_on_select = false\nreturn config\n";
+        let updated = app.update_lua_config(content, &app.fields[idx]);
+        assert_eq!(
+            updated,
+            "local config = {}\nconfig.font = wezterm.font_with_fallback({\n  'JetBrains Mono',\n  'PingFang SC',\n})\nconfig.font_size = 16\nconfig.copy_on_select = false\nreturn config\n"
+        );
+
+        // With no earlier neighbour, the field goes before a later one.
+        let content = "local config = {}\nconfig.copy_on_select = false\nconfig.line_height = 1.2\nreturn config\n";
+        let updated = app.update_lua_config(content, &app.fields[idx]);
+        assert_eq!(
+            updated,
+            "local config = {}\nconfig.copy_on_select = false\nconfig.font_size = 16\nconfig.line_height = 1.2\nreturn config\n"
+        );
+
+        // A call whose arguments continue on later lines is skipped whole.
+        let content = "local config = {}\nconfig.font = wezterm.font(\n  'JetBrains Mono',\n  { weight = 'Medium' }\n)\nreturn config\n";
+        let updated = app.update_lua_config(content, &app.fields[idx]);
+        assert_eq!(
+            updated,
+            "local config = {}\nconfig.font = wezterm.font(\n  'JetBrains Mono',\n  { weight = 'Medium' }\n)\nconfig.font_size = 16\nreturn config\n"
+        );
+    }
+
+    #[test]
+    fn missing_field_without_related_key_goes_before_return() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .unwrap();
+        app.fields[idx].value = "16".into();
+
+        // Indented lines inside a block never serve as anchors.
+        let content = "local config = {}\nif true then\n  config.font = wezterm.font('Menlo')\nend\nconfig.copy_on_select = false\nreturn config\n";
+        let updated = app.update_lua_config(content, &app.fields[idx]);
+        assert_eq!(
+            updated,
+            "local config = {}\nif true then\n  config.font = wezterm.font('Menlo')\nend\nconfig.copy_on_select = false\nconfig.font_size = 16\nreturn config\n"
+        );
+    }
+
+    #[test]
+    fn explicit_defaults_keep_default_values_in_config() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .unwrap();
+        app.fields[idx].value = app.fields[idx].default.clone();
+        let original = "local config = {}\nconfig.font_size = 20\nreturn config\n";
+
+        assert!(!app.render_config(original).contains("config.font_size"));
+
+        app.explicit_defaults = true;
+        let rendered = app.render_config(original);
+        assert!(rendered.contains("config.font_size = 17\n"));
+
+        app.fields[idx].value.clear();
+        assert_eq!(app.render_config(original), rendered);
+
+        // Every unset field is spelled out as Lua, never as its display label.
+        for field in &mut app.fields {
+            field.value.clear();
+        }
+        let rendered = app.render_config("local config = {}\nreturn config\n");
+        for field in &app.fields {
+            let placeholder = format!("config.{} = {}\n", field.lua_key, field.default);
+            if field.default.parse::<f64>().is_err() {
+                assert!(!rendered.contains(&placeholder), "{rendered}");
+            }
+        }
+        assert!(rendered.contains("config.copy_on_select = true\n"));
+        assert!(!rendered.contains("config.window_padding"));
+        assert!(!rendered.contains("__assistant_enabled__"));
+    }
+
+    #[test]
+    fn explicit_defaults_flag_is_read_from_config() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &path,
+            "local config = {}\nconfig.kaku_config_explicit_defaults = true\nreturn config\n",
+        )
+        .expect("write config");
+
+        let mut app = App::new(path);
+        app.load_config();
+        assert!(app.explicit_defaults);
+    }
+
+    #[test]
+    fn explicit_defaults_flag_tolerates_trailing_comment() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &path,
+            "local config = {}\nconfig.kaku_config_explicit_defaults = true -- keep keys\nreturn config\n",
+        )
+        .expect("write config");
+
+        let mut app = App::new(path);
+        app.load_config();
+        assert!(app.explicit_defaults);
+    }
+
+    #[test]
+    fn dry_run_save_renders_without_writing() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        let original = "local config = {}\nconfig.font_size = 15\nreturn config\n";
+        std::fs::write(&config_path, original).expect("write config");
+
+        let mut app = App::new(config_path.clone());
+        app.dry_run = true;
+        app.load_config();
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font_size")
+            .expect("font_size field to exist");
+        app.fields[idx].value = "18".into();
+        app.dirty = true;
+
+        app.save_if_dirty().expect("dry run save");
+
+        let output = app.dry_run_output.as_deref().expect("dry run output");
+        assert!(output.contains("config.font_size = 18\n"));
+        assert_eq!(
+            std::fs::read_to_string(&config_path).expect("read back"),
+            original
+        );
+        assert!(app.config_path_label().ends_with("(dry run)"));
+    }
+
+    #[test]
+    fn env_config_file_skips_bundled_defaults() {
+        assert!(is_bundled_config(Path::new(
+            "/Applications/Kaku.app/Contents/Resources/kaku.lua"
+        )));
+        assert!(!is_bundled_config(Path::new(
+            "/Users/me/dotfiles/kaku/kaku.lua"
+        )));
+
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("fixture.lua");
+        assert!(check_config_writable(&path).is_err());
+        std::fs::write(&path, "local config = {}\nreturn config\n").expect("write fixture");
+        assert!(check_config_writable(&path).is_ok());
+    }
+
+    #[test]
+    fn field_labels_match_shared_settings_list() {
+        let app = test_app();
+        let labels: Vec<&str> = app.fields.iter().map(|field| field.key).collect();
+        assert_eq!(labels, config::SETTINGS_FIELD_LABELS);
+    }
+
+    #[test]
+    fn focus_field_selects_and_expands_matching_field() {
+        let mut app = test_app();
+        let idx = app
+            .fields
+            .iter()
+            .position(|field| field.key == "Font Size")
+            .unwrap();
+        let section = app.fields[idx].section;
+        app.toggle_section(section);
+
+        assert!(app.focus_field("font size"));
+        assert_eq!(app.cursor_row(), ListRow::Field(idx));
+        assert!(!app.is_section_collapsed(section));
+
+        assert!(app.focus_field("line_height"));
+        assert_eq!(app.fields[app.selected].key, "Line Height");
+    }
+
+    #[test]
+    fn set_value_validates_and_maps_human_keys() {
+        let mut app = test_app();
+
+        assert_eq!(app.set_value("font_size", "18"), Ok(true));
+        let idx = app.find_field("Font Size").unwrap();
+        assert_eq!(app.fields[idx].value, "18");
+        assert!(app.dirty);
+        assert_eq!(app.set_value("FONT SIZE", "18"), Ok(false));
+
+        assert_eq!(app.find_field("theme"), app.find_field("color_scheme"));
+        assert_eq!(
+            app.find_field("tab_bar_position"),
+            app.find_field("Tab Bar Position")
+        );
+
+        assert!(app
+            .set_value("font_size", "big")
+            .unwrap_err()
+            .contains("not a number"));
+        assert_eq!(app.fields[idx].value, "18");
+        assert!(app
+            .set_value("no_such_setting", "1")
+            .unwrap_err()
+            .contains("Unknown setting `no_such_setting`"));
+    }
+
+    #[test]
+    fn set_setting_saves_the_new_value() {
+        let dir = tempdir().expect("tempdir");
+        let config_path = dir.path().join("kaku.lua");
+        std::fs::write(
+            &config_path,
+            "local config = {}\nconfig.font_size = 15\nreturn config\n",
+        )
+        .expect("write config");
+
+        let (changed, warning) =
+            super::set_setting(config_path.clone(), "font_size", "18").expect("set");
+        assert!(changed);
+        assert!(warning.is_none());
+        let written = std::fs::read_to_string(&config_path).unwrap();
+        assert!(written.contains("config.font_size = 18"));
+
+        assert!(super::set_setting(config_path.clone(), "font_size", "huge").is_err());
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), written);
+    }
+
+    #[test]
+    fn focus_field_ignores_unknown_names() {
+        let mut app = test_app();
+        app.selected = 2;
+
+        assert!(!app.focus_field("No Such Setting"));
+        assert_eq!(app.cursor_row(), ListRow::Field(2));
+        assert!(app.notice.as_deref().unwrap().contains("No Such Setting"));
+    }
 }
@@ -1,10 +1,10 @@
-use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::layout::{Alignment, Constraint, Layout, Margin, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
 
-use super::{App, Mode};
-use crate::tui_core::theme::{accent, bg, muted, panel, primary, text_fg};
+use super::{App, ConfigField, ListRow, Mode};
+use crate::tui_core::theme::{accent, bg, muted, panel, primary, red, selection, success, text_fg};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum MainLayoutMode {
@@ -27,7 +27,7 @@ enum FooterLabelStyle {
     Short,
 }
 
-const NORMAL_FOOTER_ACTIONS: [FooterAction; 5] = [
+const NORMAL_FOOTER_ACTIONS: [FooterAction; 7] = [
     FooterAction {
         key: "↑↓",
         long_label: "Navigate",
@@ -43,11 +43,21 @@ const NORMAL_FOOTER_ACTIONS: [FooterAction; 5] = [
         long_label: "Save & Exit",
         short_label: "Save",
     },
+    FooterAction {
+        key: "Ctrl+S",
+        long_label: "Save",
+        short_label: "Save",
+    },
     FooterAction {
         key: "Q",
         long_label: "Discard",
         short_label: "Discard",
     },
+    FooterAction {
+        key: "D",
+        long_label: "Diff",
+        short_label: "Diff",
+    },
     FooterAction {
         key: "E",
         long_label: "Open File",
@@ -73,7 +83,7 @@ const SELECTING_FOOTER_ACTIONS: [FooterAction; 3] = [
     },
 ];
 
-const EDITING_FOOTER_ACTIONS: [FooterAction; 2] = [
+const EDITING_FOOTER_ACTIONS: [FooterAction; 3] = [
     FooterAction {
         key: "Enter",
         long_label: "Apply",
@@ -84,6 +94,55 @@ const EDITING_FOOTER_ACTIONS: [FooterAction; 2] = [
         long_label: "Cancel",
         short_label: "Cancel",
     },
+    FooterAction {
+        key: "Ctrl+U",
+        long_label: "Clear",
+        short_label: "Clear",
+    },
+];
+
+const EDITING_TABLE_FOOTER_ACTIONS: [FooterAction; 3] = [
+    FooterAction {
+        key: "Ctrl+S",
+        long_label: "Apply",
+        short_label: "Apply",
+    },
+    FooterAction {
+        key: "Enter",
+        long_label: "New Line",
+        short_label: "Line",
+    },
+    FooterAction {
+        key: "Esc",
+        long_label: "Cancel",
+        short_label: "Cancel",
+    },
+];
+
+const PREVIEWING_FOOTER_ACTIONS: [FooterAction; 2] = [
+    FooterAction {
+        key: "Enter",
+        long_label: "Save & Exit",
+        short_label: "Save",
+    },
+    FooterAction {
+        key: "Esc",
+        long_label: "Back",
+        short_label: "Back",
+    },
+];
+
+const CONFIRMING_OVERWRITE_FOOTER_ACTIONS: [FooterAction; 2] = [
+    FooterAction {
+        key: "Enter",
+        long_label: "Replace",
+        short_label: "Replace",
+    },
+    FooterAction {
+        key: "Esc",
+        long_label: "Keep Custom",
+        short_label: "Keep",
+    },
 ];
 
 fn footer_copy(mode: Mode) -> &'static [FooterAction] {
@@ -91,12 +150,25 @@ fn footer_copy(mode: Mode) -> &'static [FooterAction] {
         Mode::Normal => &NORMAL_FOOTER_ACTIONS,
         Mode::Selecting => &SELECTING_FOOTER_ACTIONS,
         Mode::Editing => &EDITING_FOOTER_ACTIONS,
+        Mode::EditingTable => &EDITING_TABLE_FOOTER_ACTIONS,
+        Mode::Previewing => &PREVIEWING_FOOTER_ACTIONS,
+        Mode::ConfirmingOverwrite => &CONFIRMING_OVERWRITE_FOOTER_ACTIONS,
     }
 }
 
+/// Width of the key column in the settings list.
+const KEY_WIDTH: usize = 22;
+/// Columns before the key: indent, cursor marker, and modified dot.
+const KEY_COLUMN: usize = 6;
+
+/// Below this size the list and popups cannot be laid out legibly.
+const MIN_WIDTH: u16 = 30;
+const MIN_HEIGHT: u16 = 10;
+
 pub(super) fn ui(frame: &mut ratatui::Frame, app: &mut App) {
     let full = frame.area();
-    if full.width < 2 || full.height < 2 {
+    if is_too_small(full) {
+        render_too_small(frame, full);
         return;
     }
 
@@ -107,17 +179,17 @@ pub(super) fn ui(frame: &mut ratatui::Frame, app: &mut App) {
     frame.render_widget(Clear, area);
     frame.render_widget(Block::default().style(Style::default().bg(bg())), area);
 
-    let content_rows = rendered_field_row_count(app);
+    let content_rows = rendered_field_row_count(app, area.width);
     match resolve_main_layout(area.height, content_rows) {
         MainLayoutMode::HeaderOnly => {
             let chunks = Layout::vertical([Constraint::Length(2)]).split(area);
-            render_header(frame, chunks[0]);
+            render_header(frame, chunks[0], app);
         }
         MainLayoutMode::HeaderAndFooter => {
             let chunks =
                 Layout::vertical([Constraint::Length(2), Constraint::Length(1)]).split(area);
-            render_header(frame, chunks[0]);
-            render_footer(frame, chunks[1], app.mode);
+            render_header(frame, chunks[0], app);
+            render_footer(frame, chunks[1], app);
         }
         MainLayoutMode::Expanded => {
             let chunks = Layout::vertical([
@@ -129,9 +201,11 @@ pub(super) fn ui(frame: &mut ratatui::Frame, app: &mut App) {
             ])
             .split(area);
 
-            render_header(frame, chunks[0]);
+            render_header(frame, chunks[0], app);
+            app.list_height = chunks[1].height;
             render_fields(frame, chunks[1], app);
-            render_footer(frame, chunks[4], app.mode);
+            render_warning(frame, chunks[3], app);
+            render_footer(frame, chunks[4], app);
         }
         MainLayoutMode::Compact => {
             let chunks = Layout::vertical([
@@ -142,9 +216,11 @@ pub(super) fn ui(frame: &mut ratatui::Frame, app: &mut App) {
             ])
             .split(area);
 
-            render_header(frame, chunks[0]);
+            render_header(frame, chunks[0], app);
+            app.list_height = chunks[1].height;
             render_fields(frame, chunks[1], app);
-            render_footer(frame, chunks[3], app.mode);
+            render_warning(frame, chunks[2], app);
+            render_footer(frame, chunks[3], app);
         }
     }
 
@@ -152,9 +228,50 @@ pub(super) fn ui(frame: &mut ratatui::Frame, app: &mut App) {
         render_selector(frame, area, app);
     } else if app.mode == Mode::Editing {
         render_editor(frame, area, app);
+    } else if app.mode == Mode::EditingTable {
+        render_table_editor(frame, area, app);
+    } else if app.mode == Mode::Previewing {
+        render_preview(frame, area, app);
+    } else if app.mode == Mode::ConfirmingOverwrite {
+        render_overwrite_prompt(frame, area, app);
     }
 }
 
+fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+fn render_too_small(frame: &mut ratatui::Frame, area: Rect) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(Block::default().style(Style::default().bg(bg())), area);
+
+    let message = Rect::new(area.x, area.y + area.height / 2, area.width, 1);
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            "Window too small",
+            Style::default().fg(muted()),
+        )))
+        .alignment(Alignment::Center),
+        message,
+    );
+}
+
+/// Centers a popup of the requested size inside `area`, shrinking it to fit.
+fn centered_popup(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
 fn resolve_main_layout(area_height: u16, content_rows: u16) -> MainLayoutMode {
     let remaining_height = area_height.saturating_sub(2);
     if remaining_height == 0 {
@@ -168,69 +285,171 @@ fn resolve_main_layout(area_height: u16, content_rows: u16) -> MainLayoutMode {
     }
 }
 
-fn rendered_field_row_count(app: &App) -> u16 {
-    let mut rows = app.fields.len() as u16;
-    let mut sections = 0u16;
-    let mut last_section: Option<&str> = None;
+fn rendered_field_row_count(app: &App, width: u16) -> u16 {
+    let rows = app.visible_rows();
+    let sections = rows
+        .iter()
+        .filter(|row| matches!(row, ListRow::Section(_)))
+        .count();
 
-    for field in &app.fields {
-        if last_section != Some(field.section) {
-            sections += 1;
-            if last_section.is_some() {
-                rows += 1;
-            }
-            last_section = Some(field.section);
-        }
+    // One blank spacer line between consecutive sections.
+    let detail = selected_value_detail(app, width).len();
+    (rows.len() + sections.saturating_sub(1) + detail) as u16
+}
+
+/// Options suffix shown after the value of fields that open the selector.
+fn value_suffix(field: &ConfigField) -> &'static str {
+    if field.has_options() && field.options.len() > 2 {
+        " ▾"
+    } else {
+        ""
     }
+}
 
-    rows + sections
+/// The config's own text for a field whose line could not be parsed, which is
+/// shown instead of the default while the line is left untouched.
+fn unparsed_value(field: &ConfigField) -> Option<&str> {
+    field
+        .raw_value
+        .as_deref()
+        .filter(|_| field.skip_write && field.value.is_empty())
 }
 
-fn render_header(frame: &mut ratatui::Frame, area: Rect) {
-    let line = Line::from(vec![
+/// Value text for a field's row, preferring its unparsed config text.
+fn shown_value(app: &App, field: &ConfigField) -> String {
+    match unparsed_value(field) {
+        Some(raw) => raw.to_string(),
+        None => app.list_value(field),
+    }
+}
+
+/// Marks values inherited from Kaku's defaults rather than set in the file,
+/// and values kept from a config line the TUI cannot edit.
+fn default_tag(field: &ConfigField) -> &'static str {
+    if unparsed_value(field).is_some() {
+        " ⚠ custom (not editable here)"
+    } else if field.value.is_empty() {
+        " (default)"
+    } else {
+        ""
+    }
+}
+
+/// Room left for a value (and its suffix) after the key column.
+fn value_width(width: u16) -> usize {
+    (width as usize).saturating_sub(KEY_COLUMN + KEY_WIDTH)
+}
+
+/// Shortens `value` to at most `max` chars, ending in `…` when cut.
+fn truncate_value(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        return value.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut truncated: String = value.chars().take(max - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Full value of the selected field, split into lines shown under its row.
+/// Empty when the value fits, so only truncated values expand.
+fn selected_value_detail(app: &App, width: u16) -> Vec<String> {
+    if app.selected_section.is_some() {
+        return Vec::new();
+    }
+    let field = &app.fields[app.selected];
+    if app.is_section_collapsed(field.section) {
+        return Vec::new();
+    }
+    let value = shown_value(app, field);
+    let suffix = value_suffix(field).chars().count() + default_tag(field).chars().count();
+    if value.chars().count() + suffix <= value_width(width) {
+        return Vec::new();
+    }
+
+    let chunk = (width as usize).saturating_sub(KEY_COLUMN).max(1);
+    let chars: Vec<char> = value.chars().collect();
+    chars
+        .chunks(chunk)
+        .map(|part| part.iter().collect())
+        .collect()
+}
+
+fn render_header(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let mut spans = vec![
         Span::styled(
             "  Kaku",
             Style::default().fg(primary()).add_modifier(Modifier::BOLD),
         ),
         Span::styled(" · ", Style::default().fg(muted())),
         Span::styled("Settings", Style::default().fg(text_fg())),
-    ]);
-    frame.render_widget(Paragraph::new(vec![line, Line::from("")]), area);
+        Span::styled("  ", Style::default()),
+        Span::styled(app.config_path_label(), Style::default().fg(muted())),
+    ];
+    if app.read_only {
+        spans.push(Span::styled("  ", Style::default()));
+        spans.push(Span::styled(
+            " READ ONLY ",
+            Style::default()
+                .fg(accent())
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(spans), Line::from("")]),
+        area,
+    );
 }
 
 fn render_fields(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     let area = area.inner(Margin::new(0, 0));
     let mut items: Vec<ListItem> = Vec::new();
     let mut selected_flat: Option<usize> = None;
-    let mut flat = 0usize;
-    let key_width = 24usize;
-    let mut current_section: Option<&str> = None;
-
-    for (idx, field) in app.fields.iter().enumerate() {
-        if current_section != Some(field.section) {
-            if current_section.is_some() {
-                items.push(ListItem::new(Line::from("")));
-                flat += 1;
-            }
+    let cursor = app.cursor_row();
 
-            items.push(ListItem::new(Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(
-                    field.section,
-                    Style::default().fg(muted()).add_modifier(Modifier::BOLD),
-                ),
-            ])));
-            flat += 1;
-            current_section = Some(field.section);
+    for row in app.visible_rows() {
+        if matches!(row, ListRow::Section(_)) && !items.is_empty() {
+            items.push(ListItem::new(Line::from("")));
         }
 
-        let is_selected = idx == app.selected;
+        let is_selected = row == cursor;
         if is_selected {
-            selected_flat = Some(flat);
+            selected_flat = Some(items.len());
         }
 
-        let display_value = app.display_value(field);
-        let has_options = field.has_options();
+        let field = match row {
+            ListRow::Section(section) => {
+                let glyph = if app.is_section_collapsed(section) {
+                    "▸"
+                } else {
+                    "▾"
+                };
+                let marker = if is_selected { "› " } else { "  " };
+                let header_style = if is_selected {
+                    Style::default().fg(primary()).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(muted()).add_modifier(Modifier::BOLD)
+                };
+
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled("  ", Style::default()),
+                    Span::styled(marker, Style::default().fg(primary())),
+                    Span::styled(format!("{} {}", glyph, section), header_style),
+                ])));
+                continue;
+            }
+            ListRow::Field(idx) => &app.fields[idx],
+        };
+
+        let full_value = shown_value(app, field);
+        let suffix = value_suffix(field);
+        let tag = default_tag(field);
+        let display_value = truncate_value(
+            &full_value,
+            value_width(area.width).saturating_sub(suffix.chars().count() + tag.chars().count()),
+        );
 
         let key_style = if is_selected {
             Style::default().fg(primary()).add_modifier(Modifier::BOLD)
@@ -247,11 +466,6 @@ fn render_fields(frame: &mut ratatui::Frame, area: Rect, app: &App) {
         };
 
         let marker = if is_selected { "› " } else { "  " };
-        let suffix = if has_options && field.options.len() > 2 {
-            " ▾"
-        } else {
-            ""
-        };
 
         let line = Line::from(vec![
             Span::styled("  ", Style::default()),
@@ -266,37 +480,112 @@ fn render_fields(frame: &mut ratatui::Frame, area: Rect, app: &App) {
                     }),
             ),
             Span::styled(
-                format!("{:<width$}", field.key, width = key_width),
+                if field.is_modified() { "● " } else { "  " },
+                Style::default().fg(accent()),
+            ),
+            Span::styled(
+                format!("{:<width$}", field.key, width = KEY_WIDTH),
                 key_style,
             ),
             Span::styled(format!("{}{}", display_value, suffix), value_style),
+            Span::styled(tag, Style::default().fg(muted())),
         ]);
 
         items.push(ListItem::new(line));
-        flat += 1;
+
+        if is_selected {
+            for part in selected_value_detail(app, area.width) {
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled(" ".repeat(KEY_COLUMN), Style::default()),
+                    Span::styled(part, Style::default().fg(muted())),
+                ])));
+            }
+        }
     }
 
     let mut state = ListState::default();
     state.select(selected_flat);
 
-    let list = List::new(items).highlight_style(Style::default());
+    // Only the cursor row is ever selected, so spacer rows stay unhighlighted.
+    let list = List::new(items).highlight_style(Style::default().bg(panel()));
     frame.render_stateful_widget(list, area, &mut state);
 }
 
-fn render_footer(frame: &mut ratatui::Frame, area: Rect, mode: Mode) {
-    let actions = footer_copy(mode);
+/// Shows why saving is disabled, else the latest notice or the first load
+/// warning, above the footer.
+fn render_warning(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let Some(warning) = app
+        .read_error
+        .as_ref()
+        .or(app.notice.as_ref())
+        .or(app.warnings.first())
+    else {
+        return;
+    };
+
+    let line = Line::from(vec![
+        Span::styled(
+            "  ! ",
+            Style::default().fg(red()).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(warning.as_str(), Style::default().fg(red())),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn render_footer(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let actions = footer_copy(app.mode);
     let label_style = if area.width >= 52 {
         FooterLabelStyle::Long
     } else {
         FooterLabelStyle::Short
     };
 
+    let line = build_footer_line(actions, label_style, area.width);
+    let used_width = line.width();
+    frame.render_widget(Paragraph::new(line), area);
+
+    // Right-aligned save state, dropped when it would overlap the actions.
+    let Some((status, dirty)) = footer_status(app.dirty, app.saved_flash, app.copied_flash) else {
+        return;
+    };
+    let status_width = status.chars().count() + 2;
+    if used_width + 2 + status_width > area.width as usize {
+        return;
+    }
+    let style = if dirty {
+        Style::default().fg(accent())
+    } else {
+        Style::default().fg(muted())
+    };
     frame.render_widget(
-        Paragraph::new(build_footer_line(actions, label_style, area.width)),
+        Paragraph::new(Line::from(vec![
+            Span::styled(status, style),
+            Span::styled("  ", Style::default()),
+        ]))
+        .alignment(Alignment::Right),
         area,
     );
 }
 
+/// Save state shown at the right of the footer, and whether it is unsaved.
+/// A copy confirmation only lasts until the next key, so it wins.
+fn footer_status(
+    dirty: bool,
+    saved_flash: bool,
+    copied_flash: bool,
+) -> Option<(&'static str, bool)> {
+    if copied_flash {
+        Some(("Copied", false))
+    } else if dirty {
+        Some(("● Unsaved", true))
+    } else if saved_flash {
+        Some(("Saved", false))
+    } else {
+        None
+    }
+}
+
 fn build_footer_line(
     actions: &[FooterAction],
     label_style: FooterLabelStyle,
@@ -366,12 +655,7 @@ fn render_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
         longest_option_width.saturating_add(10).min(max_popup_width),
     );
     let popup_height = (option_count + 2).min(area.height.saturating_sub(4));
-    let popup = Rect::new(
-        (area.width.saturating_sub(popup_width)) / 2,
-        (area.height.saturating_sub(popup_height)) / 2,
-        popup_width,
-        popup_height,
-    );
+    let popup = centered_popup(area, popup_width, popup_height);
 
     frame.render_widget(Clear, popup);
 
@@ -392,6 +676,7 @@ fn render_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     let inner = block.inner(popup);
     frame.render_widget(block, popup);
 
+    let current = app.display_value(field);
     let items: Vec<ListItem> = field
         .options
         .iter()
@@ -415,6 +700,10 @@ fn render_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
                             Modifier::empty()
                         }),
                 ),
+                Span::styled(
+                    current_value_mark(opt, current),
+                    Style::default().fg(success()),
+                ),
                 Span::styled(*opt, style),
             ]))
         })
@@ -423,10 +712,117 @@ fn render_selector(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     let mut state = ListState::default();
     state.select(Some(select_index));
 
-    let list = List::new(items).highlight_style(Style::default());
+    // The popup itself is drawn on panel(), so the row needs a stronger tint.
+    let list = List::new(items).highlight_style(Style::default().bg(selection()));
     frame.render_stateful_widget(list, inner, &mut state);
 }
 
+/// Marks the option that is set now, apart from the `›` cursor, so moving
+/// the cursor never hides which value is in effect.
+fn current_value_mark(option: &str, current: &str) -> &'static str {
+    if option == current {
+        "✓ "
+    } else {
+        "  "
+    }
+}
+
+fn render_preview(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let Some(changes) = app.preview_view() else {
+        return;
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    if changes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No pending changes",
+            Style::default().fg(muted()),
+        )));
+    }
+    for change in changes {
+        lines.push(Line::from(Span::styled(
+            change.key,
+            Style::default().fg(text_fg()).add_modifier(Modifier::BOLD),
+        )));
+        if let Some(before) = &change.before {
+            for line in before.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("- {}", line),
+                    Style::default().fg(red()),
+                )));
+            }
+        }
+        if let Some(after) = &change.after {
+            for line in after.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("+ {}", line),
+                    Style::default().fg(success()),
+                )));
+            }
+        }
+    }
+
+    let popup_width = ((area.width as f32 * 0.8) as u16).min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let popup = centered_popup(area, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(" Pending Changes", Style::default().fg(primary())),
+            Span::styled("  ", Style::default()),
+            Span::styled("Enter", Style::default().fg(primary())),
+            Span::styled(": Save & Exit  ", Style::default().fg(muted())),
+            Span::styled("Esc", Style::default().fg(primary())),
+            Span::styled(": Back ", Style::default().fg(muted())),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(primary()))
+        .style(Style::default().bg(panel()));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+    frame.render_widget(Paragraph::new(lines), inner.inner(Margin::new(1, 0)));
+}
+
+fn render_overwrite_prompt(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let field = &app.fields[app.selected];
+    let message = format!(
+        "This will replace your custom config for {}. Continue?",
+        field.key
+    );
+
+    let popup_width = ((area.width as f32 * 0.7) as u16).min(area.width.saturating_sub(4));
+    let popup_height = 3u16.min(area.height.saturating_sub(4));
+    let popup = centered_popup(area, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(" Replace Custom Value", Style::default().fg(primary())),
+            Span::styled("  ", Style::default()),
+            Span::styled("Enter", Style::default().fg(primary())),
+            Span::styled(": Replace  ", Style::default().fg(muted())),
+            Span::styled("Esc", Style::default().fg(primary())),
+            Span::styled(": Keep ", Style::default().fg(muted())),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent()))
+        .style(Style::default().bg(panel()));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            message,
+            Style::default().fg(text_fg()),
+        ))),
+        inner.inner(Margin::new(1, 0)),
+    );
+}
+
 fn render_editor(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     let Some((field, edit_buf, edit_cursor)) = app.editing_view() else {
         return;
@@ -434,12 +830,7 @@ fn render_editor(frame: &mut ratatui::Frame, area: Rect, app: &App) {
 
     let popup_width = ((area.width as f32 * 0.7) as u16).min(area.width.saturating_sub(4));
     let popup_height = 5u16.min(area.height.saturating_sub(4));
-    let popup = Rect::new(
-        (area.width.saturating_sub(popup_width)) / 2,
-        (area.height.saturating_sub(popup_height)) / 2,
-        popup_width,
-        popup_height,
-    );
+    let popup = centered_popup(area, popup_width, popup_height);
 
     frame.render_widget(Clear, popup);
 
@@ -499,13 +890,101 @@ fn render_editor(frame: &mut ratatui::Frame, area: Rect, app: &App) {
     frame.render_widget(input, content_area);
 }
 
+/// Multi-line editor for table-valued fields. Lines scroll so the cursor
+/// stays visible when the table is taller than the popup.
+fn render_table_editor(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let Some((field, edit_buf, edit_cursor)) = app.editing_view() else {
+        return;
+    };
+
+    let buffer_lines: Vec<&str> = edit_buf.split('\n').collect();
+    let popup_width = ((area.width as f32 * 0.7) as u16).min(area.width.saturating_sub(4));
+    let popup_height = (buffer_lines.len() as u16 + 2)
+        .max(5)
+        .min(area.height.saturating_sub(4));
+    let popup = centered_popup(area, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(" Edit Table: ", Style::default().fg(primary())),
+            Span::styled(field.key, Style::default().fg(text_fg())),
+            Span::styled("  ", Style::default()),
+            Span::styled("Ctrl+S", Style::default().fg(primary())),
+            Span::styled(": Save  ", Style::default().fg(muted())),
+            Span::styled("Esc", Style::default().fg(primary())),
+            Span::styled(": Cancel ", Style::default().fg(muted())),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(primary()))
+        .style(Style::default().bg(panel()));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let content_area = inner.inner(Margin::new(1, 0));
+
+    // Find the cursor's line; a cursor at a line end stays on that line.
+    let mut cursor_line = 0;
+    let mut line_start = 0;
+    while cursor_line + 1 < buffer_lines.len()
+        && line_start + buffer_lines[cursor_line].chars().count() < edit_cursor
+    {
+        line_start += buffer_lines[cursor_line].chars().count() + 1;
+        cursor_line += 1;
+    }
+
+    let lines: Vec<Line> = buffer_lines
+        .iter()
+        .enumerate()
+        .map(|(idx, text)| {
+            if idx == cursor_line {
+                cursor_line_spans(text, edit_cursor - line_start)
+            } else {
+                Line::from(Span::styled(
+                    text.to_string(),
+                    Style::default().fg(text_fg()),
+                ))
+            }
+        })
+        .collect();
+
+    let visible = content_area.height as usize;
+    let scroll = (cursor_line + 1).saturating_sub(visible) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), content_area);
+}
+
+/// Renders one editor line with a block cursor at char `column`.
+fn cursor_line_spans(text: &str, column: usize) -> Line<'static> {
+    let byte_pos = text
+        .char_indices()
+        .nth(column)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let before = text[..byte_pos].to_string();
+    let mut after = text[byte_pos..].chars();
+    let current_char = after.next().unwrap_or(' ');
+    Line::from(vec![
+        Span::styled(before, Style::default().fg(text_fg())),
+        Span::styled(
+            current_char.to_string(),
+            Style::default().bg(primary()).fg(bg()),
+        ),
+        Span::styled(after.as_str().to_string(), Style::default().fg(text_fg())),
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        build_footer_line, footer_copy, resolve_main_layout, FooterAction, FooterLabelStyle,
-        MainLayoutMode, NORMAL_FOOTER_ACTIONS,
+        build_footer_line, centered_popup, current_value_mark, default_tag, footer_copy,
+        footer_status, is_too_small, resolve_main_layout, selected_value_detail, shown_value,
+        truncate_value, FooterAction, FooterLabelStyle, MainLayoutMode, NORMAL_FOOTER_ACTIONS,
     };
-    use crate::config_tui::Mode;
+    use crate::config_tui::{App, Mode};
+    use ratatui::layout::Rect;
+    use std::path::PathBuf;
 
     #[test]
     fn keeps_spacer_in_compact_layout() {
@@ -524,6 +1003,23 @@ mod tests {
         assert_eq!(resolve_main_layout(3, 1), MainLayoutMode::HeaderAndFooter);
     }
 
+    #[test]
+    fn reports_too_small_below_minimum_size() {
+        assert!(is_too_small(Rect::new(0, 0, 29, 40)));
+        assert!(is_too_small(Rect::new(0, 0, 80, 9)));
+        assert!(!is_too_small(Rect::new(0, 0, 30, 10)));
+    }
+
+    #[test]
+    fn centered_popup_stays_inside_area() {
+        let area = Rect::new(2, 3, 20, 6);
+        let popup = centered_popup(area, 40, 10);
+        assert_eq!(popup, area);
+
+        let popup = centered_popup(area, 10, 2);
+        assert_eq!(popup, Rect::new(7, 5, 10, 2));
+    }
+
     #[test]
     fn normal_footer_keeps_escape_as_apply_shortcut() {
         assert_eq!(footer_copy(Mode::Normal), &NORMAL_FOOTER_ACTIONS);
@@ -557,8 +1053,107 @@ mod tests {
     #[test]
     fn normal_footer_matches_ai_style_with_separators() {
         assert_eq!(
-            footer_text(Mode::Normal, 90),
-            "  ↑↓ Navigate | Enter Edit | Esc Save & Exit | Q Discard | E Open File"
+            footer_text(Mode::Normal, 100),
+            "  ↑↓ Navigate | Enter Edit | Esc Save & Exit | Ctrl+S Save | Q Discard | D Diff | E Open File"
+        );
+    }
+
+    #[test]
+    fn editing_footer_lists_clear_shortcut() {
+        assert_eq!(
+            footer_text(Mode::Editing, 80),
+            "  Enter Apply | Esc Cancel | Ctrl+U Clear"
+        );
+    }
+
+    #[test]
+    fn table_editor_footer_keeps_enter_for_new_lines() {
+        assert_eq!(
+            footer_text(Mode::EditingTable, 80),
+            "  Ctrl+S Apply | Enter New Line | Esc Cancel"
+        );
+    }
+
+    #[test]
+    fn previewing_footer_offers_save_and_back() {
+        assert_eq!(
+            footer_text(Mode::Previewing, 80),
+            "  Enter Save & Exit | Esc Back"
+        );
+    }
+
+    #[test]
+    fn footer_status_prefers_unsaved_over_saved_flash() {
+        assert_eq!(footer_status(false, false, false), None);
+        assert_eq!(footer_status(false, true, false), Some(("Saved", false)));
+        assert_eq!(footer_status(true, true, false), Some(("● Unsaved", true)));
+    }
+
+    #[test]
+    fn selector_marks_only_the_current_value() {
+        assert_eq!(current_value_mark("Kaku Dark", "Kaku Dark"), "✓ ");
+        assert_eq!(current_value_mark("Kaku Light", "Kaku Dark"), "  ");
+    }
+
+    #[test]
+    fn footer_status_shows_copy_confirmation() {
+        assert_eq!(footer_status(true, false, true), Some(("Copied", false)));
+    }
+
+    #[test]
+    fn long_values_are_truncated_with_ellipsis() {
+        assert_eq!(truncate_value("JetBrains Mono", 20), "JetBrains Mono");
+        assert_eq!(truncate_value("JetBrains Mono", 8), "JetBrai…");
+        assert_eq!(truncate_value("JetBrains Mono", 0), "");
+    }
+
+    #[test]
+    fn only_inherited_values_are_tagged_as_default() {
+        let mut app = App::new(PathBuf::from("/tmp/kaku-config-tui-test.lua"));
+        let field = &mut app.fields[0];
+        assert_eq!(default_tag(field), " (default)");
+
+        // A value written to the file counts as user-set even if it matches.
+        field.value = field.default.clone();
+        assert_eq!(default_tag(field), "");
+    }
+
+    #[test]
+    fn unparsed_values_are_shown_and_tagged_as_custom() {
+        let mut app = App::new(PathBuf::from("/tmp/kaku-config-tui-test.lua"));
+        app.fields[0].skip_write = true;
+        app.fields[0].raw_value = Some("base_size + 2".into());
+        let field = &app.fields[0];
+        assert_eq!(shown_value(&app, field), "base_size + 2");
+        assert_eq!(default_tag(field), " ⚠ custom (not editable here)");
+
+        // Editing the field clears skip_write, so the new value shows instead.
+        app.fields[0].skip_write = false;
+        app.fields[0].value = app.fields[0].default.clone();
+        let field = &app.fields[0];
+        assert_eq!(shown_value(&app, field), field.default);
+        assert_eq!(default_tag(field), "");
+    }
+
+    #[test]
+    fn selected_truncated_value_expands_below_its_row() {
+        let mut app = App::new(PathBuf::from("/tmp/kaku-config-tui-test.lua"));
+        let idx = app
+            .fields
+            .iter()
+            .position(|f| f.lua_key == "font")
+            .expect("font field to exist");
+        app.selected = idx;
+        app.fields[idx].value = "A Very Long Font Family Name".into();
+
+        assert!(selected_value_detail(&app, 80).is_empty());
+        assert_eq!(
+            selected_value_detail(&app, 40),
+            vec!["A Very Long Font Family Name".to_string()]
+        );
+        assert_eq!(
+            selected_value_detail(&app, 30),
+            vec!["A Very Long Font Family ".to_string(), "Name".to_string()]
         );
     }
 
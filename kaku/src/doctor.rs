@@ -1,6 +1,8 @@
-//! Doctor command for diagnosing shell integration, environment, and runtime issues.
+//! Doctor command for diagnosing shell integration, environment, runtime, and config issues.
 
+use anyhow::{bail, Context};
 use clap::Parser;
+use serde::Serialize;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{self, ErrorKind, IsTerminal, Write};
@@ -13,27 +15,40 @@ pub struct DoctorCommand {
     /// Apply safe automatic fixes, then rerun diagnostics
     #[arg(long)]
     pub fix: bool,
+
+    /// Print the report as JSON instead of text
+    #[arg(long, conflicts_with = "fix")]
+    pub json: bool,
 }
 
 impl DoctorCommand {
     pub fn run(&self) -> anyhow::Result<()> {
-        let report = build_report();
-        print!("{}", render_text_report(&report));
-
-        if self.fix {
-            run_auto_fix_and_rerun_report();
-            return Ok(());
-        }
+        let mut report = build_report();
 
-        if should_offer_auto_fix(&report) && io::stdin().is_terminal() && io::stdout().is_terminal()
-        {
-            match prompt_yes_no("Run safe auto-fix now with `kaku init --update-only`? [Y/n] ") {
-                Ok(true) => run_auto_fix_and_rerun_report(),
-                Ok(false) => {}
-                Err(err) => eprintln!("Auto-fix prompt skipped: {}", err),
+        if self.json {
+            let json = serde_json::to_string_pretty(&report).context("serialize doctor report")?;
+            println!("{}", json);
+        } else {
+            print!("{}", render_text_report(&report));
+
+            if self.fix {
+                report = run_auto_fix_and_rerun_report();
+            } else if should_offer_auto_fix(&report)
+                && io::stdin().is_terminal()
+                && io::stdout().is_terminal()
+            {
+                match prompt_yes_no("Run safe auto-fix now with `kaku init --update-only`? [Y/n] ")
+                {
+                    Ok(true) => report = run_auto_fix_and_rerun_report(),
+                    Ok(false) => {}
+                    Err(err) => eprintln!("Auto-fix prompt skipped: {}", err),
+                }
             }
         }
 
+        if report.overall_status == DoctorStatus::Fail {
+            bail!("kaku doctor found {} failing check(s)", report.summary.fail);
+        }
         Ok(())
     }
 }
@@ -55,7 +70,7 @@ fn prompt_yes_no(question: &str) -> anyhow::Result<bool> {
     Ok(answer.is_empty() || answer == "y" || answer == "yes")
 }
 
-fn run_auto_fix_and_rerun_report() {
+fn run_auto_fix_and_rerun_report() -> DoctorReport {
     println!("Auto-fix: running `kaku init --update-only`");
     let init_cmd = crate::init::InitCommand { update_only: true };
     match init_cmd.run() {
@@ -67,9 +82,11 @@ fn run_auto_fix_and_rerun_report() {
     println!();
     println!("After Auto-fix");
     print!("{}", render_text_report(&after));
+    after
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 enum DoctorStatus {
     Ok,
     Warn,
@@ -106,14 +123,14 @@ impl DoctorStatus {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct DoctorReport {
     overall_status: DoctorStatus,
     summary: DoctorSummary,
     groups: Vec<DoctorGroup>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct DoctorSummary {
     ok: usize,
     warn: usize,
@@ -121,14 +138,14 @@ struct DoctorSummary {
     info: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct DoctorGroup {
     title: &'static str,
     status: DoctorStatus,
     checks: Vec<DoctorCheck>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 struct DoctorCheck {
     title: &'static str,
     status: DoctorStatus,
@@ -141,11 +158,13 @@ fn build_report() -> DoctorReport {
     let env_group = build_environment_group();
     let shell_group = build_shell_integration_group();
     let runtime_group = build_runtime_group();
+    let config_group = build_configuration_group();
 
     let mut all_checks = Vec::new();
     all_checks.extend(env_group.checks.iter());
     all_checks.extend(shell_group.checks.iter());
     all_checks.extend(runtime_group.checks.iter());
+    all_checks.extend(config_group.checks.iter());
 
     let summary = DoctorSummary {
         ok: all_checks
@@ -179,7 +198,13 @@ fn build_report() -> DoctorReport {
     DoctorReport {
         overall_status,
         summary,
-        groups: vec![health_group, env_group, shell_group, runtime_group],
+        groups: vec![
+            health_group,
+            env_group,
+            shell_group,
+            runtime_group,
+            config_group,
+        ],
     }
 }
 
@@ -202,7 +227,7 @@ fn build_health_group(overall_status: DoctorStatus, summary: &DoctorSummary) ->
         summary: match overall_status {
             DoctorStatus::Ok => "No blocking issues detected".to_string(),
             DoctorStatus::Warn => "Kaku works but setup is incomplete".to_string(),
-            DoctorStatus::Fail => "Kaku command entry is broken or missing".to_string(),
+            DoctorStatus::Fail => "Kaku command entry or config is broken".to_string(),
             DoctorStatus::Info => "Informational only".to_string(),
         },
         details,
@@ -476,6 +501,135 @@ fn build_runtime_group() -> DoctorGroup {
     }
 }
 
+fn build_configuration_group() -> DoctorGroup {
+    let loaded = config::Config::load();
+    let config_check = kaku_config_check(
+        loaded
+            .config
+            .as_ref()
+            .map(|_| ())
+            .map_err(|err| format!("{:#}", err)),
+        loaded.file_name.as_deref(),
+        &loaded.warnings,
+    );
+
+    let assistant_check = match crate::assistant_config::assistant_toml_path() {
+        Ok(path) => {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => Some(Ok(content)),
+                Err(err) if err.kind() == ErrorKind::NotFound => None,
+                Err(err) => Some(Err(err.to_string())),
+            };
+            assistant_config_check(&path, content)
+        }
+        Err(err) => DoctorCheck {
+            title: "Assistant Config",
+            status: DoctorStatus::Warn,
+            summary: format!("Could not locate assistant.toml: {:#}", err),
+            details: Vec::new(),
+            fix: None,
+        },
+    };
+
+    let checks = vec![config_check, assistant_check];
+    DoctorGroup {
+        title: "Configuration",
+        status: group_status(&checks),
+        checks,
+    }
+}
+
+fn kaku_config_check(
+    result: Result<(), String>,
+    file_name: Option<&Path>,
+    warnings: &[String],
+) -> DoctorCheck {
+    let title = "Kaku Config";
+    let file_label = file_name
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "kaku.lua".to_string());
+
+    match result {
+        Err(err) => DoctorCheck {
+            title,
+            status: DoctorStatus::Fail,
+            summary: format!("Failed to load {}", file_label),
+            details: err.lines().map(str::to_string).collect(),
+            fix: Some(format!(
+                "Fix the error in {} then run `kaku doctor` again",
+                file_label
+            )),
+        },
+        Ok(()) if file_name.is_none() => DoctorCheck {
+            title,
+            status: DoctorStatus::Info,
+            summary: "No config file found; using built-in defaults".to_string(),
+            details: Vec::new(),
+            fix: None,
+        },
+        Ok(()) if !warnings.is_empty() => DoctorCheck {
+            title,
+            status: DoctorStatus::Warn,
+            summary: format!("Loaded {} with {} warning(s)", file_label, warnings.len()),
+            details: warnings.to_vec(),
+            fix: None,
+        },
+        Ok(()) => DoctorCheck {
+            title,
+            status: DoctorStatus::Ok,
+            summary: format!("Loaded {}", file_label),
+            details: Vec::new(),
+            fix: None,
+        },
+    }
+}
+
+fn assistant_config_check(path: &Path, content: Option<Result<String, String>>) -> DoctorCheck {
+    let title = "Assistant Config";
+    match content {
+        None => DoctorCheck {
+            title,
+            status: DoctorStatus::Info,
+            summary: format!("No assistant config at {}", path.display()),
+            details: vec![
+                "Kaku creates it with defaults the first time `kaku ai` runs".to_string(),
+            ],
+            fix: None,
+        },
+        Some(Err(err)) => DoctorCheck {
+            title,
+            status: DoctorStatus::Warn,
+            summary: format!("Could not read {}: {}", path.display(), err),
+            details: Vec::new(),
+            fix: None,
+        },
+        Some(Ok(content)) => {
+            let missing = crate::assistant_config::missing_required_keys(&content);
+            if missing.is_empty() {
+                DoctorCheck {
+                    title,
+                    status: DoctorStatus::Ok,
+                    summary: format!("{} has the required keys", path.display()),
+                    details: Vec::new(),
+                    fix: None,
+                }
+            } else {
+                DoctorCheck {
+                    title,
+                    status: DoctorStatus::Warn,
+                    summary: format!(
+                        "{} is missing required key(s): {}",
+                        path.display(),
+                        missing.join(", ")
+                    ),
+                    details: Vec::new(),
+                    fix: Some("Run `kaku ai` to restore the default keys".to_string()),
+                }
+            }
+        }
+    }
+}
+
 fn group_status(checks: &[DoctorCheck]) -> DoctorStatus {
     checks
         .iter()
@@ -877,6 +1031,42 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[test]
+    fn config_load_error_fails_the_check() {
+        let check = kaku_config_check(
+            Err("syntax error near 'end'".to_string()),
+            Some(Path::new("/tmp/kaku.lua")),
+            &[],
+        );
+        assert_eq!(check.status, DoctorStatus::Fail);
+        assert_eq!(check.details, vec!["syntax error near 'end'".to_string()]);
+
+        let check = kaku_config_check(Ok(()), Some(Path::new("/tmp/kaku.lua")), &[]);
+        assert_eq!(check.status, DoctorStatus::Ok);
+    }
+
+    #[test]
+    fn assistant_config_reports_missing_required_keys() {
+        let path = Path::new("/tmp/assistant.toml");
+        let check = assistant_config_check(
+            path,
+            Some(Ok("enabled = true\nmodel = \"x\"\n".to_string())),
+        );
+        assert_eq!(check.status, DoctorStatus::Warn);
+        assert!(check.summary.ends_with("base_url"));
+
+        let check = assistant_config_check(path, None);
+        assert_eq!(check.status, DoctorStatus::Info);
+    }
+
+    #[test]
+    fn json_report_uses_lowercase_status() {
+        let check = kaku_config_check(Ok(()), None, &[]);
+        let value = serde_json::to_value(&check).expect("serialize check");
+        assert_eq!(value["status"], "info");
+        assert_eq!(value["title"], "Kaku Config");
+    }
+
     #[test]
     fn missing_zshrc_is_not_read_error() {
         let dir = tempfile::tempdir().expect("create tempdir");
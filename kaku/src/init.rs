@@ -33,7 +33,10 @@ mod imp {
     use std::os::unix::fs::PermissionsExt;
 
     pub fn run(update_only: bool) -> anyhow::Result<()> {
-        ensure_user_config().context("ensure user config exists")?;
+        let config_path = ensure_user_config().context("ensure user config exists")?;
+        if !update_only {
+            crate::config_migrate::offer_config_migrations(&config_path)?;
+        }
 
         install_kaku_wrapper().context("install kaku wrapper")?;
 
@@ -50,12 +53,55 @@ mod imp {
             .with_context(|| format!("run {}", script.display()))?;
 
         if status.success() {
+            record_init_version();
             return Ok(());
         }
 
         bail!("kaku init failed with status {}", status);
     }
 
+    /// Remembers which Kaku version last initialized the shell and prints a
+    /// note when it differs, so users can tell an app update is why init ran.
+    /// A missing or unwritable marker never fails init.
+    fn record_init_version() {
+        let path = init_version_path();
+        let current = config::wezterm_version();
+        let previous = fs::read_to_string(&path).ok();
+        let previous = previous.as_deref().map(str::trim);
+
+        if let Some(note) = init_version_note(previous, current) {
+            println!("{}", note);
+        }
+        if previous != Some(current) {
+            if let Err(err) = fs::write(&path, format!("{}\n", current)) {
+                log::warn!(
+                    "failed to record init version in {}: {}",
+                    path.display(),
+                    err
+                );
+            }
+        }
+    }
+
+    fn init_version_note(previous: Option<&str>, current: &str) -> Option<String> {
+        let display = |version: &str| version.trim_start_matches(['v', 'V']).to_string();
+        match previous {
+            Some(previous) if !previous.is_empty() && previous != current => Some(format!(
+                "Kaku shell integration updated for v{} (previously v{}).",
+                display(current),
+                display(previous)
+            )),
+            _ => None,
+        }
+    }
+
+    fn init_version_path() -> PathBuf {
+        config::HOME_DIR
+            .join(".config")
+            .join("kaku")
+            .join(".kaku_init_version")
+    }
+
     fn install_kaku_wrapper() -> anyhow::Result<()> {
         let wrapper_path = wrapper_path();
         let wrapper_dir = wrapper_path
@@ -199,8 +245,23 @@ exit 127
         candidates.into_iter().find(|p| p.exists())
     }
 
-    fn ensure_user_config() -> anyhow::Result<()> {
-        config::ensure_user_config_exists().context("ensure user config exists")?;
-        Ok(())
+    fn ensure_user_config() -> anyhow::Result<PathBuf> {
+        config::ensure_user_config_exists().context("ensure user config exists")
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::init_version_note;
+
+        #[test]
+        fn version_note_only_on_change() {
+            assert_eq!(init_version_note(None, "0.7.1"), None);
+            assert_eq!(init_version_note(Some(""), "0.7.1"), None);
+            assert_eq!(init_version_note(Some("0.7.1"), "0.7.1"), None);
+            assert_eq!(
+                init_version_note(Some("v0.7.0"), "0.7.1").as_deref(),
+                Some("Kaku shell integration updated for v0.7.1 (previously v0.7.0).")
+            );
+        }
     }
 }
@@ -1,9 +1,10 @@
+use crate::utils::file_modified_time;
 use config::{configuration, ConfigHandle};
 use std::sync::Mutex;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 use wezterm_term::color::{ColorPalette, SrgbaTuple};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct ThemePalette {
     pub primary: SrgbaTuple,
     pub secondary: SrgbaTuple,
@@ -27,17 +28,38 @@ static THEME_CACHE: Mutex<Option<(usize, CachedTheme)>> = Mutex::new(None);
 static APPEARANCE_CACHE: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
 #[cfg(target_os = "macos")]
 const APPEARANCE_CACHE_TTL: Duration = Duration::from_secs(1);
-
-fn rgb(hex: &str) -> SrgbaTuple {
-    let hex = hex.trim_start_matches('#');
-    let parse = |range| {
-        hex.get(range)
-            .and_then(|s| u8::from_str_radix(s, 16).ok())
-            .unwrap_or(0) as f32
-            / 255.0
+static CONFIG_MTIME_CACHE: Mutex<Option<(Instant, Option<SystemTime>)>> = Mutex::new(None);
+const CONFIG_MTIME_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Color scheme read straight from the config file after it changed on disk.
+static FILE_COLOR_SCHEME: Mutex<Option<ColorSchemeSelection>> = Mutex::new(None);
+
+/// Parses `#RGB`, `#RRGGBB` or `#RRGGBBAA` (the `#` is optional).
+/// The alpha byte is kept on the tuple, but ratatui's `Color` has no alpha
+/// channel, so it is dropped once the color reaches the TUI.
+fn try_rgb(hex: &str) -> Result<SrgbaTuple, String> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("{hex:?} contains non-hex characters"));
+    }
+    let expanded = match digits.len() {
+        6 | 8 => digits.to_string(),
+        3 => digits.chars().flat_map(|c| [c, c]).collect(),
+        _ => return Err(format!("{hex:?} is not a 3, 6 or 8 digit hex color")),
+    };
+    let parse = |range| u8::from_str_radix(&expanded[range], 16).unwrap_or(0) as f32 / 255.0;
+    let alpha = if expanded.len() == 8 {
+        parse(6..8)
+    } else {
+        1.0
     };
 
-    SrgbaTuple(parse(0..2), parse(2..4), parse(4..6), 1.0)
+    Ok(SrgbaTuple(parse(0..2), parse(2..4), parse(4..6), alpha))
+}
+
+/// Built-in palette colors are literals, so a typo is a bug in Kaku: fail
+/// loudly rather than quietly rendering it as black.
+fn rgb(hex: &str) -> SrgbaTuple {
+    try_rgb(hex).unwrap_or_else(|err| panic!("invalid built-in theme color: {err}"))
 }
 
 fn opaque(color: SrgbaTuple) -> SrgbaTuple {
@@ -184,7 +206,36 @@ fn appearance_sensitive_theme(palette: ThemePalette, is_dark: bool) -> CachedThe
     }
 }
 
+/// Theme for a color scheme parsed from the config file. `Other` needs the
+/// Lua config to resolve its palette, so it yields None.
+fn theme_for_file_selection(
+    selection: ColorSchemeSelection,
+    is_dark: impl FnOnce() -> bool,
+) -> Option<CachedTheme> {
+    match selection {
+        ColorSchemeSelection::Dark => Some(cached_theme(dark_palette())),
+        ColorSchemeSelection::Light => Some(cached_theme(light_palette())),
+        ColorSchemeSelection::Auto => {
+            let is_dark = is_dark();
+            let palette = if is_dark {
+                dark_palette()
+            } else {
+                light_palette()
+            };
+            Some(appearance_sensitive_theme(palette, is_dark))
+        }
+        ColorSchemeSelection::Other => None,
+    }
+}
+
 fn builtin_kaku_theme(config: &ConfigHandle) -> Option<CachedTheme> {
+    let file_selection = *FILE_COLOR_SCHEME.lock().unwrap();
+    if let Some(theme) =
+        file_selection.and_then(|s| theme_for_file_selection(s, is_macos_dark_mode))
+    {
+        return Some(theme);
+    }
+
     let dark = dark_palette();
     let light = light_palette();
     let dark_terminal_text = rgb("#EDECEE");
@@ -389,7 +440,53 @@ fn is_current_theme_cache_hit(
     theme.appearance_is_dark == current_appearance_is_dark
 }
 
+/// The first observation only records the mtime; later ones report whether
+/// the config file was modified, created or removed since.
+fn config_file_changed(previous: Option<Option<SystemTime>>, current: Option<SystemTime>) -> bool {
+    matches!(previous, Some(previous) if previous != current)
+}
+
+/// Re-reads the color scheme when the config file changed on disk, so a
+/// long-lived process picks up theme edits made outside of it without
+/// re-running the Lua config. The file is stat'ed at most once per
+/// `CONFIG_MTIME_CHECK_INTERVAL`.
+fn refresh_color_scheme_if_file_changed() {
+    let now = Instant::now();
+    let mut cache = CONFIG_MTIME_CACHE.lock().unwrap();
+    if let Some((checked_at, _)) = *cache {
+        if now.duration_since(checked_at) < CONFIG_MTIME_CHECK_INTERVAL {
+            return;
+        }
+    }
+
+    let path = config::effective_config_file_path();
+    let mtime = file_modified_time(&path);
+    let changed = config_file_changed(cache.map(|(_, previous)| previous), mtime);
+    *cache = Some((now, mtime));
+    drop(cache);
+
+    if changed {
+        let selection = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| color_scheme_selection_from_content(&content))
+            .unwrap_or(ColorSchemeSelection::Other);
+        *FILE_COLOR_SCHEME.lock().unwrap() = Some(selection);
+        THEME_CACHE.lock().unwrap().take();
+    }
+}
+
+/// Lets the next `current_theme` call stat the config file right away.
+#[cfg(test)]
+pub(crate) fn expire_config_file_check() {
+    if let Some((checked_at, _)) = CONFIG_MTIME_CACHE.lock().unwrap().as_mut() {
+        *checked_at = checked_at
+            .checked_sub(CONFIG_MTIME_CHECK_INTERVAL)
+            .unwrap_or(*checked_at);
+    }
+}
+
 fn current_theme() -> CachedTheme {
+    refresh_color_scheme_if_file_changed();
     let config = configuration();
     let generation = config.generation();
 
@@ -423,9 +520,10 @@ pub fn current_theme_palette() -> ThemePalette {
 mod tests {
     use super::{
         appearance_sensitive_theme, cached_theme, color_distance,
-        color_scheme_selection_from_content, dark_palette, has_enough_separation,
-        is_current_theme_cache_hit, luminance, parse_color_scheme_selection_line, pick_visible,
-        rgb, ColorSchemeSelection,
+        color_scheme_selection_from_content, config_file_changed, dark_palette,
+        has_enough_separation, is_current_theme_cache_hit, luminance,
+        parse_color_scheme_selection_line, pick_visible, rgb, theme_for_file_selection, try_rgb,
+        ColorSchemeSelection,
     };
 
     #[test]
@@ -571,4 +669,79 @@ config.color_scheme = some_runtime_value
         assert!(has_enough_separation(bg, adjusted));
         assert!(luminance(adjusted) > luminance(fallback));
     }
+
+    #[test]
+    fn try_rgb_expands_three_digit_hex() {
+        assert_eq!(try_rgb("#fa0"), try_rgb("#ffaa00"));
+        assert_eq!(try_rgb("#FFF"), Ok(rgb("#FFFFFF")));
+    }
+
+    #[test]
+    fn try_rgb_parses_alpha_byte() {
+        use wezterm_term::color::SrgbaTuple;
+
+        assert_eq!(try_rgb("#ffffffff"), Ok(rgb("#fff")));
+        assert_eq!(
+            try_rgb("#15141B80"),
+            Ok(SrgbaTuple(
+                0x15 as f32 / 255.0,
+                0x14 as f32 / 255.0,
+                0x1B as f32 / 255.0,
+                0x80 as f32 / 255.0
+            ))
+        );
+    }
+
+    #[test]
+    fn try_rgb_accepts_missing_hash() {
+        assert_eq!(try_rgb("A277FF"), Ok(rgb("#A277FF")));
+    }
+
+    #[test]
+    fn try_rgb_rejects_invalid_characters_and_lengths() {
+        assert!(try_rgb("#GG0000").is_err());
+        assert!(try_rgb("#12345é").is_err());
+        assert!(try_rgb("#1234").is_err());
+        assert!(try_rgb("#1234567").is_err());
+        assert!(try_rgb("#123456789").is_err());
+        assert!(try_rgb("").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid built-in theme color")]
+    fn builtin_rgb_panics_on_typo() {
+        rgb("#15141");
+    }
+
+    #[test]
+    fn file_selection_picks_builtin_theme_without_lua() {
+        let dark = theme_for_file_selection(ColorSchemeSelection::Dark, || unreachable!())
+            .expect("dark theme");
+        assert!(!dark.palette.is_light);
+
+        let light = theme_for_file_selection(ColorSchemeSelection::Light, || unreachable!())
+            .expect("light theme");
+        assert!(light.palette.is_light);
+
+        let auto =
+            theme_for_file_selection(ColorSchemeSelection::Auto, || false).expect("auto theme");
+        assert!(auto.palette.is_light);
+        assert_eq!(auto.appearance_is_dark, Some(false));
+
+        assert!(theme_for_file_selection(ColorSchemeSelection::Other, || true).is_none());
+    }
+
+    #[test]
+    fn config_file_change_is_detected_after_first_observation() {
+        use std::time::{Duration, SystemTime};
+
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let t1 = t0 + Duration::from_secs(1);
+
+        assert!(!config_file_changed(None, Some(t0)));
+        assert!(!config_file_changed(Some(Some(t0)), Some(t0)));
+        assert!(config_file_changed(Some(Some(t0)), Some(t1)));
+        assert!(config_file_changed(Some(None), Some(t0)));
+        assert!(config_file_changed(Some(Some(t0)), None));
+    }
 }
@@ -19,7 +19,7 @@ use clap::{Parser, ValueEnum, ValueHint};
 use clap_complete::{generate as generate_completion, shells, Generator as CompletionGenerator};
 use config::{wezterm_version, ConfigHandle};
 use mux::Mux;
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
 use umask::UmaskSaver;
@@ -29,6 +29,7 @@ mod ai_config;
 mod assistant_config;
 mod cli;
 mod config_cmd;
+mod config_migrate;
 mod config_tui;
 mod doctor;
 mod init;
@@ -276,9 +277,9 @@ fn main() {
     Mux::shutdown();
 }
 
-fn init_config(opts: &Opt) -> anyhow::Result<ConfigHandle> {
+fn init_config(opts: &Opt, config_file: Option<&OsStr>) -> anyhow::Result<ConfigHandle> {
     config::common_init(
-        opts.config_file.as_ref(),
+        config_file.map(OsString::from).as_ref(),
         &opts.config_override,
         opts.skip_config,
     )
@@ -334,14 +335,21 @@ fn run() -> anyhow::Result<()> {
         }
         SubCommand::Update(cmd) => cmd.run(),
         SubCommand::Config(cmd) => {
-            init_config(&opts)?;
-            cmd.run(opts.config_file.as_ref().map(PathBuf::from))
+            // Read before init_config, which points KAKU_CONFIG_FILE at
+            // whatever file it ends up loading.
+            let config_file = opts
+                .config_file
+                .as_ref()
+                .map(PathBuf::from)
+                .or_else(config_tui::config_file_from_env);
+            init_config(&opts, config_file.as_ref().map(|path| path.as_os_str()))?;
+            cmd.run(config_file)
         }
         SubCommand::Init(cmd) => cmd.run(),
         SubCommand::Doctor(cmd) => cmd.run(),
         SubCommand::Reset(cmd) => cmd.run(),
         SubCommand::Ai(cmd) => {
-            init_config(&opts)?;
+            init_config(&opts, opts.config_file.as_deref())?;
             cmd.run()
         }
     }
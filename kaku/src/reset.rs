@@ -10,19 +10,32 @@ pub struct ResetCommand {
     /// Skip confirmation prompt
     #[arg(long, short = 'y')]
     pub yes: bool,
+
+    /// Leave the git delta pager settings in place
+    #[arg(long)]
+    pub keep_delta: bool,
+
+    /// Delete assistant.toml instead of only clearing its API key
+    #[arg(long)]
+    pub remove_assistant_config: bool,
+
+    /// Print the reset report as JSON instead of text; requires --yes
+    #[arg(long, requires = "yes")]
+    pub json: bool,
 }
 
 impl ResetCommand {
     pub fn run(&self) -> anyhow::Result<()> {
-        imp::run(self.yes)
+        imp::run(self)
     }
 }
 
 #[cfg(not(target_os = "macos"))]
 mod imp {
+    use super::ResetCommand;
     use anyhow::bail;
 
-    pub fn run(_yes: bool) -> anyhow::Result<()> {
+    pub fn run(_cmd: &ResetCommand) -> anyhow::Result<()> {
         bail!("`kaku reset` is currently supported on macOS only")
     }
 }
@@ -30,6 +43,9 @@ mod imp {
 #[cfg(target_os = "macos")]
 mod imp {
     use super::*;
+    use crate::config_migrate::{LEGACY_THEME_MARKER, THEME_MARKER};
+    use crate::utils::abbreviate_home;
+    use serde::Serialize;
 
     const KAKU_SOURCE_PATTERN: &str = "kaku/zsh/kaku.zsh";
     const KAKU_PATH_MARKER: &str = "# Kaku PATH Integration";
@@ -37,6 +53,7 @@ mod imp {
     const KAKU_LEGACY_INLINE_MARKER: &str = "# Kaku Shell Integration";
     const KAKU_LEGACY_INLINE_VAR: &str = "KAKU_ZSH_DIR";
     const KAKU_LEGACY_SYNTAX_HINT: &str = "zsh-syntax-highlighting/zsh-syntax-highlighting.zsh";
+    const KAKU_WRAPPER_MARKER: &str = "exec \"${KAKU_BIN}\"";
 
     const KAKU_GIT_DEFAULTS: &[(&str, &str)] = &[
         ("core.pager", "delta"),
@@ -52,10 +69,12 @@ mod imp {
         ("delta.hunk-header-style", "file line-number syntax"),
     ];
 
-    #[derive(Default)]
+    #[derive(Default, Serialize)]
     struct ResetReport {
         changed: Vec<String>,
         skipped: Vec<String>,
+        /// Whether every reset step ran; only reported in `--json` output.
+        completed: bool,
     }
 
     impl ResetReport {
@@ -86,47 +105,20 @@ mod imp {
         }
     }
 
-    pub fn run(yes: bool) -> anyhow::Result<()> {
-        confirm_reset(yes)?;
+    pub fn run(cmd: &ResetCommand) -> anyhow::Result<()> {
+        let yes = cmd.yes;
+        confirm_reset(yes, cmd.keep_delta)?;
 
         let mut report = ResetReport::default();
+        let result = apply_reset(cmd.keep_delta, cmd.remove_assistant_config, &mut report);
 
-        remove_zsh_integration(&mut report)?;
-        remove_kaku_shell_dir(&mut report)?;
-        remove_tmux_integration(&mut report)?;
-        remove_file_if_exists(
-            config_home().join("tmux").join("kaku.tmux.conf"),
-            "removed managed tmux integration script",
-            &mut report,
-        )?;
-        cleanup_git_delta_defaults(&mut report)?;
-        cleanup_theme_block(&mut report)?;
-        remove_file_if_exists(
-            config_home().join("state.json"),
-            "removed persisted Kaku state",
-            &mut report,
-        )?;
-        remove_file_if_exists(
-            config_home().join(".kaku_config_version"),
-            "removed legacy Kaku config version marker",
-            &mut report,
-        )?;
-        remove_file_if_exists(
-            config_home().join(".kaku_window_geometry"),
-            "removed legacy Kaku window geometry marker",
-            &mut report,
-        )?;
-        remove_file_if_exists(
-            config_home().join("lazygit_state.json"),
-            "removed Lazygit hint state",
-            &mut report,
-        )?;
-        remove_dir_if_exists(
-            config_home().join("backups"),
-            "removed Kaku backup directory",
-            &mut report,
-        )?;
-        remove_empty_kaku_config_dir(&mut report)?;
+        if cmd.json {
+            report.completed = result.is_ok();
+            let json = serde_json::to_string_pretty(&report).context("serialize reset report")?;
+            println!("{}", json);
+            return result;
+        }
+        result?;
 
         report.print();
 
@@ -159,7 +151,58 @@ mod imp {
         Ok(())
     }
 
-    fn confirm_reset(yes: bool) -> anyhow::Result<()> {
+    fn apply_reset(
+        keep_delta: bool,
+        remove_assistant_config: bool,
+        report: &mut ResetReport,
+    ) -> anyhow::Result<()> {
+        remove_zsh_integration(report)?;
+        remove_kaku_shell_dir(report)?;
+        remove_kaku_wrapper_bin(report)?;
+        remove_tmux_integration(report)?;
+        remove_file_if_exists(
+            config_home().join("tmux").join("kaku.tmux.conf"),
+            "removed managed tmux integration script",
+            report,
+        )?;
+        cleanup_git_delta_defaults(keep_delta, report)?;
+        cleanup_theme_block(report)?;
+        remove_file_if_exists(
+            config_home().join("state.json"),
+            "removed persisted Kaku state",
+            report,
+        )?;
+        remove_file_if_exists(
+            config_home().join(".kaku_config_version"),
+            "removed legacy Kaku config version marker",
+            report,
+        )?;
+        remove_file_if_exists(
+            config_home().join(".kaku_init_version"),
+            "removed Kaku init version marker",
+            report,
+        )?;
+        remove_file_if_exists(
+            config_home().join(".kaku_window_geometry"),
+            "removed legacy Kaku window geometry marker",
+            report,
+        )?;
+        remove_file_if_exists(
+            config_home().join("lazygit_state.json"),
+            "removed Lazygit hint state",
+            report,
+        )?;
+        cleanup_assistant_config(remove_assistant_config, report)?;
+        remove_dir_if_exists(
+            config_home().join("backups"),
+            "removed Kaku backup directory",
+            report,
+        )?;
+        remove_empty_kaku_config_dir(report)?;
+        Ok(())
+    }
+
+    fn confirm_reset(yes: bool, keep_delta: bool) -> anyhow::Result<()> {
         if yes {
             return Ok(());
         }
@@ -168,9 +211,15 @@ mod imp {
             bail!("non-interactive terminal detected; rerun with --yes to confirm reset")
         }
 
-        println!(
-            "This will remove Kaku shell and tmux integration and reset Kaku-managed git defaults."
-        );
+        if keep_delta {
+            println!(
+                "This will remove Kaku shell and tmux integration and clear the assistant API key; git delta settings are kept."
+            );
+        } else {
+            println!(
+                "This will remove Kaku shell and tmux integration, clear the assistant API key, and reset Kaku-managed git defaults."
+            );
+        }
         print!("Continue with reset? [y/N] ");
         io::stdout().flush().context("flush stdout")?;
 
@@ -223,7 +272,7 @@ mod imp {
     fn remove_zsh_integration(report: &mut ResetReport) -> anyhow::Result<()> {
         let zshrc = zshrc_path();
         if !zshrc.exists() {
-            report.skipped(format!("{} not found", zshrc.display()));
+            report.skipped(format!("{} not found", abbreviate_home(&zshrc)));
             return Ok(());
         }
 
@@ -244,7 +293,7 @@ mod imp {
         if !removed_managed_lines && !removed_legacy_block {
             report.skipped(format!(
                 "no Kaku shell integration found in {}",
-                zshrc.display()
+                abbreviate_home(&zshrc)
             ));
             return Ok(());
         }
@@ -253,17 +302,17 @@ mod imp {
         if removed_managed_lines && removed_legacy_block {
             report.changed(format!(
                 "removed Kaku-managed .zshrc lines and legacy inline block from {}",
-                zshrc.display()
+                abbreviate_home(&zshrc)
             ));
         } else if removed_managed_lines {
             report.changed(format!(
                 "removed Kaku-managed .zshrc lines from {}",
-                zshrc.display()
+                abbreviate_home(&zshrc)
             ));
         } else {
             report.changed(format!(
                 "removed legacy inline Kaku block from {}",
-                zshrc.display()
+                abbreviate_home(&zshrc)
             ));
         }
         Ok(())
@@ -274,17 +323,52 @@ mod imp {
         if kaku_init.exists() {
             std::fs::remove_file(&kaku_init)
                 .with_context(|| format!("remove {}", kaku_init.display()))?;
-            report.changed(format!("removed {}", kaku_init.display()));
+            report.changed(format!("removed {}", abbreviate_home(&kaku_init)));
         } else {
-            report.skipped(format!("{} not found", kaku_init.display()));
+            report.skipped(format!("{} not found", abbreviate_home(&kaku_init)));
         }
         Ok(())
     }
 
+    fn remove_kaku_wrapper_bin(report: &mut ResetReport) -> anyhow::Result<()> {
+        let bin_dir = config_home().join("zsh").join("bin");
+        let wrapper = bin_dir.join("kaku");
+        if std::fs::symlink_metadata(&wrapper).is_err() {
+            report.skipped(format!("{} not found", abbreviate_home(&wrapper)));
+            return Ok(());
+        }
+
+        let content = std::fs::read(&wrapper).unwrap_or_default();
+        if !is_kaku_wrapper_script(&String::from_utf8_lossy(&content)) {
+            report.skipped(format!(
+                "{} is not the Kaku wrapper; left in place",
+                abbreviate_home(&wrapper)
+            ));
+            return Ok(());
+        }
+
+        std::fs::remove_file(&wrapper).with_context(|| format!("remove {}", wrapper.display()))?;
+        report.changed(format!("removed {}", abbreviate_home(&wrapper)));
+
+        let is_empty = std::fs::read_dir(&bin_dir)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(false);
+        if is_empty {
+            std::fs::remove_dir(&bin_dir)
+                .with_context(|| format!("remove {}", bin_dir.display()))?;
+            report.changed(format!("removed empty {}", abbreviate_home(&bin_dir)));
+        }
+        Ok(())
+    }
+
+    fn is_kaku_wrapper_script(content: &str) -> bool {
+        content.starts_with("#!") && content.contains(KAKU_WRAPPER_MARKER)
+    }
+
     fn remove_tmux_integration(report: &mut ResetReport) -> anyhow::Result<()> {
         let tmuxrc = tmuxrc_path();
         if !tmuxrc.exists() {
-            report.skipped(format!("{} not found", tmuxrc.display()));
+            report.skipped(format!("{} not found", abbreviate_home(&tmuxrc)));
             return Ok(());
         }
 
@@ -298,7 +382,7 @@ mod imp {
         if filtered.len() == original.lines().count() {
             report.skipped(format!(
                 "no Kaku tmux integration found in {}",
-                tmuxrc.display()
+                abbreviate_home(&tmuxrc)
             ));
             return Ok(());
         }
@@ -310,21 +394,42 @@ mod imp {
         std::fs::write(&tmuxrc, updated).with_context(|| format!("write {}", tmuxrc.display()))?;
         report.changed(format!(
             "removed Kaku-managed tmux source line from {}",
-            tmuxrc.display()
+            abbreviate_home(&tmuxrc)
         ));
         Ok(())
     }
 
-    fn cleanup_git_delta_defaults(report: &mut ResetReport) -> anyhow::Result<()> {
+    /// What happened to one Kaku-managed git key during cleanup.
+    #[derive(Debug, PartialEq, Eq)]
+    enum GitKeyCleanup {
+        Removed,
+        NotSet,
+        /// The key holds a value other than the Kaku default, so the user
+        /// changed it and it is left alone.
+        Customized,
+    }
+
+    fn cleanup_git_delta_defaults(
+        keep_delta: bool,
+        report: &mut ResetReport,
+    ) -> anyhow::Result<()> {
+        if keep_delta {
+            report.skipped("kept git delta settings (--keep-delta)");
+            return Ok(());
+        }
+
         if !command_exists("git") {
             report.skipped("git not found; skipped git config cleanup");
             return Ok(());
         }
 
         let mut removed = Vec::new();
+        let mut customized = Vec::new();
         for (key, expected) in KAKU_GIT_DEFAULTS {
-            if unset_git_key_if_matches(key, expected)? {
-                removed.push(*key);
+            match unset_git_key_if_matches(key, expected)? {
+                GitKeyCleanup::Removed => removed.push(*key),
+                GitKeyCleanup::Customized => customized.push(*key),
+                GitKeyCleanup::NotSet => {}
             }
         }
 
@@ -333,11 +438,27 @@ mod imp {
         } else {
             report.changed(format!("removed git defaults: {}", removed.join(", ")));
         }
+        if !customized.is_empty() {
+            report.skipped(format!(
+                "kept customized git keys (value differs from Kaku default): {}",
+                customized.join(", ")
+            ));
+        }
 
         Ok(())
     }
 
-    fn unset_git_key_if_matches(key: &str, expected: &str) -> anyhow::Result<bool> {
+    fn classify_git_values(values: &[String], expected: &str) -> GitKeyCleanup {
+        if values.is_empty() {
+            GitKeyCleanup::NotSet
+        } else if values.iter().any(|v| v != expected) {
+            GitKeyCleanup::Customized
+        } else {
+            GitKeyCleanup::Removed
+        }
+    }
+
+    fn unset_git_key_if_matches(key: &str, expected: &str) -> anyhow::Result<GitKeyCleanup> {
         let output = Command::new("git")
             .args(["config", "--global", "--get-all", key])
             .output()
@@ -345,7 +466,7 @@ mod imp {
 
         if !output.status.success() {
             if output.status.code() == Some(1) {
-                return Ok(false);
+                return Ok(GitKeyCleanup::NotSet);
             }
             let stderr = String::from_utf8_lossy(&output.stderr);
             return Err(anyhow!(
@@ -361,8 +482,9 @@ mod imp {
             .filter(|line| !line.is_empty())
             .collect();
 
-        if values.is_empty() || values.iter().any(|v| v != expected) {
-            return Ok(false);
+        let state = classify_git_values(&values, expected);
+        if state != GitKeyCleanup::Removed {
+            return Ok(state);
         }
 
         let status = Command::new("git")
@@ -370,7 +492,11 @@ mod imp {
             .status()
             .with_context(|| format!("unset git config key {}", key))?;
 
-        Ok(status.success())
+        Ok(if status.success() {
+            GitKeyCleanup::Removed
+        } else {
+            GitKeyCleanup::NotSet
+        })
     }
 
     fn command_exists(name: &str) -> bool {
@@ -386,17 +512,15 @@ mod imp {
     fn cleanup_theme_block(report: &mut ResetReport) -> anyhow::Result<()> {
         let config_path = config_home().join("kaku.lua");
         if !config_path.exists() {
-            report.skipped(format!("{} not found", config_path.display()));
+            report.skipped(format!("{} not found", abbreviate_home(&config_path)));
             return Ok(());
         }
 
         let original = std::fs::read_to_string(&config_path)
             .with_context(|| format!("read {}", config_path.display()))?;
 
-        let (after_managed, changed_managed) =
-            strip_theme_block(&original, "-- ===== Kaku Theme Defaults (managed) =====");
-        let (after_legacy, changed_legacy) =
-            strip_theme_block(&after_managed, "-- ===== Kaku Theme =====");
+        let (after_managed, changed_managed) = strip_theme_block(&original, THEME_MARKER);
+        let (after_legacy, changed_legacy) = strip_theme_block(&after_managed, LEGACY_THEME_MARKER);
 
         if !changed_managed && !changed_legacy {
             report.skipped("no managed Kaku theme block found in ~/.config/kaku/kaku.lua");
@@ -461,40 +585,16 @@ mod imp {
         while i < lines.len() {
             let current = lines[i].trim();
             if current == KAKU_LEGACY_INLINE_MARKER {
-                let mut j = i + 1;
-                let mut saw_kaku_var = false;
-                let mut saw_syntax_line = false;
-                let mut end = None;
-
-                while j < lines.len() {
-                    let line = lines[j];
-                    if line.contains(KAKU_LEGACY_INLINE_VAR) {
-                        saw_kaku_var = true;
-                    }
-                    if line.contains(KAKU_LEGACY_SYNTAX_HINT) {
-                        saw_syntax_line = true;
-                    }
-                    if saw_syntax_line && line.trim() == "fi" {
-                        end = Some(j);
-                        break;
-                    }
-                    if j.saturating_sub(i) > 600 {
-                        break;
-                    }
-                    j += 1;
-                }
-
-                if saw_kaku_var {
-                    if let Some(end_idx) = end {
+                match find_legacy_block_end(&lines, i) {
+                    Some(end_idx) => {
                         changed = true;
                         i = end_idx + 1;
                         while i < lines.len() && lines[i].trim().is_empty() {
                             i += 1;
                         }
                         continue;
-                    } else {
-                        return (content.to_string(), false);
                     }
+                    None => return (content.to_string(), false),
                 }
             }
 
@@ -513,13 +613,123 @@ mod imp {
         (merged, true)
     }
 
+    /// Returns the index of the `fi` closing the legacy block that starts at
+    /// `start`. The block must set `KAKU_ZSH_DIR` and end with the
+    /// syntax-highlighting line immediately followed by `fi`; reaching another
+    /// marker or the end of the file first means the block is not recognized.
+    fn find_legacy_block_end(lines: &[&str], start: usize) -> Option<usize> {
+        let mut saw_kaku_var = false;
+        let mut prev_is_syntax_line = false;
+
+        for (idx, line) in lines.iter().enumerate().skip(start + 1) {
+            let trimmed = line.trim();
+            if trimmed == KAKU_LEGACY_INLINE_MARKER {
+                return None;
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+            if saw_kaku_var && prev_is_syntax_line && trimmed == "fi" {
+                return Some(idx);
+            }
+            if line.contains(KAKU_LEGACY_INLINE_VAR) {
+                saw_kaku_var = true;
+            }
+            prev_is_syntax_line = line.contains(KAKU_LEGACY_SYNTAX_HINT);
+        }
+
+        None
+    }
+
+    /// What reset does with assistant.toml.
+    #[derive(Debug, PartialEq, Eq)]
+    enum AssistantConfigCleanup {
+        Remove,
+        /// Rewrite the file with the API key commented out; other settings stay.
+        Scrub(String),
+        /// Nothing sensitive to clear.
+        Keep,
+    }
+
+    const ASSISTANT_API_KEY_PLACEHOLDER: &str = "# api_key = \"<your_api_key>\"";
+
+    fn cleanup_assistant_config(
+        remove_assistant_config: bool,
+        report: &mut ResetReport,
+    ) -> anyhow::Result<()> {
+        let path = crate::assistant_config::assistant_toml_path()?;
+        if !path.exists() {
+            report.skipped(format!("{} not found", abbreviate_home(&path)));
+            return Ok(());
+        }
+
+        let content =
+            std::fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+        match classify_assistant_config(&content, remove_assistant_config) {
+            AssistantConfigCleanup::Remove => {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("remove {}", path.display()))?;
+                report.changed(format!("removed {}", abbreviate_home(&path)));
+            }
+            AssistantConfigCleanup::Scrub(updated) => {
+                std::fs::write(&path, updated)
+                    .with_context(|| format!("write {}", path.display()))?;
+                report.changed(format!(
+                    "cleared API key in {}; other assistant settings kept \
+                     (use --remove-assistant-config to delete the file)",
+                    abbreviate_home(&path)
+                ));
+            }
+            AssistantConfigCleanup::Keep => {
+                report.skipped(format!(
+                    "no API key set in {}; file kept",
+                    abbreviate_home(&path)
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The untouched default template is removed outright since Kaku created
+    /// it; anything the user edited only loses its API key unless removal was
+    /// asked for.
+    fn classify_assistant_config(content: &str, remove: bool) -> AssistantConfigCleanup {
+        if remove || content == crate::assistant_config::default_assistant_toml_template() {
+            return AssistantConfigCleanup::Remove;
+        }
+
+        let mut scrubbed = false;
+        let mut lines = Vec::new();
+        for line in content.lines() {
+            // Any `api_key` line is cleared, including ones inside tables.
+            let head = line.split('#').next().unwrap_or("").trim();
+            match head.split_once('=') {
+                Some((name, _)) if name.trim() == "api_key" => {
+                    lines.push(ASSISTANT_API_KEY_PLACEHOLDER);
+                    scrubbed = true;
+                }
+                _ => lines.push(line),
+            }
+        }
+
+        if !scrubbed {
+            return AssistantConfigCleanup::Keep;
+        }
+        let mut updated = lines.join("\n");
+        if content.ends_with('\n') {
+            updated.push('\n');
+        }
+        AssistantConfigCleanup::Scrub(updated)
+    }
+
     fn remove_file_if_exists(
         path: PathBuf,
         changed_msg: &str,
         report: &mut ResetReport,
     ) -> anyhow::Result<()> {
         if !path.exists() {
-            report.skipped(format!("{} not found", path.display()));
+            report.skipped(format!("{} not found", abbreviate_home(&path)));
             return Ok(());
         }
 
@@ -534,7 +744,7 @@ mod imp {
         report: &mut ResetReport,
     ) -> anyhow::Result<()> {
         if !path.exists() {
-            report.skipped(format!("{} not found", path.display()));
+            report.skipped(format!("{} not found", abbreviate_home(&path)));
             return Ok(());
         }
 
@@ -551,7 +761,7 @@ mod imp {
 
         if is_dir_empty(&dir)? {
             std::fs::remove_dir(&dir).with_context(|| format!("remove {}", dir.display()))?;
-            report.changed(format!("removed empty {}", dir.display()));
+            report.changed(format!("removed empty {}", abbreviate_home(&dir)));
         }
 
         Ok(())
@@ -565,7 +775,23 @@ mod imp {
 
     #[cfg(test)]
     mod tests {
-        use super::{is_active_kaku_tmux_source_line, KAKU_TMUX_SOURCE_PATTERN};
+        use super::{
+            classify_assistant_config, classify_git_values, is_active_kaku_tmux_source_line,
+            is_kaku_wrapper_script, strip_legacy_inline_block, AssistantConfigCleanup,
+            GitKeyCleanup, ResetReport, KAKU_TMUX_SOURCE_PATTERN,
+        };
+
+        const LEGACY_BLOCK: &str = "# Kaku Shell Integration\n\
+            export KAKU_ZSH_DIR=\"$HOME/.config/kaku/zsh\"\n\
+            if [[ -f \"$KAKU_ZSH_DIR/plugins/zsh-syntax-highlighting/zsh-syntax-highlighting.zsh\" ]]; then\n\
+            source \"$KAKU_ZSH_DIR/plugins/zsh-syntax-highlighting/zsh-syntax-highlighting.zsh\"\n\
+            fi\n";
+
+        fn filler(prefix: &str, count: usize) -> String {
+            (0..count)
+                .map(|n| format!("export {}_{}=1\n", prefix, n))
+                .collect()
+        }
 
         #[test]
         fn active_tmux_source_line_is_detected() {
@@ -585,5 +811,126 @@ mod imp {
             let line = format!("note: {}", KAKU_TMUX_SOURCE_PATTERN);
             assert!(!is_active_kaku_tmux_source_line(&line));
         }
+
+        #[test]
+        fn legacy_block_is_removed_from_large_zshrc() {
+            let before = filler("BEFORE", 1000);
+            let after = filler("AFTER", 1000);
+            let content = format!("{}{}\n{}", before, LEGACY_BLOCK, after);
+
+            let (updated, changed) = strip_legacy_inline_block(&content);
+            assert!(changed);
+            assert_eq!(updated, format!("{}{}", before, after));
+        }
+
+        #[test]
+        fn legacy_block_with_long_body_is_removed() {
+            let body = filler("INNER", 800);
+            let block = LEGACY_BLOCK.replacen("if [[", &format!("{}if [[", body), 1);
+            let content = format!("{}export KEEP=1\n", block);
+
+            let (updated, changed) = strip_legacy_inline_block(&content);
+            assert!(changed);
+            assert_eq!(updated, "export KEEP=1\n");
+        }
+
+        #[test]
+        fn malformed_legacy_block_is_left_alone() {
+            let content = format!(
+                "{}{}",
+                LEGACY_BLOCK.trim_end().trim_end_matches("fi"),
+                filler("AFTER", 10)
+            );
+
+            let (updated, changed) = strip_legacy_inline_block(&content);
+            assert!(!changed);
+            assert_eq!(updated, content);
+        }
+
+        #[test]
+        fn legacy_marker_without_kaku_var_is_left_alone() {
+            let content = "# Kaku Shell Integration\n\
+                source ~/zsh-syntax-highlighting/zsh-syntax-highlighting.zsh\n\
+                fi\n";
+
+            let (updated, changed) = strip_legacy_inline_block(content);
+            assert!(!changed);
+            assert_eq!(updated, content);
+        }
+
+        #[test]
+        fn only_generated_wrapper_is_recognized() {
+            let wrapper = "#!/bin/bash\nset -euo pipefail\n\n\
+                if [[ -n \"${KAKU_BIN:-}\" && -x \"${KAKU_BIN}\" ]]; then\n\
+                \texec \"${KAKU_BIN}\" \"$@\"\nfi\n";
+            assert!(is_kaku_wrapper_script(wrapper));
+            assert!(!is_kaku_wrapper_script(
+                "#!/bin/sh\nexec /opt/kaku/bin/kaku \"$@\"\n"
+            ));
+            assert!(!is_kaku_wrapper_script("\u{7f}ELF binary"));
+        }
+
+        #[test]
+        fn json_report_has_stable_fields() {
+            let mut report = ResetReport::default();
+            report.changed("removed a");
+            report.skipped("b not found");
+            report.completed = true;
+
+            let value = serde_json::to_value(&report).unwrap();
+            assert_eq!(
+                value,
+                serde_json::json!({
+                    "changed": ["removed a"],
+                    "skipped": ["b not found"],
+                    "completed": true,
+                })
+            );
+        }
+
+        #[test]
+        fn assistant_config_default_is_removed_and_edits_are_scrubbed() {
+            let template = crate::assistant_config::default_assistant_toml_template();
+            assert_eq!(
+                classify_assistant_config(&template, false),
+                AssistantConfigCleanup::Remove
+            );
+
+            let edited = "enabled = true\napi_key = \"sk-secret\" # mine\nmodel = \"m\"\n";
+            assert_eq!(
+                classify_assistant_config(edited, false),
+                AssistantConfigCleanup::Scrub(
+                    "enabled = true\n# api_key = \"<your_api_key>\"\nmodel = \"m\"\n".to_string()
+                )
+            );
+            assert_eq!(
+                classify_assistant_config(edited, true),
+                AssistantConfigCleanup::Remove
+            );
+
+            let no_key = "enabled = false\n# api_key = \"sk-old\"\n";
+            assert_eq!(
+                classify_assistant_config(no_key, false),
+                AssistantConfigCleanup::Keep
+            );
+        }
+
+        #[test]
+        fn customized_git_values_are_kept() {
+            let default = vec!["Coldark-Dark".to_string()];
+            let custom = vec!["Dracula".to_string()];
+            assert_eq!(
+                classify_git_values(&default, "Coldark-Dark"),
+                GitKeyCleanup::Removed
+            );
+            assert_eq!(
+                classify_git_values(&custom, "Coldark-Dark"),
+                GitKeyCleanup::Customized
+            );
+            assert_eq!(
+                classify_git_values(&[], "Coldark-Dark"),
+                GitKeyCleanup::NotSet
+            );
+        }
     }
 }
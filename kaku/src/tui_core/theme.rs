@@ -1,4 +1,4 @@
-use config::configuration;
+use crate::kaku_theme::ThemePalette;
 use ratatui::style::Color;
 use std::sync::Mutex;
 use wezterm_term::color::SrgbaTuple;
@@ -13,13 +13,67 @@ struct Theme {
     muted: Color,
     bg: Color,
     panel: Color,
+    selection: Color,
 }
 
-static THEME_CACHE: Mutex<Option<(usize, Theme)>> = Mutex::new(None);
+static THEME_CACHE: Mutex<Option<(ThemePalette, Theme)>> = Mutex::new(None);
+
+/// Terminals advertise 24-bit color support through `COLORTERM`.
+fn supports_truecolor() -> bool {
+    std::env::var("COLORTERM")
+        .map(|v| matches!(v.as_str(), "truecolor" | "24bit"))
+        .unwrap_or(false)
+}
+
+/// Channel levels of the 6x6x6 color cube at indices 16..=231.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn nearest_cube_level(v: u8) -> usize {
+    CUBE_LEVELS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, level)| (i32::from(**level) - i32::from(v)).abs())
+        .map(|(idx, _)| idx)
+        .unwrap_or(0)
+}
+
+fn distance_sq(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+    let d = |x: u8, y: u8| i32::from(x) - i32::from(y);
+    d(a.0, b.0).pow(2) + d(a.1, b.1).pow(2) + d(a.2, b.2).pow(2)
+}
+
+/// Maps an RGB color to the closest entry of the xterm 256-color palette,
+/// considering both the color cube and the grayscale ramp (232..=255).
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let (ri, gi, bi) = (
+        nearest_cube_level(r),
+        nearest_cube_level(g),
+        nearest_cube_level(b),
+    );
+    let cube_rgb = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let avg = (u32::from(r) + u32::from(g) + u32::from(b)) / 3;
+    let gray_step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray_level = 8 + 10 * gray_step;
+    let gray_index = 232 + gray_step;
+
+    if distance_sq((r, g, b), (gray_level, gray_level, gray_level))
+        < distance_sq((r, g, b), cube_rgb)
+    {
+        gray_index
+    } else {
+        cube_index as u8
+    }
+}
 
 fn to_color(c: SrgbaTuple) -> Color {
     let (r, g, b, _) = c.to_srgb_u8();
-    Color::Rgb(r, g, b)
+    if supports_truecolor() {
+        Color::Rgb(r, g, b)
+    } else {
+        Color::Indexed(rgb_to_ansi256(r, g, b))
+    }
 }
 
 fn blend(base: SrgbaTuple, overlay: SrgbaTuple, amount: f32) -> SrgbaTuple {
@@ -32,11 +86,22 @@ fn blend(base: SrgbaTuple, overlay: SrgbaTuple, amount: f32) -> SrgbaTuple {
     )
 }
 
-fn theme_from_palette(palette: &crate::kaku_theme::ThemePalette) -> Theme {
+/// Amounts of text blended over bg for popup panels and for the selected
+/// row inside a popup, which must still stand out against the panel.
+fn surface_blends(is_light: bool) -> (f32, f32) {
+    if is_light {
+        (0.05, 0.11)
+    } else {
+        (0.08, 0.16)
+    }
+}
+
+fn theme_from_palette(palette: &ThemePalette) -> Theme {
     // Derive panel from bg+text blend so popups have enough contrast vs the
     // Preserve the existing background formula regardless of external tool integrations.
-    let panel_blend = if palette.is_light { 0.05 } else { 0.08 };
+    let (panel_blend, selection_blend) = surface_blends(palette.is_light);
     let panel = blend(palette.bg, palette.text, panel_blend);
+    let selection = blend(palette.bg, palette.text, selection_blend);
 
     Theme {
         primary: to_color(palette.primary),
@@ -47,23 +112,25 @@ fn theme_from_palette(palette: &crate::kaku_theme::ThemePalette) -> Theme {
         muted: to_color(palette.muted),
         bg: to_color(palette.bg),
         panel: to_color(panel),
+        selection: to_color(selection),
     }
 }
 
 fn current_theme() -> Theme {
-    let config = configuration();
-    let generation = config.generation();
+    // kaku_theme tracks config reloads, edits to the config file and the
+    // system appearance, so ask it every time and only rebuild the colors
+    // when the palette moved.
+    let palette = crate::kaku_theme::current_theme_palette();
 
     let mut cached = THEME_CACHE.lock().unwrap();
-    if let Some((cached_generation, theme)) = *cached {
-        if cached_generation == generation {
+    if let Some((cached_palette, theme)) = *cached {
+        if cached_palette == palette {
             return theme;
         }
     }
 
-    let palette = crate::kaku_theme::current_theme_palette();
     let theme = theme_from_palette(&palette);
-    *cached = Some((generation, theme));
+    *cached = Some((palette, theme));
     theme
 }
 
@@ -98,3 +165,65 @@ pub fn bg() -> Color {
 pub fn panel() -> Color {
     current_theme().panel
 }
+
+/// Background for the highlighted row of a popup drawn on `panel()`.
+pub fn selection() -> Color {
+    current_theme().selection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bg, primary, rgb_to_ansi256, surface_blends};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn rgb_maps_to_nearest_256_color_index() {
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(95, 135, 175), 67);
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+        assert_eq!(rgb_to_ansi256(0x1c, 0x1c, 0x1c), 234);
+    }
+
+    #[test]
+    fn popup_selection_is_stronger_than_panel() {
+        for is_light in [true, false] {
+            let (panel, selection) = surface_blends(is_light);
+            assert!(selection > panel);
+        }
+    }
+
+    #[test]
+    fn primary_follows_color_scheme_edits_in_config_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("kaku.lua");
+        let write_scheme = |scheme: &str, mtime_secs: u64| {
+            std::fs::write(
+                &path,
+                format!("local config = {{}}\nconfig.color_scheme = '{scheme}'\nreturn config\n"),
+            )
+            .expect("write config");
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| {
+                    file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(mtime_secs))
+                })
+                .expect("set mtime");
+            crate::kaku_theme::expire_config_file_check();
+        };
+
+        config::set_config_file_override(&path);
+        write_scheme("Kaku Light", 1_000);
+        // The first look at a file only records its mtime.
+        primary();
+        write_scheme("Kaku Light", 2_000);
+        let light = (primary(), bg());
+        write_scheme("Kaku Dark", 3_000);
+        let dark = (primary(), bg());
+        config::clear_config_file_override();
+
+        assert_ne!(light, dark);
+    }
+}
@@ -1,15 +1,41 @@
 use anyhow::{anyhow, bail, Context};
 use clap::Parser;
 
+/// Holds the release tag whose update notifications the user skipped.
+/// Read by the gui update checker, which removes it once the installed
+/// version reaches that tag.
+const SKIPPED_UPDATE_FILE: &str = "skipped_update";
+
 #[derive(Debug, Parser, Clone, Default)]
-pub struct UpdateCommand {}
+pub struct UpdateCommand {
+    /// Stop update notifications for this release tag (e.g. v0.9.0).
+    /// Newer releases are still announced.
+    #[arg(long, value_name = "TAG")]
+    skip: Option<String>,
+}
 
 impl UpdateCommand {
     pub fn run(&self) -> anyhow::Result<()> {
+        if let Some(tag) = &self.skip {
+            return skip_version(tag);
+        }
         imp::run()
     }
 }
 
+fn skip_version(tag: &str) -> anyhow::Result<()> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        bail!("--skip requires a release tag");
+    }
+    config::create_user_owned_dirs(&config::DATA_DIR).context("create data directory")?;
+    let path = config::DATA_DIR.join(SKIPPED_UPDATE_FILE);
+    std::fs::write(&path, format!("{}\n", tag))
+        .with_context(|| format!("write {}", path.display()))?;
+    println!("Update notifications for {} will be skipped.", tag);
+    Ok(())
+}
+
 #[cfg(not(target_os = "macos"))]
 mod imp {
     use anyhow::bail;
@@ -50,6 +76,8 @@ mod imp {
     struct GitHubAsset {
         name: String,
         browser_download_url: String,
+        #[serde(default)]
+        size: Option<u64>,
     }
 
     struct BrewInfo {
@@ -111,9 +139,10 @@ mod imp {
             }
         }
 
-        let zip_url = release
+        let zip_asset = release
             .as_ref()
-            .and_then(|rel| find_asset(&rel.assets, UPDATE_ZIP_NAME))
+            .and_then(|rel| find_asset(&rel.assets, UPDATE_ZIP_NAME));
+        let zip_url = zip_asset
             .map(|asset| asset.browser_download_url.as_str())
             .unwrap_or(LATEST_ZIP_URL);
 
@@ -144,12 +173,24 @@ mod imp {
         curl_download_to_file(zip_url, &zip_path, &current_version)
             .context("failed to download update package")?;
 
+        if let Some(expected_size) = zip_asset.and_then(|asset| asset.size) {
+            let actual_size = fs::metadata(&zip_path)
+                .context("read downloaded update package")?
+                .len();
+            if let Err(err) = check_download_size(expected_size, actual_size) {
+                let _ = fs::remove_dir_all(&work_dir);
+                return Err(err.context("update package is incomplete or corrupt"));
+            }
+        }
+
         if let Some(sha_url) = sha_url {
             match curl_get_text(sha_url, &current_version) {
                 Ok(checksum_text) => {
                     println!("Verifying package checksum...");
-                    verify_sha256(&zip_path, &checksum_text)
-                        .context("checksum verification failed")?;
+                    if let Err(err) = verify_sha256(&zip_path, &checksum_text) {
+                        let _ = fs::remove_dir_all(&work_dir);
+                        return Err(err.context("checksum verification failed"));
+                    }
                 }
                 Err(err) => {
                     println!(
@@ -553,7 +594,33 @@ mod imp {
         )
     }
 
+    fn check_download_size(expected: u64, actual: u64) -> anyhow::Result<()> {
+        if expected != actual {
+            bail!(
+                "size mismatch (expected {} bytes, got {} bytes)",
+                expected,
+                actual
+            );
+        }
+        Ok(())
+    }
+
     fn verify_sha256(zip_path: &Path, checksum_text: &str) -> anyhow::Result<()> {
+        let output = run_output(
+            Command::new("/usr/bin/shasum")
+                .arg("-a")
+                .arg("256")
+                .arg(zip_path),
+            "compute sha256",
+        )?;
+        let actual_line =
+            String::from_utf8(output).context("`shasum` output was not valid UTF-8")?;
+        compare_sha256(checksum_text, &actual_line)
+    }
+
+    /// Compares the digest in a `.sha256` file against `shasum -a 256` output;
+    /// both use the `<hex digest>  <file name>` layout.
+    fn compare_sha256(checksum_text: &str, shasum_output: &str) -> anyhow::Result<()> {
         let expected = checksum_text
             .split_whitespace()
             .next()
@@ -565,16 +632,7 @@ mod imp {
             bail!("checksum file has invalid sha256: {}", expected);
         }
 
-        let output = run_output(
-            Command::new("/usr/bin/shasum")
-                .arg("-a")
-                .arg("256")
-                .arg(zip_path),
-            "compute sha256",
-        )?;
-        let actual_line =
-            String::from_utf8(output).context("`shasum` output was not valid UTF-8")?;
-        let actual = actual_line
+        let actual = shasum_output
             .split_whitespace()
             .next()
             .ok_or_else(|| anyhow!("failed to parse `shasum` output"))?
@@ -1042,4 +1100,32 @@ log "done"
         }
         Some(out)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{check_download_size, compare_sha256};
+
+        // sha256("abc"), used only as a well-formed digest string
+        const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+        #[test]
+        fn sha256_digest_strings_compare_case_insensitively() {
+            let checksum = format!("{}  kaku_for_update.zip\n", ABC_SHA256.to_uppercase());
+            let shasum = format!("{}  /tmp/updates/kaku_for_update.zip\n", ABC_SHA256);
+            assert!(compare_sha256(&checksum, &shasum).is_ok());
+
+            let corrupt = format!("{}0  kaku_for_update.zip\n", &ABC_SHA256[..63]);
+            let err = compare_sha256(&checksum, &corrupt).unwrap_err();
+            assert!(err.to_string().contains("mismatch"));
+
+            assert!(compare_sha256("not-a-digest", &shasum).is_err());
+            assert!(compare_sha256("", &shasum).is_err());
+        }
+
+        #[test]
+        fn download_size_must_match_asset() {
+            assert!(check_download_size(1024, 1024).is_ok());
+            assert!(check_download_size(1024, 512).is_err());
+        }
+    }
 }
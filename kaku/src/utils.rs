@@ -1,7 +1,8 @@
 use anyhow::Context;
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::path::Path;
 use std::process::{Command, Stdio};
+use std::time::SystemTime;
 
 pub fn is_jsonc_path(path: &Path) -> bool {
     path.extension()
@@ -45,11 +46,165 @@ pub fn write_atomic(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Replaces a leading home directory with `~` for user-facing messages.
+pub fn abbreviate_home(path: &Path) -> String {
+    abbreviate_home_in(path, &config::HOME_DIR)
+}
+
+fn abbreviate_home_in(path: &Path, home: &Path) -> String {
+    // `strip_prefix` matches whole components, so `/Users/meadow` is not
+    // treated as being inside `/Users/me`.
+    match path.strip_prefix(home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => format!("~/{}", rest.display()),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+/// Returns the file's modification time, or `None` if it can't be read.
+pub fn file_modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// OSC 1337 SetUserVar=KAKU_CONFIG_CHANGED=base64(payload). The payload is the
+/// comma-separated `lua_key`s that changed, or `1` when any key may have
+/// changed, which is also all older kaku-gui builds understand.
+fn config_changed_osc(changed_keys: &[&str]) -> Vec<u8> {
+    use base64::Engine;
+
+    let payload = if changed_keys.is_empty() {
+        "1".to_string()
+    } else {
+        changed_keys.join(",")
+    };
+    format!(
+        "\x1b]1337;SetUserVar=KAKU_CONFIG_CHANGED={}\x07",
+        base64::engine::general_purpose::STANDARD.encode(payload)
+    )
+    .into_bytes()
+}
+
+/// OSC 52 asking the terminal to put `text` on the system clipboard.
+fn clipboard_osc(text: &str) -> Vec<u8> {
+    use base64::Engine;
+
+    format!(
+        "\x1b]52;c;{}\x07",
+        base64::engine::general_purpose::STANDARD.encode(text)
+    )
+    .into_bytes()
+}
+
+/// Terminal multiplexer sitting between us and kaku-gui, which swallows
+/// escape sequences it does not understand unless they are passed through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    /// `KAKU_FORCE_PASSTHROUGH=tmux|screen|none` overrides detection for
+    /// setups where `TMUX`/`STY` are not propagated, e.g. over SSH.
+    fn from_env() -> Self {
+        Self::detect(
+            std::env::var("KAKU_FORCE_PASSTHROUGH").ok().as_deref(),
+            std::env::var_os("TMUX").is_some(),
+            std::env::var_os("STY").is_some(),
+        )
+    }
+
+    fn detect(force: Option<&str>, in_tmux: bool, in_screen: bool) -> Self {
+        match force
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("tmux") => return Self::Tmux,
+            Some("screen") => return Self::Screen,
+            Some("none") => return Self::None,
+            _ => {}
+        }
+        // tmux started inside screen is the closer layer, so it wins.
+        if in_tmux {
+            Self::Tmux
+        } else if in_screen {
+            Self::Screen
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Wraps `seq` in the DCS passthrough of `mux` so that it reaches the outer
+/// terminal. tmux needs every ESC in the payload doubled; screen takes the
+/// payload as is.
+fn passthrough_sequence(mux: Multiplexer, seq: &[u8]) -> Vec<u8> {
+    match mux {
+        Multiplexer::None => seq.to_vec(),
+        Multiplexer::Tmux => {
+            let mut out = b"\x1bPtmux;".to_vec();
+            for &byte in seq {
+                if byte == 0x1b {
+                    out.push(0x1b);
+                }
+                out.push(byte);
+            }
+            out.extend_from_slice(b"\x1b\\");
+            out
+        }
+        Multiplexer::Screen => {
+            let mut out = b"\x1bP".to_vec();
+            out.extend_from_slice(seq);
+            out.extend_from_slice(b"\x1b\\");
+            out
+        }
+    }
+}
+
+/// Send an OSC 1337 SetUserVar to signal kaku-gui that config has changed.
+/// This triggers an immediate config reload instead of waiting for the file watcher.
+/// `changed_keys` lets kaku-gui skip work the keys cannot affect; pass an
+/// empty slice when the changes are unknown, e.g. after an external editor.
+/// Nothing is sent when stdout is redirected, such as `kaku config set` in a
+/// script; the file watcher picks the change up instead.
+pub fn signal_config_changed(changed_keys: &[&str]) {
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return;
+    }
+    let seq = passthrough_sequence(Multiplexer::from_env(), &config_changed_osc(changed_keys));
+    let _ = stdout.write_all(&seq);
+    let _ = stdout.flush();
+}
+
+/// Copies `text` to the system clipboard through OSC 52, which kaku-gui and
+/// most terminals honor, so it also works over SSH.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    let seq = passthrough_sequence(Multiplexer::from_env(), &clipboard_osc(text));
+    let mut stdout = std::io::stdout();
+    stdout.write_all(&seq)?;
+    stdout.flush()
+}
+
 pub fn open_path_in_editor(path: &Path) -> anyhow::Result<()> {
+    open_path_in_editor_with(path, false)
+}
+
+/// Like `open_path_in_editor`, but asks `$VISUAL`/`$EDITOR` to open the file
+/// read-only when it is an editor with such a flag. Other editors open it
+/// normally.
+pub fn open_path_in_editor_read_only(path: &Path) -> anyhow::Result<()> {
+    open_path_in_editor_with(path, true)
+}
+
+fn open_path_in_editor_with(path: &Path, read_only: bool) -> anyhow::Result<()> {
     let mut errors = Vec::new();
 
     for var in ["VISUAL", "EDITOR"] {
-        match try_env_editor(var, path) {
+        match try_env_editor(var, path, read_only) {
             Ok(true) => return Ok(()),
             Ok(false) => {}
             Err(err) => errors.push(err.to_string()),
@@ -86,7 +241,7 @@ pub fn open_path_in_editor(path: &Path) -> anyhow::Result<()> {
     );
 }
 
-fn try_env_editor(var: &str, path: &Path) -> anyhow::Result<bool> {
+fn try_env_editor(var: &str, path: &Path, read_only: bool) -> anyhow::Result<bool> {
     let Some(raw) = std::env::var_os(var) else {
         return Ok(false);
     };
@@ -94,6 +249,10 @@ fn try_env_editor(var: &str, path: &Path) -> anyhow::Result<bool> {
     let raw = raw.to_string_lossy();
     let (program, args) =
         parse_editor_command(raw.trim()).with_context(|| format!("parse ${var}"))?;
+    let mut args = with_vscode_goto(&program, args);
+    if read_only {
+        args = with_read_only_flag(&program, args);
+    }
 
     run_editor_command(&program, &args, path)
         .with_context(|| format!("launch ${var} editor `{program}`"))?;
@@ -108,6 +267,35 @@ fn parse_editor_command(raw: &str) -> anyhow::Result<(String, Vec<String>)> {
     Ok((program.clone(), args.to_vec()))
 }
 
+/// `try_vscode` launches `code -g`; pass the same flag when `$VISUAL` or
+/// `$EDITOR` points at VSCode so both launch paths behave identically.
+fn with_vscode_goto(program: &str, mut args: Vec<String>) -> Vec<String> {
+    let is_vscode = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name == "code" || name == "code-insiders");
+    if is_vscode && !args.iter().any(|arg| arg == "-g" || arg == "--goto") {
+        args.push("-g".to_string());
+    }
+    args
+}
+
+/// Adds the view-only flag of terminal editors that have one.
+fn with_read_only_flag(program: &str, mut args: Vec<String>) -> Vec<String> {
+    let flag = match Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+    {
+        Some("vim" | "nvim" | "vi" | "mvim" | "gvim") => "-R",
+        Some("nano") => "-v",
+        _ => return args,
+    };
+    if !args.iter().any(|arg| arg == flag) {
+        args.push(flag.to_string());
+    }
+    args
+}
+
 fn try_vscode(path: &Path) -> anyhow::Result<bool> {
     let mut candidates = vec![
         "code".to_string(),
@@ -356,4 +544,110 @@ mod tests {
         let err = parse_editor_command("   ").expect_err("empty editor command should fail");
         assert!(err.to_string().contains("empty"));
     }
+
+    #[test]
+    fn config_changed_sequence_wraps_for_tmux() {
+        let plain = passthrough_sequence(Multiplexer::None, &config_changed_osc(&[]));
+        let wrapped = passthrough_sequence(Multiplexer::Tmux, &config_changed_osc(&[]));
+        assert!(plain.starts_with(b"\x1b]1337;SetUserVar=KAKU_CONFIG_CHANGED="));
+        assert!(wrapped.starts_with(b"\x1bPtmux;\x1b\x1b]1337;"));
+        assert!(wrapped.ends_with(b"\x1b\\"));
+        assert_eq!(
+            wrapped,
+            b"\x1bPtmux;\x1b\x1b]1337;SetUserVar=KAKU_CONFIG_CHANGED=MQ==\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn config_changed_sequence_wraps_for_screen() {
+        let wrapped = passthrough_sequence(Multiplexer::Screen, &config_changed_osc(&[]));
+        assert_eq!(
+            wrapped,
+            b"\x1bP\x1b]1337;SetUserVar=KAKU_CONFIG_CHANGED=MQ==\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn config_changed_sequence_lists_changed_keys() {
+        // base64("font_size,window_background_opacity")
+        assert_eq!(
+            config_changed_osc(&["font_size", "window_background_opacity"]),
+            b"\x1b]1337;SetUserVar=KAKU_CONFIG_CHANGED=Zm9udF9zaXplLHdpbmRvd19iYWNrZ3JvdW5kX29wYWNpdHk=\x07"
+        );
+    }
+
+    #[test]
+    fn clipboard_sequence_encodes_text() {
+        // base64("Ctrl+K")
+        assert_eq!(clipboard_osc("Ctrl+K"), b"\x1b]52;c;Q3RybCtL\x07");
+    }
+
+    #[test]
+    fn multiplexer_detection_honors_force_override() {
+        assert_eq!(Multiplexer::detect(None, false, false), Multiplexer::None);
+        assert_eq!(Multiplexer::detect(None, false, true), Multiplexer::Screen);
+        assert_eq!(Multiplexer::detect(None, true, true), Multiplexer::Tmux);
+        assert_eq!(
+            Multiplexer::detect(Some("Screen"), true, false),
+            Multiplexer::Screen
+        );
+        assert_eq!(
+            Multiplexer::detect(Some("none"), true, false),
+            Multiplexer::None
+        );
+        assert_eq!(
+            Multiplexer::detect(Some("tmux"), false, false),
+            Multiplexer::Tmux
+        );
+        assert_eq!(
+            Multiplexer::detect(Some(""), false, true),
+            Multiplexer::Screen
+        );
+    }
+
+    #[test]
+    fn abbreviate_home_only_matches_whole_components() {
+        let home = Path::new("/Users/me");
+        assert_eq!(
+            abbreviate_home_in(Path::new("/Users/me/.config/kaku/kaku.lua"), home),
+            "~/.config/kaku/kaku.lua"
+        );
+        assert_eq!(abbreviate_home_in(Path::new("/Users/me"), home), "~");
+        assert_eq!(abbreviate_home_in(Path::new("/Users/me/"), home), "~");
+        assert_eq!(
+            abbreviate_home_in(Path::new("/Users/meadow/.zshrc"), home),
+            "/Users/meadow/.zshrc"
+        );
+        assert_eq!(abbreviate_home_in(Path::new("/tmp/x"), home), "/tmp/x");
+    }
+
+    #[test]
+    fn env_editor_keeps_vscode_goto_flag() {
+        assert_eq!(
+            with_vscode_goto("code", vec!["-w".to_string()]),
+            vec!["-w", "-g"]
+        );
+        assert_eq!(
+            with_vscode_goto("/usr/local/bin/code", vec!["--goto".to_string()]),
+            vec!["--goto"]
+        );
+        assert_eq!(with_vscode_goto("nvim", Vec::new()), Vec::<String>::new());
+    }
+
+    #[test]
+    fn read_only_flag_only_added_for_known_editors() {
+        assert_eq!(
+            with_read_only_flag("/opt/homebrew/bin/nvim", Vec::new()),
+            vec!["-R"]
+        );
+        assert_eq!(
+            with_read_only_flag("vim", vec!["-R".to_string()]),
+            vec!["-R"]
+        );
+        assert_eq!(with_read_only_flag("nano", Vec::new()), vec!["-v"]);
+        assert_eq!(
+            with_read_only_flag("code", Vec::new()),
+            Vec::<String>::new()
+        );
+    }
 }
@@ -2,30 +2,69 @@
 use crate::Mux;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 static COUNT: AtomicUsize = AtomicUsize::new(0);
 
+/// Reasons recorded by `Activity::with_reason`, for debugging what is
+/// keeping the frontend alive. Bounded so that a leak can't grow it
+/// without limit; activities beyond the cap are still counted.
+static REASONS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+const MAX_REASONS: usize = 64;
+
 /// Create and hold on to an Activity while you are processing
 /// the direct result of a user initiated action, such as preparing
 /// to open a window.
 /// Once you have opened the window, drop the activity.
 /// The activity is used to keep the frontend alive even if there
 /// may be no windows present in the mux.
-pub struct Activity {}
+pub struct Activity {
+    reason: Option<&'static str>,
+}
 
 impl Activity {
     pub fn new() -> Self {
         COUNT.fetch_add(1, Ordering::SeqCst);
-        Self {}
+        Self { reason: None }
+    }
+
+    /// Like `new`, but records `reason` so that it shows up in
+    /// `current_reasons` until the activity is dropped.
+    pub fn with_reason(reason: &'static str) -> Self {
+        let mut activity = Self::new();
+        if let Ok(mut reasons) = REASONS.lock() {
+            if reasons.len() < MAX_REASONS {
+                reasons.push(reason);
+                activity.reason = Some(reason);
+            }
+        }
+        activity
     }
 
     pub fn count() -> usize {
         COUNT.load(Ordering::SeqCst)
     }
+
+    /// Returns the reasons of the live activities that were created
+    /// via `with_reason`, oldest first.
+    pub fn current_reasons() -> Vec<&'static str> {
+        REASONS
+            .lock()
+            .map(|reasons| reasons.clone())
+            .unwrap_or_default()
+    }
 }
 
 impl Drop for Activity {
     fn drop(&mut self) {
+        if let Some(reason) = self.reason.take() {
+            if let Ok(mut reasons) = REASONS.lock() {
+                if let Some(idx) = reasons.iter().position(|r| *r == reason) {
+                    reasons.remove(idx);
+                }
+            }
+        }
+
         let prev = COUNT.fetch_sub(1, Ordering::SeqCst);
         let remaining = prev.saturating_sub(1);
         log::trace!("Activity dropped; remaining={remaining}; scheduling prune_dead_windows");
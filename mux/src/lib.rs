@@ -576,7 +576,7 @@ fn read_from_pane_pty(
                 let decoded = if let Some(pane) = pane.upgrade() {
                     decoder.decode(pane.get_encoding(), &buf[..size])
                 } else {
-                    buf[..size].to_vec()
+                    std::borrow::Cow::Borrowed(&buf[..size])
                 };
                 if let Err(err) = tx.write_all(&decoded) {
                     error!(
@@ -589,6 +589,15 @@ fn read_from_pane_pty(
         }
     }
 
+    // Surface any partial character or escape left at the end of the stream
+    // instead of silently dropping it.
+    let trailing = decoder.finish();
+    if !trailing.is_empty() {
+        if let Err(err) = tx.write_all(&trailing) {
+            log::trace!("read_pty failed to flush trailing bytes: pane {pane_id} {err:?}");
+        }
+    }
+
     if pty_fatal {
         // The pty fd is gone (EBADF/EIO): no output can arrive, no input can be sent.
         // Force-remove the pane regardless of exit_behavior — holding a completely
@@ -1201,7 +1210,11 @@ impl Mux {
 
     pub fn prune_dead_windows(&self) {
         if Activity::count() > 0 {
-            log::trace!("prune_dead_windows: Activity::count={}", Activity::count());
+            log::trace!(
+                "prune_dead_windows: Activity::count={} reasons={:?}",
+                Activity::count(),
+                Activity::current_reasons()
+            );
             return;
         }
         let live_tab_ids: Vec<TabId> = self.tabs.read().keys().cloned().collect();
@@ -1292,7 +1305,7 @@ impl Mux {
         self.windows.write().insert(window_id, window);
         MuxWindowBuilder {
             window_id,
-            activity: Some(Activity::new()),
+            activity: Some(Activity::with_reason("window builder")),
             notified: false,
         }
     }
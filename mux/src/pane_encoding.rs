@@ -1,7 +1,12 @@
 use config::keyassignment::PaneEncoding;
 use encoding_rs::Encoding;
+use std::borrow::Cow;
 
 const MAX_TRAILING_ENCODED_BYTES: usize = 4;
+/// Escape sequences are passed through verbatim, so long ones (Sixel images,
+/// tmux passthrough) are flushed in chunks of this size rather than held
+/// until the terminator arrives.
+const MAX_BUFFERED_ESCAPE_BYTES: usize = 4096;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum EscapeState {
@@ -23,7 +28,8 @@ impl Default for EscapeState {
 fn get_encoding(encoding: PaneEncoding) -> Option<&'static Encoding> {
     match encoding {
         PaneEncoding::Utf8 => None,
-        PaneEncoding::Gbk => Some(encoding_rs::GBK),
+        // encoding_rs unifies GB2312 into GBK, its superset.
+        PaneEncoding::Gbk | PaneEncoding::Gb2312 => Some(encoding_rs::GBK),
         PaneEncoding::Gb18030 => Some(encoding_rs::GB18030),
         PaneEncoding::Big5 => Some(encoding_rs::BIG5),
         PaneEncoding::EucKr => Some(encoding_rs::EUC_KR),
@@ -87,6 +93,22 @@ fn begin_escape(state: &mut EscapeState, escape_bytes: &mut Vec<u8>, byte: u8) {
     };
 }
 
+/// Feeds one byte of an in-progress escape sequence, flushing the buffered
+/// bytes to `output` when the sequence ends or the buffer fills up.
+fn continue_escape(
+    state: &mut EscapeState,
+    escape_bytes: &mut Vec<u8>,
+    byte: u8,
+    output: &mut Vec<u8>,
+) {
+    escape_bytes.push(byte);
+    *state = advance_escape(*state, byte);
+    if *state == EscapeState::Ground || escape_bytes.len() >= MAX_BUFFERED_ESCAPE_BYTES {
+        output.extend_from_slice(escape_bytes);
+        escape_bytes.clear();
+    }
+}
+
 pub fn decode_bytes_to_string(encoding: PaneEncoding, raw: &[u8]) -> String {
     if let Ok(text) = std::str::from_utf8(raw) {
         return text.to_string();
@@ -121,7 +143,9 @@ impl Default for PaneInputEncoder {
 }
 
 impl PaneInputEncoder {
-    pub fn encode(&mut self, encoding: PaneEncoding, data: &[u8]) -> Vec<u8> {
+    /// Converts UTF-8 input to `encoding`. UTF-8 panes borrow `data` as-is,
+    /// so the common case never copies.
+    pub fn encode<'a>(&mut self, encoding: PaneEncoding, data: &'a [u8]) -> Cow<'a, [u8]> {
         if self.encoding != encoding {
             self.encoding = encoding;
             self.state = EscapeState::Ground;
@@ -130,7 +154,7 @@ impl PaneInputEncoder {
         }
 
         if encoding == PaneEncoding::Utf8 {
-            return data.to_vec();
+            return Cow::Borrowed(data);
         }
 
         let mut output = Vec::with_capacity(data.len());
@@ -147,12 +171,8 @@ impl PaneInputEncoder {
             }
 
             if self.state != EscapeState::Ground {
-                self.escape_bytes.push(byte);
-                self.state = advance_escape(self.state, byte);
-
+                continue_escape(&mut self.state, &mut self.escape_bytes, byte, &mut output);
                 if self.state == EscapeState::Ground {
-                    output.extend_from_slice(&self.escape_bytes);
-                    self.escape_bytes.clear();
                     text_start = idx + 1;
                 }
             }
@@ -162,6 +182,19 @@ impl PaneInputEncoder {
             self.encode_text(encoding, &data[text_start..], &mut output);
         }
 
+        Cow::Owned(output)
+    }
+
+    /// Emits anything still buffered at the end of the stream. An incomplete
+    /// UTF-8 sequence becomes `?`, matching how `encode` treats invalid input.
+    pub fn finish(&mut self) -> Vec<u8> {
+        let mut output = Vec::new();
+        if !self.pending_utf8.is_empty() {
+            self.pending_utf8.clear();
+            output.push(b'?');
+        }
+        output.append(&mut self.escape_bytes);
+        self.state = EscapeState::Ground;
         output
     }
 
@@ -228,7 +261,9 @@ impl Default for PaneOutputDecoder {
 }
 
 impl PaneOutputDecoder {
-    pub fn decode(&mut self, encoding: PaneEncoding, data: &[u8]) -> Vec<u8> {
+    /// Converts pane output in `encoding` to UTF-8. UTF-8 panes borrow
+    /// `data` as-is, so the common case never copies.
+    pub fn decode<'a>(&mut self, encoding: PaneEncoding, data: &'a [u8]) -> Cow<'a, [u8]> {
         if self.encoding != encoding {
             self.encoding = encoding;
             self.state = EscapeState::Ground;
@@ -237,7 +272,7 @@ impl PaneOutputDecoder {
         }
 
         if encoding == PaneEncoding::Utf8 {
-            return data.to_vec();
+            return Cow::Borrowed(data);
         }
 
         let mut output = Vec::with_capacity(data.len());
@@ -254,11 +289,8 @@ impl PaneOutputDecoder {
             }
 
             if self.state != EscapeState::Ground {
-                self.escape_bytes.push(byte);
-                self.state = advance_escape(self.state, byte);
+                continue_escape(&mut self.state, &mut self.escape_bytes, byte, &mut output);
                 if self.state == EscapeState::Ground {
-                    output.extend_from_slice(&self.escape_bytes);
-                    self.escape_bytes.clear();
                     text_start = idx + 1;
                 }
             }
@@ -268,6 +300,20 @@ impl PaneOutputDecoder {
             self.decode_text(encoding, &data[text_start..], &mut output);
         }
 
+        Cow::Owned(output)
+    }
+
+    /// Emits anything still buffered at the end of the stream, so a trailing
+    /// partial character shows up as U+FFFD instead of being dropped.
+    pub fn finish(&mut self) -> Vec<u8> {
+        let pending = std::mem::take(&mut self.pending_encoded);
+        let mut output = match get_encoding(self.encoding) {
+            _ if pending.is_empty() => Vec::new(),
+            Some(enc) => enc.decode(&pending).0.as_bytes().to_vec(),
+            None => String::from_utf8_lossy(&pending).as_bytes().to_vec(),
+        };
+        output.append(&mut self.escape_bytes);
+        self.state = EscapeState::Ground;
         output
     }
 
@@ -302,8 +348,66 @@ impl PaneOutputDecoder {
             return;
         }
 
-        let (decoded, _, _) = enc.decode(&pending);
+        // A malformed byte earlier on blocks every clean split. Decode up to
+        // it lossily, but hold back a partial character at the end so it can
+        // still complete on the next call instead of becoming U+FFFD.
+        let split = pending.len() - incomplete_tail_len(enc, &pending);
+        let (decoded, _, _) = enc.decode(&pending[..split]);
         output.extend_from_slice(decoded.as_bytes());
+        self.pending_encoded.extend_from_slice(&pending[split..]);
+    }
+}
+
+/// Length of the longest suffix of `bytes`, up to `MAX_TRAILING_ENCODED_BYTES`,
+/// that is the valid start of a character still waiting for more bytes.
+fn incomplete_tail_len(enc: &'static Encoding, bytes: &[u8]) -> usize {
+    let longest = MAX_TRAILING_ENCODED_BYTES.min(bytes.len().saturating_sub(1));
+    (1..=longest)
+        .rev()
+        .find(|&len| {
+            let tail = &bytes[bytes.len() - len..];
+            let mut decoder = enc.new_decoder_without_bom_handling();
+            let mut decoded = String::with_capacity(
+                decoder
+                    .max_utf8_buffer_length_without_replacement(tail.len())
+                    .unwrap_or(16),
+            );
+            let (result, read) =
+                decoder.decode_to_string_without_replacement(tail, &mut decoded, false);
+            result == encoding_rs::DecoderResult::InputEmpty
+                && read == tail.len()
+                && decoded.is_empty()
+        })
+        .unwrap_or(0)
+}
+
+/// One-shot conversions for a complete, self-contained buffer. Each call
+/// uses a fresh encoder or decoder and flushes it with `finish`, so a partial
+/// character at the end is resolved immediately rather than held for more
+/// input. Streamed chunks must go through `PaneInputEncoder` and
+/// `PaneOutputDecoder` instead.
+pub trait PaneEncodingExt {
+    /// Decodes `raw` from this encoding to UTF-8.
+    fn transcode_to_utf8(&self, raw: &[u8]) -> String;
+    /// Encodes UTF-8 `text` into this encoding.
+    fn transcode_from_utf8(&self, text: &str) -> Vec<u8>;
+}
+
+impl PaneEncodingExt for PaneEncoding {
+    fn transcode_to_utf8(&self, raw: &[u8]) -> String {
+        let mut decoder = PaneOutputDecoder::default();
+        let mut decoded = decoder.decode(*self, raw).into_owned();
+        decoded.extend(decoder.finish());
+        // UTF-8 panes pass bytes through unchecked.
+        String::from_utf8(decoded)
+            .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned())
+    }
+
+    fn transcode_from_utf8(&self, text: &str) -> Vec<u8> {
+        let mut encoder = PaneInputEncoder::default();
+        let mut encoded = encoder.encode(*self, text.as_bytes()).into_owned();
+        encoded.extend(encoder.finish());
+        encoded
     }
 }
 
@@ -329,15 +433,60 @@ mod tests {
         assert_eq!(decoder.decode(PaneEncoding::Utf8, data), data.to_vec());
     }
 
+    #[test]
+    fn utf8_passthrough_borrows_input() {
+        let mut encoder = PaneInputEncoder::default();
+        let mut decoder = PaneOutputDecoder::default();
+        let data = "hello 世界".as_bytes();
+
+        let encoded = encoder.encode(PaneEncoding::Utf8, data);
+        assert!(matches!(encoded, Cow::Borrowed(b) if b.as_ptr() == data.as_ptr()));
+        let decoded = decoder.decode(PaneEncoding::Utf8, data);
+        assert!(matches!(decoded, Cow::Borrowed(b) if b.as_ptr() == data.as_ptr()));
+
+        assert!(matches!(
+            decoder.decode(PaneEncoding::Gbk, data),
+            Cow::Owned(_)
+        ));
+    }
+
     #[test]
     fn supports_all_encodings_roundtrip() {
         round_trip_text(PaneEncoding::Gbk, "你好");
+        round_trip_text(PaneEncoding::Gb2312, "你好");
         round_trip_text(PaneEncoding::Gb18030, "你好世界");
         round_trip_text(PaneEncoding::Big5, "繁體中文");
         round_trip_text(PaneEncoding::EucKr, "안녕하세요");
         round_trip_text(PaneEncoding::ShiftJis, "こんにちは");
     }
 
+    fn transcode_round_trip(encoding: PaneEncoding, text: &str) {
+        let encoded = encoding.transcode_from_utf8(text);
+        assert_eq!(encoding.transcode_to_utf8(&encoded), text);
+    }
+
+    #[test]
+    fn transcode_supports_all_encodings_roundtrip() {
+        transcode_round_trip(PaneEncoding::Utf8, "hello 世界");
+        transcode_round_trip(PaneEncoding::Gbk, "你好");
+        transcode_round_trip(PaneEncoding::Gb2312, "你好");
+        transcode_round_trip(PaneEncoding::Gb18030, "你好世界");
+        transcode_round_trip(PaneEncoding::Big5, "繁體中文");
+        transcode_round_trip(PaneEncoding::EucKr, "안녕하세요");
+        transcode_round_trip(PaneEncoding::ShiftJis, "こんにちは");
+        transcode_round_trip(PaneEncoding::Gbk, "\x1b[31m红色\x1b[0m");
+    }
+
+    #[test]
+    fn transcode_flushes_incomplete_trailing_character() {
+        let mut encoded = PaneEncoding::Gbk.transcode_from_utf8("你好");
+        encoded.pop();
+        assert_eq!(PaneEncoding::Gbk.transcode_to_utf8(&encoded), "你\u{fffd}");
+
+        let truncated = &"好".as_bytes()[..2];
+        assert_eq!(PaneEncoding::Utf8.transcode_to_utf8(truncated), "\u{fffd}");
+    }
+
     #[test]
     fn preserves_csi_esc_bracket_sequences() {
         let mut decoder = PaneOutputDecoder::default();
@@ -362,6 +511,33 @@ mod tests {
         assert_eq!(decoder.decode(PaneEncoding::Gbk, dcs), dcs.to_vec());
     }
 
+    #[test]
+    fn long_dcs_payload_streams_through_in_chunks() {
+        let mut dcs = b"\x1bPq".to_vec();
+        dcs.extend((0..3 * MAX_BUFFERED_ESCAPE_BYTES).map(|i| b'?' + (i % 60) as u8));
+        dcs.extend_from_slice(b"\x1b\\");
+
+        let mut input = vec![0xc4, 0xe3];
+        input.extend_from_slice(&dcs);
+
+        let mut decoder = PaneOutputDecoder::default();
+        let mut output = Vec::new();
+        let mut flushed_before_end = false;
+        for chunk in input.chunks(61) {
+            output.extend_from_slice(&decoder.decode(PaneEncoding::Gbk, chunk));
+            assert!(decoder.escape_bytes.len() < MAX_BUFFERED_ESCAPE_BYTES);
+            if decoder.state != EscapeState::Ground && output.len() > 2 * MAX_BUFFERED_ESCAPE_BYTES
+            {
+                flushed_before_end = true;
+            }
+        }
+
+        assert!(flushed_before_end);
+        let mut expected = "你".as_bytes().to_vec();
+        expected.extend_from_slice(&dcs);
+        assert_eq!(output, expected);
+    }
+
     #[test]
     fn mixed_text_and_escape_decode() {
         let mut decoder = PaneOutputDecoder::default();
@@ -390,6 +566,52 @@ mod tests {
         assert_eq!(result2, "你".as_bytes().to_vec());
     }
 
+    #[test]
+    fn split_gb18030_four_byte_decode_is_buffered() {
+        let encoded = PaneEncoding::Gb18030.transcode_from_utf8("a😀");
+        assert_eq!(encoded.len(), 5);
+
+        let mut decoder = PaneOutputDecoder::default();
+        let result1 = decoder.decode(PaneEncoding::Gb18030, &encoded[..2]);
+        assert_eq!(result1, b"a".to_vec());
+        let result2 = decoder.decode(PaneEncoding::Gb18030, &encoded[2..]);
+        assert_eq!(result2, "😀".as_bytes().to_vec());
+    }
+
+    #[test]
+    fn malformed_input_keeps_trailing_partial_character() {
+        let encoded = PaneEncoding::Gb18030.transcode_from_utf8("😀");
+        let mut part1 = vec![0xff, b'x', b'y', b'z'];
+        part1.extend_from_slice(&encoded[..2]);
+
+        let mut decoder = PaneOutputDecoder::default();
+        let result1 = decoder.decode(PaneEncoding::Gb18030, &part1);
+        assert_eq!(result1, "\u{fffd}xyz".as_bytes().to_vec());
+        let result2 = decoder.decode(PaneEncoding::Gb18030, &encoded[2..]);
+        assert_eq!(result2, "😀".as_bytes().to_vec());
+    }
+
+    #[test]
+    fn finish_flushes_partial_character_as_replacement() {
+        let mut decoder = PaneOutputDecoder::default();
+        assert!(decoder.decode(PaneEncoding::Gbk, &[0xc4]).is_empty());
+        assert_eq!(decoder.finish(), "\u{fffd}".as_bytes().to_vec());
+        assert!(decoder.finish().is_empty());
+
+        let mut encoder = PaneInputEncoder::default();
+        assert!(encoder.encode(PaneEncoding::Gbk, &[0xe4]).is_empty());
+        assert_eq!(encoder.finish(), b"?".to_vec());
+        assert!(encoder.finish().is_empty());
+    }
+
+    #[test]
+    fn finish_flushes_unterminated_escape() {
+        let mut decoder = PaneOutputDecoder::default();
+        assert!(decoder.decode(PaneEncoding::Gbk, b"\x1b]0;tit").is_empty());
+        assert_eq!(decoder.finish(), b"\x1b]0;tit".to_vec());
+        assert_eq!(decoder.decode(PaneEncoding::Gbk, b"ok"), b"ok".to_vec());
+    }
+
     #[test]
     fn split_multibyte_encode_is_buffered() {
         let mut encoder = PaneInputEncoder::default();
@@ -74,6 +74,16 @@ pub enum ClipboardData {
     Files(Vec<PathBuf>),
 }
 
+/// Directory under `config::RUNTIME_DIR` holding clipboard images that were
+/// written out so they can be pasted as file paths.
+pub const CLIPBOARD_IMAGE_DIR: &str = "clipboard-images";
+
+/// True for an image file written from the clipboard by `read_data`, as
+/// opposed to a file the user copied.
+pub fn is_clipboard_image_path(path: &std::path::Path) -> bool {
+    path.parent() == Some(config::RUNTIME_DIR.join(CLIPBOARD_IMAGE_DIR).as_path())
+}
+
 impl Default for Clipboard {
     fn default() -> Self {
         Self::Clipboard
@@ -1,5 +1,5 @@
 use crate::macos::{nsstring, nsstring_to_str};
-use crate::ClipboardData;
+use crate::{ClipboardData, CLIPBOARD_IMAGE_DIR};
 use cocoa::appkit::{NSFilenamesPboardType, NSPasteboard, NSStringPboardType};
 use cocoa::base::*;
 use cocoa::foundation::NSArray;
@@ -10,15 +10,108 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-const PNG_PASTEBOARD_TYPE: &str = "public.png";
-const TIFF_PASTEBOARD_TYPE: &str = "public.tiff";
+/// Image pasteboard types we read, with the extension used for the temp
+/// file. Earlier entries win when an app offers several representations.
+const IMAGE_PASTEBOARD_TYPES: &[(&str, &str)] = &[
+    ("public.png", "png"),
+    ("public.tiff", "tiff"),
+    ("com.compuserve.gif", "gif"),
+    ("com.microsoft.bmp", "bmp"),
+];
+
+/// Returns the first non-empty representation in `IMAGE_PASTEBOARD_TYPES`
+/// order, with its file extension. `data_for_type` yields None for a type
+/// that is missing or empty.
+fn preferred_image_representation<D>(
+    mut data_for_type: impl FnMut(&'static str) -> Option<D>,
+) -> Option<(D, &'static str)> {
+    IMAGE_PASTEBOARD_TYPES
+        .iter()
+        .find_map(|&(uti, extension)| data_for_type(uti).map(|data| (data, extension)))
+}
+
+/// URL pasteboard type, e.g. a link copied from Safari's address bar. Its
+/// `public.url-name` title companion is not used until paste can carry more
+/// than plain text.
+const URL_PASTEBOARD_TYPE: &str = "public.url";
 const MAX_CLIPBOARD_IMAGE_BYTES: usize = 32 * 1024 * 1024;
-const CLIPBOARD_IMAGE_DIR: &str = "clipboard-images";
 const CLIPBOARD_IMAGE_FILE_PREFIX: &str = "clipboard-image-";
 const MAX_CLIPBOARD_IMAGE_FILES: usize = 128;
 const CLIPBOARD_IMAGE_RETENTION_SECS: u64 = 24 * 60 * 60;
+/// A failed prune is retried this many times in total, waiting a little
+/// longer each time, before it is left to the next paste.
+const CLIPBOARD_IMAGE_CLEANUP_MAX_ATTEMPTS: u32 = 3;
+const CLIPBOARD_IMAGE_CLEANUP_RETRY_DELAY: Duration = Duration::from_millis(250);
 static CLIPBOARD_IMAGE_CLEANUP_RUNNING: AtomicBool = AtomicBool::new(false);
 
+/// Runs `cleanup` on `dir` until it succeeds or `max_attempts` runs failed,
+/// sleeping `delay` times the attempt number in between. Returns the last
+/// result.
+async fn cleanup_with_retry<T>(
+    dir: &Path,
+    max_attempts: u32,
+    delay: Duration,
+    mut cleanup: impl FnMut(&Path) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    let mut attempt = 1;
+    loop {
+        match cleanup(dir) {
+            Err(err) if attempt < max_attempts => {
+                log::debug!(
+                    "pruning clipboard image cache at {} failed (attempt {attempt}/{max_attempts}), retrying: {err:#}",
+                    dir.display()
+                );
+                async_io::Timer::after(delay * attempt).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The URL to paste from a `public.url` entry, or None when it is blank.
+fn pasteboard_url_text(raw: &str) -> Option<&str> {
+    let url = raw.trim();
+    (!url.is_empty()).then_some(url)
+}
+
+/// True for a file name `write_image_to_runtime_dir` could have produced.
+fn is_clipboard_image_file_name(file_name: &str) -> bool {
+    file_name.starts_with(CLIPBOARD_IMAGE_FILE_PREFIX)
+        && Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                IMAGE_PASTEBOARD_TYPES
+                    .iter()
+                    .any(|&(_, extension)| extension == ext)
+            })
+}
+
+/// Copies clipboard text, cutting it at the last char boundary within
+/// `max_bytes` and appending a marker when it is longer than that.
+fn limit_clipboard_text(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    log::warn!(
+        "clipboard text is {} bytes, truncating paste to max_clipboard_text_bytes = {}",
+        text.len(),
+        max_bytes
+    );
+    format!(
+        "{}\n[clipboard text truncated at {} of {} bytes]",
+        &text[..end],
+        end,
+        text.len()
+    )
+}
+
 pub struct Clipboard {
     pasteboard: id,
 }
@@ -32,33 +125,56 @@ impl Clipboard {
         Clipboard { pasteboard }
     }
 
-    fn read_image_data(&self) -> anyhow::Result<Option<(Vec<u8>, &'static str)>> {
+    /// Every image on the pasteboard, one per pasteboard item and in
+    /// pasteboard order, so copying several images pastes them all. Each
+    /// item contributes its preferred representation per
+    /// `IMAGE_PASTEBOARD_TYPES`. Falls back to the pasteboard as a whole when
+    /// no item carries image data.
+    fn read_image_data(&self) -> anyhow::Result<Vec<(Vec<u8>, &'static str)>> {
+        let mut images = Vec::new();
         unsafe {
-            for (uti, extension) in [(PNG_PASTEBOARD_TYPE, "png"), (TIFF_PASTEBOARD_TYPE, "tiff")] {
-                let data: id = msg_send![self.pasteboard, dataForType:*nsstring(uti)];
-                if data.is_null() {
-                    continue;
-                }
-
-                let len: usize = msg_send![data, length];
-                if len == 0 {
-                    continue;
+            let items: id = msg_send![self.pasteboard, pasteboardItems];
+            if !items.is_null() {
+                for i in 0..items.count() {
+                    if let Some(image) = Self::read_image_from(items.objectAtIndex(i))? {
+                        images.push(image);
+                    }
                 }
-                anyhow::ensure!(
-                    len <= MAX_CLIPBOARD_IMAGE_BYTES,
-                    "clipboard image exceeds {} bytes",
-                    MAX_CLIPBOARD_IMAGE_BYTES
-                );
+            }
+            if images.is_empty() {
+                images.extend(Self::read_image_from(self.pasteboard)?);
+            }
+        }
 
-                let bytes: *const u8 = msg_send![data, bytes];
-                anyhow::ensure!(!bytes.is_null(), "clipboard image bytes returned null");
+        Ok(images)
+    }
 
-                let data = std::slice::from_raw_parts(bytes, len).to_vec();
-                return Ok(Some((data, extension)));
+    /// Reads the first image type `source` offers. `source` is the pasteboard
+    /// or one of its items, which both answer `dataForType:`.
+    unsafe fn read_image_from(source: id) -> anyhow::Result<Option<(Vec<u8>, &'static str)>> {
+        let found = preferred_image_representation(|uti| {
+            let data: id = msg_send![source, dataForType:*nsstring(uti)];
+            if data.is_null() {
+                return None;
             }
-        }
+            let len: usize = msg_send![data, length];
+            (len > 0).then_some((data, len))
+        });
+        let Some(((data, len), extension)) = found else {
+            return Ok(None);
+        };
 
-        Ok(None)
+        anyhow::ensure!(
+            len <= MAX_CLIPBOARD_IMAGE_BYTES,
+            "clipboard image exceeds {} bytes",
+            MAX_CLIPBOARD_IMAGE_BYTES
+        );
+
+        let bytes: *const u8 = msg_send![data, bytes];
+        anyhow::ensure!(!bytes.is_null(), "clipboard image bytes returned null");
+
+        let data = std::slice::from_raw_parts(bytes, len).to_vec();
+        Ok(Some((data, extension)))
     }
 
     fn write_image_to_runtime_dir(
@@ -75,11 +191,24 @@ impl Clipboard {
         {
             let dir_clone = dir.clone();
             promise::spawn::spawn(async move {
-                if let Err(err) = Self::cleanup_runtime_image_dir_static(&dir_clone) {
-                    log::warn!(
+                // The guard stays set across retries so concurrent pastes
+                // never stack a second cleanup.
+                match cleanup_with_retry(
+                    &dir_clone,
+                    CLIPBOARD_IMAGE_CLEANUP_MAX_ATTEMPTS,
+                    CLIPBOARD_IMAGE_CLEANUP_RETRY_DELAY,
+                    Self::cleanup_runtime_image_dir_static,
+                )
+                .await
+                {
+                    Ok((retained, removed)) => log::debug!(
+                        "pruned clipboard image cache at {}: retained {retained}, removed {removed}",
+                        dir_clone.display()
+                    ),
+                    Err(err) => log::warn!(
                         "failed to prune clipboard image cache at {}: {err:#}",
                         dir_clone.display()
-                    );
+                    ),
                 }
                 CLIPBOARD_IMAGE_CLEANUP_RUNNING.store(false, Ordering::Release);
             })
@@ -112,10 +241,14 @@ impl Clipboard {
         anyhow::bail!("failed to allocate unique clipboard image path")
     }
 
-    fn cleanup_runtime_image_dir_static(dir: &Path) -> anyhow::Result<()> {
+    /// Removes expired images and trims the cache to the newest
+    /// `MAX_CLIPBOARD_IMAGE_FILES`. Returns how many images were retained
+    /// and removed.
+    fn cleanup_runtime_image_dir_static(dir: &Path) -> anyhow::Result<(usize, usize)> {
         let retention = Duration::from_secs(CLIPBOARD_IMAGE_RETENTION_SECS);
         let now = SystemTime::now();
         let mut retained = Vec::new();
+        let mut removed = 0;
 
         for entry in std::fs::read_dir(dir)? {
             let entry = match entry {
@@ -137,7 +270,7 @@ impl Clipboard {
             let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
                 continue;
             };
-            if !file_name.starts_with(CLIPBOARD_IMAGE_FILE_PREFIX) {
+            if !is_clipboard_image_file_name(file_name) {
                 continue;
             }
 
@@ -157,13 +290,15 @@ impl Clipboard {
                 .map(|elapsed| elapsed > retention)
                 .unwrap_or(false);
             if expired {
-                if let Err(err) = std::fs::remove_file(&path) {
-                    if err.kind() != std::io::ErrorKind::NotFound {
+                match std::fs::remove_file(&path) {
+                    Ok(()) => removed += 1,
+                    Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
                         log::warn!(
                             "failed to remove expired clipboard image {}: {err:#}",
                             path.display()
                         );
                     }
+                    Err(_) => {}
                 }
                 continue;
             }
@@ -172,23 +307,25 @@ impl Clipboard {
         }
 
         if retained.len() <= MAX_CLIPBOARD_IMAGE_FILES {
-            return Ok(());
+            return Ok((retained.len(), removed));
         }
 
         retained.sort_by_key(|(modified, _)| *modified);
         let remove_count = retained.len().saturating_sub(MAX_CLIPBOARD_IMAGE_FILES);
-        for (_, path) in retained.into_iter().take(remove_count) {
-            if let Err(err) = std::fs::remove_file(&path) {
-                if err.kind() != std::io::ErrorKind::NotFound {
+        for (_, path) in retained.drain(..remove_count) {
+            match std::fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
                     log::warn!(
                         "failed to trim clipboard image cache file {}: {err:#}",
                         path.display()
                     );
                 }
+                Err(_) => {}
             }
         }
 
-        Ok(())
+        Ok((retained.len(), removed))
     }
 
     pub fn read_data(&self) -> anyhow::Result<ClipboardData> {
@@ -204,13 +341,29 @@ impl Clipboard {
             let s = self.pasteboard.stringForType(NSStringPboardType);
             if !s.is_null() {
                 let str = nsstring_to_str(s);
-                return Ok(ClipboardData::Text(str.to_string()));
+                let max_bytes = config::configuration().max_clipboard_text_bytes;
+                return Ok(ClipboardData::Text(limit_clipboard_text(str, max_bytes)));
+            }
+            let url = self
+                .pasteboard
+                .stringForType(*nsstring(URL_PASTEBOARD_TYPE));
+            if !url.is_null() {
+                if let Some(url) = pasteboard_url_text(nsstring_to_str(url)) {
+                    let max_bytes = config::configuration().max_clipboard_text_bytes;
+                    return Ok(ClipboardData::Text(limit_clipboard_text(url, max_bytes)));
+                }
             }
         }
 
-        if let Some((image_data, extension)) = self.read_image_data()? {
-            let path = self.write_image_to_runtime_dir(&image_data, extension)?;
-            return Ok(ClipboardData::Files(vec![path]));
+        let images = self.read_image_data()?;
+        if !images.is_empty() {
+            let paths = images
+                .iter()
+                .map(|(image_data, extension)| {
+                    self.write_image_to_runtime_dir(image_data, extension)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            return Ok(ClipboardData::Files(paths));
         }
 
         anyhow::bail!("pasteboard read returned empty");
@@ -253,3 +406,104 @@ impl Clipboard {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cleanup_with_retry, is_clipboard_image_file_name, limit_clipboard_text,
+        pasteboard_url_text, preferred_image_representation, Clipboard,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn pasteboard_url_is_trimmed_and_blank_is_skipped() {
+        assert_eq!(
+            pasteboard_url_text(" https://example.com/a?b=c\n"),
+            Some("https://example.com/a?b=c")
+        );
+        assert_eq!(pasteboard_url_text("  "), None);
+    }
+
+    #[test]
+    fn png_is_preferred_over_other_image_types() {
+        let offered = |types: &'static [&'static str]| {
+            move |uti: &'static str| types.contains(&uti).then_some(uti)
+        };
+
+        assert_eq!(
+            preferred_image_representation(offered(&["public.tiff", "public.png"])),
+            Some(("public.png", "png"))
+        );
+        assert_eq!(
+            preferred_image_representation(offered(&["com.microsoft.bmp", "com.compuserve.gif"])),
+            Some(("com.compuserve.gif", "gif"))
+        );
+        assert_eq!(
+            preferred_image_representation(offered(&["public.utf8-plain-text"])),
+            None
+        );
+    }
+
+    #[test]
+    fn cleanup_matches_every_image_extension() {
+        for name in [
+            "clipboard-image-1-2-0.png",
+            "clipboard-image-1-2-0.tiff",
+            "clipboard-image-1-2-0.gif",
+            "clipboard-image-1-2-0.bmp",
+        ] {
+            assert!(is_clipboard_image_file_name(name), "{name}");
+        }
+        assert!(!is_clipboard_image_file_name("clipboard-image-1-2-0.txt"));
+        assert!(!is_clipboard_image_file_name("notes.png"));
+    }
+
+    #[test]
+    fn cleanup_retries_until_the_cache_dir_is_readable() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let dir = root.path().join("clipboard-images");
+        let mut calls = 0;
+
+        // The first run fails because the directory is not there yet.
+        let result = async_io::block_on(cleanup_with_retry(&dir, 3, Duration::ZERO, |dir| {
+            calls += 1;
+            if calls == 2 {
+                std::fs::create_dir(dir).unwrap();
+                std::fs::write(dir.join("clipboard-image-1-2-0.png"), b"png").unwrap();
+            }
+            Clipboard::cleanup_runtime_image_dir_static(dir)
+        }));
+
+        assert_eq!(result.expect("second attempt succeeds"), (1, 0));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn cleanup_gives_up_after_max_attempts() {
+        let root = tempfile::tempdir().expect("tempdir");
+        let dir = root.path().join("missing");
+        let mut calls = 0;
+
+        let result = async_io::block_on(cleanup_with_retry(&dir, 3, Duration::ZERO, |dir| {
+            calls += 1;
+            Clipboard::cleanup_runtime_image_dir_static(dir)
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn small_text_is_untouched() {
+        assert_eq!(limit_clipboard_text("hello", 5), "hello");
+    }
+
+    #[test]
+    fn oversized_text_is_cut_on_a_char_boundary_with_marker() {
+        // "é" is two bytes, so a 2 byte limit cannot split it.
+        assert_eq!(
+            limit_clipboard_text("aé b", 2),
+            "a\n[clipboard text truncated at 1 of 5 bytes]"
+        );
+    }
+}